@@ -1,4 +1,5 @@
 // src/main.rs
+#[allow(clippy::all)]
 mod parser;
 use std::collections::HashMap;
 use std::fs::File;
@@ -13,6 +14,7 @@ pub enum Expr {
     Lisp(String, Box<Expr>, Box<Expr>), 
     Infix(String, Box<Expr>, Box<Expr>), 
     Not(Box<Expr>), // new
+    Ternary(String, Box<Expr>, Box<Expr>, Box<Expr>), // mux, maj
 }
 
 impl Expr {
@@ -22,15 +24,18 @@ impl Expr {
             Expr::Lisp(op, e1, e2) => format!("({} {} {})", op, e1.to_lisp(), e2.to_lisp()),
             Expr::Infix(op, e1, e2) => format!("({} {} {})", e1.to_lisp(), op, e2.to_lisp()),
             Expr::Not(e) => format!("(! {})", e.to_lisp()), 
+            Expr::Ternary(op, e1, e2, e3) => format!("({} {} {} {})", op, e1.to_lisp(), e2.to_lisp(), e3.to_lisp()),
         }
     }
 
+    #[allow(dead_code)]
     fn to_infix(&self) -> String {
         match self {
             Expr::VariableStr(n) => n.to_string(),
             Expr::Lisp(op, e1, e2) => format!("({} {} {})", e1.to_infix(), op, e2.to_infix()), 
             Expr::Infix(op, e1, e2) => format!("({} {} {})", e1.to_infix(), op, e2.to_infix()),
             Expr::Not(e) => format!("(! {})", e.to_infix()),
+            Expr::Ternary(op, e1, e2, e3) => format!("{}({}, {}, {})", op, e1.to_infix(), e2.to_infix(), e3.to_infix()),
         }
     }
 
@@ -59,6 +64,7 @@ impl Expr {
     //     }
     // }
 
+    #[allow(dead_code)]
     fn gather_and_expressions(&self, expressions: &mut HashMap<usize, String>, id: &mut usize, nested: bool) {
         match self {
             Expr::VariableStr(_) => {
@@ -86,6 +92,10 @@ impl Expr {
                 }
             },
             Expr::Not(e) => e.gather_and_expressions(expressions, id, true),
+            Expr::Ternary(..) => {
+                expressions.insert(*id, self.to_infix());
+                *id += 1;
+            },
         }
     }
 }
@@ -109,12 +119,8 @@ fn main() {
 
     match parser::ExprParser::new().parse(&input) {
         Ok(expr) => {
-            let output = if args.len() > 3 && args[3] == "lisp" {
-            expr.to_lisp()
-            
-            } else {
-                expr.to_lisp()
-            };
+            // infix2lisp always emits s-expressions
+            let output = expr.to_lisp();
 
             let mut file = File::create(output_file).unwrap_or_else(|err| {
                 eprintln!("Error creating file: {:?}", err);
@@ -1,5 +1,5 @@
 // auto-generated: "lalrpop 0.19.12"
-// sha3: 4f025a5db7d291c8e31ea40bdafeff745eca3ae69de0dd24b042192b19154d08
+// sha3: ca5a9fed4bec2ae28455cebe598c369087fafce35a13a95f74621a6db2dfc9b7
 use crate::Expr;
 #[allow(unused_extern_crates)]
 extern crate lalrpop_util as __lalrpop_util;
@@ -9,7 +9,7 @@ extern crate core;
 extern crate alloc;
 
 #[cfg_attr(rustfmt, rustfmt_skip)]
-mod __parse__And {
+mod __parse__Expr {
     #![allow(non_snake_case, non_camel_case_types, unused_mut, unused_variables, unused_imports, unused_parens, clippy::all)]
 
     use crate::Expr;
@@ -29,42 +29,88 @@ mod __parse__And {
     }
     const __ACTION: &[i8] = &[
         // State 0
-        0, 2, 0, 0, 0, 11,
+        16, 0, 3, 0, 0, 0, 0, 0, 17, 18, 0, 19, 20, 21, 22, 23,
         // State 1
-        0, 2, 0, 0, 0, 11,
+        16, 0, 3, 0, 0, 0, 0, 0, 17, 18, 0, 19, 20, 21, 22, 23,
         // State 2
-        0, 2, 0, 0, 0, 11,
+        16, 0, 3, 0, 0, 0, 0, 0, 17, 18, 0, 19, 20, 21, 22, 23,
         // State 3
-        0, 2, 0, 0, 0, 11,
+        16, 0, 3, 0, 0, 0, 0, 0, 17, 18, 0, 19, 20, 21, 22, 23,
         // State 4
-        0, 2, 0, 0, 0, 11,
+        0, 27, 0, 0, 28, 29, 0, 30, 0, 0, 31, 0, 0, 0, 0, 0,
         // State 5
-        0, 0, 0, 0, 0, 0,
+        16, 0, 8, 0, 0, 0, 0, 0, 17, 18, 0, 19, 20, 21, 22, 23,
         // State 6
-        -7, 0, -7, 3, -7, 0,
+        16, 0, 3, 0, 0, 0, 0, 0, 17, 18, 0, 19, 20, 21, 22, 23,
         // State 7
-        -9, 0, -9, 0, 4, 0,
+        16, 0, 3, 0, 0, 0, 0, 0, 17, 18, 0, 19, 20, 21, 22, 23,
         // State 8
-        5, 0, -2, 0, 0, 0,
+        16, 0, 3, 0, 0, 0, 0, 0, 17, 18, 0, 19, 20, 21, 22, 23,
         // State 9
-        -4, 0, -4, -4, -4, 0,
+        0, 27, 0, 0, 28, 29, 0, 30, 0, 0, 31, 0, 0, 0, 0, 0,
         // State 10
-        -10, 0, -10, -10, -10, 0,
+        16, 0, 3, 0, 0, 0, 0, 0, 17, 18, 0, 19, 20, 21, 22, 23,
         // State 11
-        0, 0, -3, 0, 0, 0,
+        16, 0, 3, 0, 0, 0, 0, 0, 17, 18, 0, 19, 20, 21, 22, 23,
         // State 12
-        0, 0, 17, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
         // State 13
-        -6, 0, -6, 0, -6, 0,
+        0, 0, 4, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
         // State 14
-        -8, 0, -8, 0, 0, 0,
+        0, -1, 0, -1, -1, -1, -1, -1, 0, 0, -1, 0, 0, 0, 0, 0,
         // State 15
-        0, 0, -1, 0, 0, 0,
+        -7, 0, -7, 0, 0, 0, 0, 0, -7, -7, 0, -7, -7, -7, -7, -7,
         // State 16
-        -5, 0, -5, -5, -5, 0,
+        0, 0, -14, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        // State 17
+        0, 0, -13, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        // State 18
+        0, -19, 0, -19, -19, -19, -19, -19, 0, 0, -19, 0, 0, 0, 0, 0,
+        // State 19
+        0, -16, 0, -16, -16, -16, -16, -16, 0, 0, -16, 0, 0, 0, 0, 0,
+        // State 20
+        0, -17, 0, -17, -17, -17, -17, -17, 0, 0, -17, 0, 0, 0, 0, 0,
+        // State 21
+        0, -15, 0, -15, -15, -15, -15, -15, 0, 0, -15, 0, 0, 0, 0, 0,
+        // State 22
+        0, -18, 0, -18, -18, -18, -18, -18, 0, 0, -18, 0, 0, 0, 0, 0,
+        // State 23
+        0, -2, 0, -2, -2, -2, -2, -2, 0, 0, -2, 0, 0, 0, 0, 0,
+        // State 24
+        0, -1, 0, 32, -1, -1, 0, -1, 0, 0, -1, 0, 0, 0, 0, 0,
+        // State 25
+        0, 0, 0, 0, 0, 0, 9, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        // State 26
+        -10, 0, -10, 0, 0, 0, 0, 0, -10, -10, 0, -10, -10, -10, -10, -10,
+        // State 27
+        -8, 0, -8, 0, 0, 0, 0, 0, -8, -8, 0, -8, -8, -8, -8, -8,
+        // State 28
+        -9, 0, -9, 0, 0, 0, 0, 0, -9, -9, 0, -9, -9, -9, -9, -9,
+        // State 29
+        -11, 0, -11, 0, 0, 0, 0, 0, -11, -11, 0, -11, -11, -11, -11, -11,
+        // State 30
+        -12, 0, -12, 0, 0, 0, 0, 0, -12, -12, 0, -12, -12, -12, -12, -12,
+        // State 31
+        0, -3, 0, -3, -3, -3, -3, -3, 0, 0, -3, 0, 0, 0, 0, 0,
+        // State 32
+        0, 0, 0, 35, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        // State 33
+        0, 0, 0, 0, 0, 0, 12, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        // State 34
+        0, -5, 0, -5, -5, -5, -5, -5, 0, 0, -5, 0, 0, 0, 0, 0,
+        // State 35
+        0, 0, 0, 38, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        // State 36
+        0, 0, 0, 39, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        // State 37
+        0, -5, 0, 40, -5, -5, 0, -5, 0, 0, -5, 0, 0, 0, 0, 0,
+        // State 38
+        0, -6, 0, -6, -6, -6, -6, -6, 0, 0, -6, 0, 0, 0, 0, 0,
+        // State 39
+        0, -4, 0, -4, -4, -4, -4, -4, 0, 0, -4, 0, 0, 0, 0, 0,
     ];
     fn __action(state: i8, integer: usize) -> i8 {
-        __ACTION[(state as usize) * 6 + integer]
+        __ACTION[(state as usize) * 16 + integer]
     }
     const __EOF_ACTION: &[i8] = &[
         // State 0
@@ -78,59 +124,123 @@ mod __parse__And {
         // State 4
         0,
         // State 5
-        -11,
+        0,
         // State 6
-        -7,
+        0,
         // State 7
-        -9,
+        0,
         // State 8
-        -2,
+        0,
         // State 9
-        -4,
+        0,
         // State 10
-        -10,
+        0,
         // State 11
         0,
         // State 12
-        0,
+        -20,
         // State 13
-        -6,
+        0,
         // State 14
-        -8,
-        // State 15
         -1,
+        // State 15
+        0,
         // State 16
+        0,
+        // State 17
+        0,
+        // State 18
+        -19,
+        // State 19
+        -16,
+        // State 20
+        -17,
+        // State 21
+        -15,
+        // State 22
+        -18,
+        // State 23
+        -2,
+        // State 24
+        0,
+        // State 25
+        0,
+        // State 26
+        0,
+        // State 27
+        0,
+        // State 28
+        0,
+        // State 29
+        0,
+        // State 30
+        0,
+        // State 31
+        -3,
+        // State 32
+        0,
+        // State 33
+        0,
+        // State 34
         -5,
+        // State 35
+        0,
+        // State 36
+        0,
+        // State 37
+        0,
+        // State 38
+        -6,
+        // State 39
+        -4,
     ];
     fn __goto(state: i8, nt: usize) -> i8 {
         match nt {
             0 => match state {
-                1 => 11,
-                4 => 15,
-                _ => 5,
+                2 => 4,
+                7 => 9,
+                0 => 12,
+                3 => 25,
+                6 => 32,
+                8 => 33,
+                10 => 35,
+                11 => 36,
+                _ => 23,
+            },
+            1 => match state {
+                2 | 7 => 5,
+                _ => 1,
             },
-            1 => 12,
-            2 => 6,
-            3 => match state {
-                2 => 13,
-                _ => 7,
+            2 => match state {
+                9 => 10,
+                _ => 6,
             },
+            3 => 13,
             4 => match state {
-                3 => 14,
-                _ => 8,
+                2 | 7 => 24,
+                _ => 14,
             },
-            5 => 9,
             _ => 0,
         }
     }
     fn __expected_tokens(__state: i8) -> alloc::vec::Vec<alloc::string::String> {
         const __TERMINAL: &[&str] = &[
+            r###""!""###,
             r###""&""###,
             r###""(""###,
             r###"")""###,
             r###""*""###,
             r###""+""###,
-            r###"r#"[a-z_][a-zA-Z0-9_]*"#"###,
+            r###"",""###,
+            r###""^""###,
+            r###""maj""###,
+            r###""mux""###,
+            r###""xnor""###,
+            r###"r#"[0-1]"#"###,
+            r###"r#"[a-zA-Z_][a-zA-Z0-9_]*"#"###,
+            r###"r#"[a-z]+[0-9]+\\[[0-9]+\\]"#"###,
+            r###"r#"[a-z]+\\[[0-9]+\\]"#"###,
+            r###"r#"[a-z_]+[A-Z0-9_]+\\|[a-zA-Z0-9_]+"#"###,
         ];
         __TERMINAL.iter().enumerate().filter_map(|(index, terminal)| {
             let next_state = __action(__state, index);
@@ -183,7 +293,7 @@ mod __parse__And {
 
         #[inline]
         fn error_action(&self, state: i8) -> i8 {
-            __action(state, 6 - 1)
+            __action(state, 16 - 1)
         }
 
         #[inline]
@@ -246,12 +356,22 @@ mod __parse__And {
     ) -> Option<usize>
     {
         match *__token {
-            Token(1, _) if true => Some(0),
-            Token(2, _) if true => Some(1),
-            Token(3, _) if true => Some(2),
-            Token(4, _) if true => Some(3),
-            Token(5, _) if true => Some(4),
-            Token(0, _) if true => Some(5),
+            Token(5, _) if true => Some(0),
+            Token(6, _) if true => Some(1),
+            Token(7, _) if true => Some(2),
+            Token(8, _) if true => Some(3),
+            Token(9, _) if true => Some(4),
+            Token(10, _) if true => Some(5),
+            Token(11, _) if true => Some(6),
+            Token(12, _) if true => Some(7),
+            Token(13, _) if true => Some(8),
+            Token(14, _) if true => Some(9),
+            Token(15, _) if true => Some(10),
+            Token(0, _) if true => Some(11),
+            Token(1, _) if true => Some(12),
+            Token(2, _) if true => Some(13),
+            Token(3, _) if true => Some(14),
+            Token(4, _) if true => Some(15),
             _ => None,
         }
     }
@@ -264,22 +384,22 @@ mod __parse__And {
     ) -> __Symbol<'input>
     {
         match __token_index {
-            0 | 1 | 2 | 3 | 4 | 5 => match __token {
-                Token(1, __tok0) | Token(2, __tok0) | Token(3, __tok0) | Token(4, __tok0) | Token(5, __tok0) | Token(0, __tok0) if true => __Symbol::Variant0(__tok0),
+            0 | 1 | 2 | 3 | 4 | 5 | 6 | 7 | 8 | 9 | 10 | 11 | 12 | 13 | 14 | 15 => match __token {
+                Token(5, __tok0) | Token(6, __tok0) | Token(7, __tok0) | Token(8, __tok0) | Token(9, __tok0) | Token(10, __tok0) | Token(11, __tok0) | Token(12, __tok0) | Token(13, __tok0) | Token(14, __tok0) | Token(15, __tok0) | Token(0, __tok0) | Token(1, __tok0) | Token(2, __tok0) | Token(3, __tok0) | Token(4, __tok0) if true => __Symbol::Variant0(__tok0),
                 _ => unreachable!(),
             },
             _ => unreachable!(),
         }
     }
-    pub struct AndParser {
+    pub struct ExprParser {
         builder: __lalrpop_util::lexer::MatcherBuilder,
         _priv: (),
     }
 
-    impl AndParser {
-        pub fn new() -> AndParser {
+    impl ExprParser {
+        pub fn new() -> ExprParser {
             let __builder = super::__intern_token::new_builder();
-            AndParser {
+            ExprParser {
                 builder: __builder,
                 _priv: (),
             }
@@ -346,12 +466,7 @@ mod __parse__And {
                 __reduce9(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
             }
             10 => {
-                // __And = And => ActionFn(4);
-                let __sym0 = __pop_Variant1(__symbols);
-                let __start = __sym0.0.clone();
-                let __end = __sym0.2.clone();
-                let __nt = super::__action4::<>(input, __sym0);
-                return Some(Ok(__nt));
+                __reduce10(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
             }
             11 => {
                 __reduce11(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
@@ -368,6 +483,23 @@ mod __parse__And {
             15 => {
                 __reduce15(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
             }
+            16 => {
+                __reduce16(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
+            }
+            17 => {
+                __reduce17(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
+            }
+            18 => {
+                __reduce18(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
+            }
+            19 => {
+                // __Expr = Expr => ActionFn(0);
+                let __sym0 = __pop_Variant1(__symbols);
+                let __start = __sym0.0.clone();
+                let __end = __sym0.2.clone();
+                let __nt = super::__action0::<>(input, __sym0);
+                return Some(Ok(__nt));
+            }
             _ => panic!("invalid action code {}", __action)
         };
         let __states_len = __states.len();
@@ -423,16 +555,13 @@ mod __parse__And {
         _: core::marker::PhantomData<(&'input ())>,
     ) -> (usize, usize)
     {
-        // And = Sum, "&", And => ActionFn(13);
-        assert!(__symbols.len() >= 3);
-        let __sym2 = __pop_Variant1(__symbols);
-        let __sym1 = __pop_Variant0(__symbols);
-        let __sym0 = __pop_Variant1(__symbols);
+        // Expr = VariableStr => ActionFn(14);
+        let __sym0 = __pop_Variant2(__symbols);
         let __start = __sym0.0.clone();
-        let __end = __sym2.2.clone();
-        let __nt = super::__action13::<>(input, __sym0, __sym1, __sym2);
+        let __end = __sym0.2.clone();
+        let __nt = super::__action14::<>(input, __sym0);
         __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (3, 0)
+        (1, 0)
     }
     pub(crate) fn __reduce1<
         'input,
@@ -443,13 +572,15 @@ mod __parse__And {
         _: core::marker::PhantomData<(&'input ())>,
     ) -> (usize, usize)
     {
-        // And = Sum => ActionFn(14);
-        let __sym0 = __pop_Variant1(__symbols);
+        // Expr = NotOp, Expr => ActionFn(15);
+        assert!(__symbols.len() >= 2);
+        let __sym1 = __pop_Variant1(__symbols);
+        let __sym0 = __pop_Variant2(__symbols);
         let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action14::<>(input, __sym0);
+        let __end = __sym1.2.clone();
+        let __nt = super::__action15::<>(input, __sym0, __sym1);
         __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 0)
+        (2, 0)
     }
     pub(crate) fn __reduce2<
         'input,
@@ -460,13 +591,16 @@ mod __parse__And {
         _: core::marker::PhantomData<(&'input ())>,
     ) -> (usize, usize)
     {
-        // Expr = And => ActionFn(15);
-        let __sym0 = __pop_Variant1(__symbols);
+        // Expr = "(", VariableStr, ")" => ActionFn(16);
+        assert!(__symbols.len() >= 3);
+        let __sym2 = __pop_Variant0(__symbols);
+        let __sym1 = __pop_Variant2(__symbols);
+        let __sym0 = __pop_Variant0(__symbols);
         let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action15::<>(input, __sym0);
+        let __end = __sym2.2.clone();
+        let __nt = super::__action16::<>(input, __sym0, __sym1, __sym2);
         __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 1)
+        (3, 0)
     }
     pub(crate) fn __reduce3<
         'input,
@@ -477,13 +611,21 @@ mod __parse__And {
         _: core::marker::PhantomData<(&'input ())>,
     ) -> (usize, usize)
     {
-        // Factor = Term => ActionFn(7);
-        let __sym0 = __pop_Variant2(__symbols);
+        // Expr = "(", NotOp, "(", Expr, Op, Expr, ")", ")" => ActionFn(17);
+        assert!(__symbols.len() >= 8);
+        let __sym7 = __pop_Variant0(__symbols);
+        let __sym6 = __pop_Variant0(__symbols);
+        let __sym5 = __pop_Variant1(__symbols);
+        let __sym4 = __pop_Variant2(__symbols);
+        let __sym3 = __pop_Variant1(__symbols);
+        let __sym2 = __pop_Variant0(__symbols);
+        let __sym1 = __pop_Variant2(__symbols);
+        let __sym0 = __pop_Variant0(__symbols);
         let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action7::<>(input, __sym0);
+        let __end = __sym7.2.clone();
+        let __nt = super::__action17::<>(input, __sym0, __sym1, __sym2, __sym3, __sym4, __sym5, __sym6, __sym7);
         __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 2)
+        (8, 0)
     }
     pub(crate) fn __reduce4<
         'input,
@@ -494,16 +636,18 @@ mod __parse__And {
         _: core::marker::PhantomData<(&'input ())>,
     ) -> (usize, usize)
     {
-        // Factor = "(", Expr, ")" => ActionFn(8);
-        assert!(__symbols.len() >= 3);
-        let __sym2 = __pop_Variant0(__symbols);
+        // Expr = "(", Expr, Op, Expr, ")" => ActionFn(18);
+        assert!(__symbols.len() >= 5);
+        let __sym4 = __pop_Variant0(__symbols);
+        let __sym3 = __pop_Variant1(__symbols);
+        let __sym2 = __pop_Variant2(__symbols);
         let __sym1 = __pop_Variant1(__symbols);
         let __sym0 = __pop_Variant0(__symbols);
         let __start = __sym0.0.clone();
-        let __end = __sym2.2.clone();
-        let __nt = super::__action8::<>(input, __sym0, __sym1, __sym2);
+        let __end = __sym4.2.clone();
+        let __nt = super::__action18::<>(input, __sym0, __sym1, __sym2, __sym3, __sym4);
         __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (3, 2)
+        (5, 0)
     }
     pub(crate) fn __reduce5<
         'input,
@@ -514,16 +658,21 @@ mod __parse__And {
         _: core::marker::PhantomData<(&'input ())>,
     ) -> (usize, usize)
     {
-        // Prod = Factor, "*", Prod => ActionFn(9);
-        assert!(__symbols.len() >= 3);
+        // Expr = TernOp, "(", Expr, ",", Expr, ",", Expr, ")" => ActionFn(19);
+        assert!(__symbols.len() >= 8);
+        let __sym7 = __pop_Variant0(__symbols);
+        let __sym6 = __pop_Variant1(__symbols);
+        let __sym5 = __pop_Variant0(__symbols);
+        let __sym4 = __pop_Variant1(__symbols);
+        let __sym3 = __pop_Variant0(__symbols);
         let __sym2 = __pop_Variant1(__symbols);
         let __sym1 = __pop_Variant0(__symbols);
-        let __sym0 = __pop_Variant1(__symbols);
+        let __sym0 = __pop_Variant2(__symbols);
         let __start = __sym0.0.clone();
-        let __end = __sym2.2.clone();
-        let __nt = super::__action9::<>(input, __sym0, __sym1, __sym2);
+        let __end = __sym7.2.clone();
+        let __nt = super::__action19::<>(input, __sym0, __sym1, __sym2, __sym3, __sym4, __sym5, __sym6, __sym7);
         __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (3, 3)
+        (8, 0)
     }
     pub(crate) fn __reduce6<
         'input,
@@ -534,13 +683,13 @@ mod __parse__And {
         _: core::marker::PhantomData<(&'input ())>,
     ) -> (usize, usize)
     {
-        // Prod = Factor => ActionFn(10);
-        let __sym0 = __pop_Variant1(__symbols);
+        // NotOp = "!" => ActionFn(13);
+        let __sym0 = __pop_Variant0(__symbols);
         let __start = __sym0.0.clone();
         let __end = __sym0.2.clone();
-        let __nt = super::__action10::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 3)
+        let __nt = super::__action13::<>(input, __sym0);
+        __symbols.push((__start, __Symbol::Variant2(__nt), __end));
+        (1, 1)
     }
     pub(crate) fn __reduce7<
         'input,
@@ -551,16 +700,13 @@ mod __parse__And {
         _: core::marker::PhantomData<(&'input ())>,
     ) -> (usize, usize)
     {
-        // Sum = Prod, "+", Sum => ActionFn(11);
-        assert!(__symbols.len() >= 3);
-        let __sym2 = __pop_Variant1(__symbols);
-        let __sym1 = __pop_Variant0(__symbols);
-        let __sym0 = __pop_Variant1(__symbols);
+        // Op = "*" => ActionFn(6);
+        let __sym0 = __pop_Variant0(__symbols);
         let __start = __sym0.0.clone();
-        let __end = __sym2.2.clone();
-        let __nt = super::__action11::<>(input, __sym0, __sym1, __sym2);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (3, 4)
+        let __end = __sym0.2.clone();
+        let __nt = super::__action6::<>(input, __sym0);
+        __symbols.push((__start, __Symbol::Variant2(__nt), __end));
+        (1, 2)
     }
     pub(crate) fn __reduce8<
         'input,
@@ -571,13 +717,13 @@ mod __parse__And {
         _: core::marker::PhantomData<(&'input ())>,
     ) -> (usize, usize)
     {
-        // Sum = Prod => ActionFn(12);
-        let __sym0 = __pop_Variant1(__symbols);
+        // Op = "+" => ActionFn(7);
+        let __sym0 = __pop_Variant0(__symbols);
         let __start = __sym0.0.clone();
         let __end = __sym0.2.clone();
-        let __nt = super::__action12::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 4)
+        let __nt = super::__action7::<>(input, __sym0);
+        __symbols.push((__start, __Symbol::Variant2(__nt), __end));
+        (1, 2)
     }
     pub(crate) fn __reduce9<
         'input,
@@ -588,13 +734,30 @@ mod __parse__And {
         _: core::marker::PhantomData<(&'input ())>,
     ) -> (usize, usize)
     {
-        // Term = r#"[a-z_][a-zA-Z0-9_]*"# => ActionFn(6);
+        // Op = "&" => ActionFn(8);
         let __sym0 = __pop_Variant0(__symbols);
         let __start = __sym0.0.clone();
         let __end = __sym0.2.clone();
-        let __nt = super::__action6::<>(input, __sym0);
+        let __nt = super::__action8::<>(input, __sym0);
+        __symbols.push((__start, __Symbol::Variant2(__nt), __end));
+        (1, 2)
+    }
+    pub(crate) fn __reduce10<
+        'input,
+    >(
+        input: &'input str,
+        __lookahead_start: Option<&usize>,
+        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
+        _: core::marker::PhantomData<(&'input ())>,
+    ) -> (usize, usize)
+    {
+        // Op = "^" => ActionFn(9);
+        let __sym0 = __pop_Variant0(__symbols);
+        let __start = __sym0.0.clone();
+        let __end = __sym0.2.clone();
+        let __nt = super::__action9::<>(input, __sym0);
         __symbols.push((__start, __Symbol::Variant2(__nt), __end));
-        (1, 5)
+        (1, 2)
     }
     pub(crate) fn __reduce11<
         'input,
@@ -605,13 +768,13 @@ mod __parse__And {
         _: core::marker::PhantomData<(&'input ())>,
     ) -> (usize, usize)
     {
-        // __Expr = Expr => ActionFn(5);
-        let __sym0 = __pop_Variant1(__symbols);
+        // Op = "xnor" => ActionFn(10);
+        let __sym0 = __pop_Variant0(__symbols);
         let __start = __sym0.0.clone();
         let __end = __sym0.2.clone();
-        let __nt = super::__action5::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 7)
+        let __nt = super::__action10::<>(input, __sym0);
+        __symbols.push((__start, __Symbol::Variant2(__nt), __end));
+        (1, 2)
     }
     pub(crate) fn __reduce12<
         'input,
@@ -622,13 +785,13 @@ mod __parse__And {
         _: core::marker::PhantomData<(&'input ())>,
     ) -> (usize, usize)
     {
-        // __Factor = Factor => ActionFn(1);
-        let __sym0 = __pop_Variant1(__symbols);
+        // TernOp = "mux" => ActionFn(11);
+        let __sym0 = __pop_Variant0(__symbols);
         let __start = __sym0.0.clone();
         let __end = __sym0.2.clone();
-        let __nt = super::__action1::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 8)
+        let __nt = super::__action11::<>(input, __sym0);
+        __symbols.push((__start, __Symbol::Variant2(__nt), __end));
+        (1, 3)
     }
     pub(crate) fn __reduce13<
         'input,
@@ -639,13 +802,13 @@ mod __parse__And {
         _: core::marker::PhantomData<(&'input ())>,
     ) -> (usize, usize)
     {
-        // __Prod = Prod => ActionFn(2);
-        let __sym0 = __pop_Variant1(__symbols);
+        // TernOp = "maj" => ActionFn(12);
+        let __sym0 = __pop_Variant0(__symbols);
         let __start = __sym0.0.clone();
         let __end = __sym0.2.clone();
-        let __nt = super::__action2::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 9)
+        let __nt = super::__action12::<>(input, __sym0);
+        __symbols.push((__start, __Symbol::Variant2(__nt), __end));
+        (1, 3)
     }
     pub(crate) fn __reduce14<
         'input,
@@ -656,13 +819,13 @@ mod __parse__And {
         _: core::marker::PhantomData<(&'input ())>,
     ) -> (usize, usize)
     {
-        // __Sum = Sum => ActionFn(3);
-        let __sym0 = __pop_Variant1(__symbols);
+        // VariableStr = r#"[a-z]+\\[[0-9]+\\]"# => ActionFn(1);
+        let __sym0 = __pop_Variant0(__symbols);
         let __start = __sym0.0.clone();
         let __end = __sym0.2.clone();
-        let __nt = super::__action3::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 10)
+        let __nt = super::__action1::<>(input, __sym0);
+        __symbols.push((__start, __Symbol::Variant2(__nt), __end));
+        (1, 4)
     }
     pub(crate) fn __reduce15<
         'input,
@@ -673,21 +836,70 @@ mod __parse__And {
         _: core::marker::PhantomData<(&'input ())>,
     ) -> (usize, usize)
     {
-        // __Term = Term => ActionFn(0);
-        let __sym0 = __pop_Variant2(__symbols);
+        // VariableStr = r#"[a-zA-Z_][a-zA-Z0-9_]*"# => ActionFn(2);
+        let __sym0 = __pop_Variant0(__symbols);
+        let __start = __sym0.0.clone();
+        let __end = __sym0.2.clone();
+        let __nt = super::__action2::<>(input, __sym0);
+        __symbols.push((__start, __Symbol::Variant2(__nt), __end));
+        (1, 4)
+    }
+    pub(crate) fn __reduce16<
+        'input,
+    >(
+        input: &'input str,
+        __lookahead_start: Option<&usize>,
+        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
+        _: core::marker::PhantomData<(&'input ())>,
+    ) -> (usize, usize)
+    {
+        // VariableStr = r#"[a-z]+[0-9]+\\[[0-9]+\\]"# => ActionFn(3);
+        let __sym0 = __pop_Variant0(__symbols);
+        let __start = __sym0.0.clone();
+        let __end = __sym0.2.clone();
+        let __nt = super::__action3::<>(input, __sym0);
+        __symbols.push((__start, __Symbol::Variant2(__nt), __end));
+        (1, 4)
+    }
+    pub(crate) fn __reduce17<
+        'input,
+    >(
+        input: &'input str,
+        __lookahead_start: Option<&usize>,
+        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
+        _: core::marker::PhantomData<(&'input ())>,
+    ) -> (usize, usize)
+    {
+        // VariableStr = r#"[a-z_]+[A-Z0-9_]+\\|[a-zA-Z0-9_]+"# => ActionFn(4);
+        let __sym0 = __pop_Variant0(__symbols);
+        let __start = __sym0.0.clone();
+        let __end = __sym0.2.clone();
+        let __nt = super::__action4::<>(input, __sym0);
+        __symbols.push((__start, __Symbol::Variant2(__nt), __end));
+        (1, 4)
+    }
+    pub(crate) fn __reduce18<
+        'input,
+    >(
+        input: &'input str,
+        __lookahead_start: Option<&usize>,
+        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
+        _: core::marker::PhantomData<(&'input ())>,
+    ) -> (usize, usize)
+    {
+        // VariableStr = r#"[0-1]"# => ActionFn(5);
+        let __sym0 = __pop_Variant0(__symbols);
         let __start = __sym0.0.clone();
         let __end = __sym0.2.clone();
-        let __nt = super::__action0::<>(input, __sym0);
+        let __nt = super::__action5::<>(input, __sym0);
         __symbols.push((__start, __Symbol::Variant2(__nt), __end));
-        (1, 11)
+        (1, 4)
     }
 }
-pub use self::__parse__And::AndParser;
-
+pub use self::__parse__Expr::ExprParser;
 #[cfg_attr(rustfmt, rustfmt_skip)]
-mod __parse__Expr {
-    #![allow(non_snake_case, non_camel_case_types, unused_mut, unused_variables, unused_imports, unused_parens, clippy::all)]
-
+mod __intern_token {
+    #![allow(unused_imports)]
     use crate::Expr;
     #[allow(unused_extern_crates)]
     extern crate lalrpop_util as __lalrpop_util;
@@ -695,3328 +907,27 @@ mod __parse__Expr {
     use self::__lalrpop_util::state_machine as __state_machine;
     extern crate core;
     extern crate alloc;
-    use self::__lalrpop_util::lexer::Token;
-    #[allow(dead_code)]
-    pub(crate) enum __Symbol<'input>
-     {
-        Variant0(&'input str),
-        Variant1(Expr),
-        Variant2(String),
-    }
-    const __ACTION: &[i8] = &[
-        // State 0
-        0, 2, 0, 0, 0, 12,
-        // State 1
-        0, 2, 0, 0, 0, 12,
-        // State 2
-        0, 2, 0, 0, 0, 12,
-        // State 3
-        0, 2, 0, 0, 0, 12,
-        // State 4
-        0, 2, 0, 0, 0, 12,
-        // State 5
-        0, 0, -3, 0, 0, 0,
-        // State 6
-        0, 0, 0, 0, 0, 0,
-        // State 7
-        -7, 0, -7, 3, -7, 0,
-        // State 8
-        -9, 0, -9, 0, 4, 0,
-        // State 9
-        5, 0, -2, 0, 0, 0,
-        // State 10
-        -4, 0, -4, -4, -4, 0,
-        // State 11
-        -10, 0, -10, -10, -10, 0,
-        // State 12
-        0, 0, 17, 0, 0, 0,
-        // State 13
-        -6, 0, -6, 0, -6, 0,
-        // State 14
-        -8, 0, -8, 0, 0, 0,
-        // State 15
-        0, 0, -1, 0, 0, 0,
-        // State 16
-        -5, 0, -5, -5, -5, 0,
-    ];
-    fn __action(state: i8, integer: usize) -> i8 {
-        __ACTION[(state as usize) * 6 + integer]
-    }
-    const __EOF_ACTION: &[i8] = &[
-        // State 0
-        0,
-        // State 1
-        0,
-        // State 2
-        0,
-        // State 3
-        0,
-        // State 4
-        0,
-        // State 5
-        -3,
-        // State 6
-        -12,
-        // State 7
-        -7,
-        // State 8
-        -9,
-        // State 9
-        -2,
-        // State 10
-        -4,
-        // State 11
-        -10,
-        // State 12
-        0,
-        // State 13
-        -6,
-        // State 14
-        -8,
-        // State 15
-        -1,
-        // State 16
-        -5,
-    ];
-    fn __goto(state: i8, nt: usize) -> i8 {
-        match nt {
-            0 => match state {
-                4 => 15,
-                _ => 5,
-            },
-            1 => match state {
-                1 => 12,
-                _ => 6,
-            },
-            2 => 7,
-            3 => match state {
-                2 => 13,
-                _ => 8,
-            },
-            4 => match state {
-                3 => 14,
-                _ => 9,
-            },
-            5 => 10,
-            _ => 0,
-        }
-    }
-    fn __expected_tokens(__state: i8) -> alloc::vec::Vec<alloc::string::String> {
-        const __TERMINAL: &[&str] = &[
-            r###""&""###,
-            r###""(""###,
-            r###"")""###,
-            r###""*""###,
-            r###""+""###,
-            r###"r#"[a-z_][a-zA-Z0-9_]*"#"###,
-        ];
-        __TERMINAL.iter().enumerate().filter_map(|(index, terminal)| {
-            let next_state = __action(__state, index);
-            if next_state == 0 {
-                None
-            } else {
-                Some(alloc::string::ToString::to_string(terminal))
-            }
-        }).collect()
-    }
-    pub(crate) struct __StateMachine<'input>
-    where 
-    {
-        input: &'input str,
-        __phantom: core::marker::PhantomData<(&'input ())>,
-    }
-    impl<'input> __state_machine::ParserDefinition for __StateMachine<'input>
-    where 
-    {
-        type Location = usize;
-        type Error = &'static str;
-        type Token = Token<'input>;
-        type TokenIndex = usize;
-        type Symbol = __Symbol<'input>;
-        type Success = Expr;
-        type StateIndex = i8;
-        type Action = i8;
-        type ReduceIndex = i8;
-        type NonterminalIndex = usize;
-
-        #[inline]
-        fn start_location(&self) -> Self::Location {
-              Default::default()
-        }
-
-        #[inline]
-        fn start_state(&self) -> Self::StateIndex {
-              0
-        }
-
-        #[inline]
-        fn token_to_index(&self, token: &Self::Token) -> Option<usize> {
-            __token_to_integer(token, core::marker::PhantomData::<(&())>)
-        }
-
-        #[inline]
-        fn action(&self, state: i8, integer: usize) -> i8 {
-            __action(state, integer)
-        }
-
-        #[inline]
-        fn error_action(&self, state: i8) -> i8 {
-            __action(state, 6 - 1)
-        }
-
-        #[inline]
-        fn eof_action(&self, state: i8) -> i8 {
-            __EOF_ACTION[state as usize]
-        }
-
-        #[inline]
-        fn goto(&self, state: i8, nt: usize) -> i8 {
-            __goto(state, nt)
-        }
-
-        fn token_to_symbol(&self, token_index: usize, token: Self::Token) -> Self::Symbol {
-            __token_to_symbol(token_index, token, core::marker::PhantomData::<(&())>)
-        }
-
-        fn expected_tokens(&self, state: i8) -> alloc::vec::Vec<alloc::string::String> {
-            __expected_tokens(state)
-        }
-
-        #[inline]
-        fn uses_error_recovery(&self) -> bool {
-            false
-        }
-
-        #[inline]
-        fn error_recovery_symbol(
-            &self,
-            recovery: __state_machine::ErrorRecovery<Self>,
-        ) -> Self::Symbol {
-            panic!("error recovery not enabled for this grammar")
-        }
-
-        fn reduce(
-            &mut self,
-            action: i8,
-            start_location: Option<&Self::Location>,
-            states: &mut alloc::vec::Vec<i8>,
-            symbols: &mut alloc::vec::Vec<__state_machine::SymbolTriple<Self>>,
-        ) -> Option<__state_machine::ParseResult<Self>> {
-            __reduce(
-                self.input,
-                action,
-                start_location,
-                states,
-                symbols,
-                core::marker::PhantomData::<(&())>,
-            )
-        }
-
-        fn simulate_reduce(&self, action: i8) -> __state_machine::SimulatedReduce<Self> {
-            panic!("error recovery not enabled for this grammar")
-        }
-    }
-    fn __token_to_integer<
-        'input,
-    >(
-        __token: &Token<'input>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> Option<usize>
-    {
-        match *__token {
-            Token(1, _) if true => Some(0),
-            Token(2, _) if true => Some(1),
-            Token(3, _) if true => Some(2),
-            Token(4, _) if true => Some(3),
-            Token(5, _) if true => Some(4),
-            Token(0, _) if true => Some(5),
-            _ => None,
-        }
-    }
-    fn __token_to_symbol<
-        'input,
-    >(
-        __token_index: usize,
-        __token: Token<'input>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> __Symbol<'input>
-    {
-        match __token_index {
-            0 | 1 | 2 | 3 | 4 | 5 => match __token {
-                Token(1, __tok0) | Token(2, __tok0) | Token(3, __tok0) | Token(4, __tok0) | Token(5, __tok0) | Token(0, __tok0) if true => __Symbol::Variant0(__tok0),
-                _ => unreachable!(),
-            },
-            _ => unreachable!(),
-        }
-    }
-    pub struct ExprParser {
-        builder: __lalrpop_util::lexer::MatcherBuilder,
-        _priv: (),
-    }
-
-    impl ExprParser {
-        pub fn new() -> ExprParser {
-            let __builder = super::__intern_token::new_builder();
-            ExprParser {
-                builder: __builder,
-                _priv: (),
-            }
-        }
-
-        #[allow(dead_code)]
-        pub fn parse<
-            'input,
-        >(
-            &self,
-            input: &'input str,
-        ) -> Result<Expr, __lalrpop_util::ParseError<usize, Token<'input>, &'static str>>
-        {
-            let mut __tokens = self.builder.matcher(input);
-            __state_machine::Parser::drive(
-                __StateMachine {
-                    input,
-                    __phantom: core::marker::PhantomData::<(&())>,
-                },
-                __tokens,
-            )
-        }
-    }
-    pub(crate) fn __reduce<
-        'input,
-    >(
-        input: &'input str,
-        __action: i8,
-        __lookahead_start: Option<&usize>,
-        __states: &mut alloc::vec::Vec<i8>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> Option<Result<Expr,__lalrpop_util::ParseError<usize, Token<'input>, &'static str>>>
-    {
-        let (__pop_states, __nonterminal) = match __action {
-            0 => {
-                __reduce0(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            1 => {
-                __reduce1(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            2 => {
-                __reduce2(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            3 => {
-                __reduce3(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            4 => {
-                __reduce4(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            5 => {
-                __reduce5(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            6 => {
-                __reduce6(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            7 => {
-                __reduce7(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            8 => {
-                __reduce8(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            9 => {
-                __reduce9(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            10 => {
-                __reduce10(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            11 => {
-                // __Expr = Expr => ActionFn(5);
-                let __sym0 = __pop_Variant1(__symbols);
-                let __start = __sym0.0.clone();
-                let __end = __sym0.2.clone();
-                let __nt = super::__action5::<>(input, __sym0);
-                return Some(Ok(__nt));
-            }
-            12 => {
-                __reduce12(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            13 => {
-                __reduce13(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            14 => {
-                __reduce14(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            15 => {
-                __reduce15(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            _ => panic!("invalid action code {}", __action)
-        };
-        let __states_len = __states.len();
-        __states.truncate(__states_len - __pop_states);
-        let __state = *__states.last().unwrap();
-        let __next_state = __goto(__state, __nonterminal);
-        __states.push(__next_state);
-        None
-    }
-    #[inline(never)]
-    fn __symbol_type_mismatch() -> ! {
-        panic!("symbol type mismatch")
-    }
-    fn __pop_Variant1<
-      'input,
-    >(
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>
-    ) -> (usize, Expr, usize)
-     {
-        match __symbols.pop() {
-            Some((__l, __Symbol::Variant1(__v), __r)) => (__l, __v, __r),
-            _ => __symbol_type_mismatch()
-        }
-    }
-    fn __pop_Variant2<
-      'input,
-    >(
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>
-    ) -> (usize, String, usize)
-     {
-        match __symbols.pop() {
-            Some((__l, __Symbol::Variant2(__v), __r)) => (__l, __v, __r),
-            _ => __symbol_type_mismatch()
-        }
-    }
-    fn __pop_Variant0<
-      'input,
-    >(
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>
-    ) -> (usize, &'input str, usize)
-     {
-        match __symbols.pop() {
-            Some((__l, __Symbol::Variant0(__v), __r)) => (__l, __v, __r),
-            _ => __symbol_type_mismatch()
-        }
-    }
-    pub(crate) fn __reduce0<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // And = Sum, "&", And => ActionFn(13);
-        assert!(__symbols.len() >= 3);
-        let __sym2 = __pop_Variant1(__symbols);
-        let __sym1 = __pop_Variant0(__symbols);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym2.2.clone();
-        let __nt = super::__action13::<>(input, __sym0, __sym1, __sym2);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (3, 0)
-    }
-    pub(crate) fn __reduce1<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // And = Sum => ActionFn(14);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action14::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 0)
-    }
-    pub(crate) fn __reduce2<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // Expr = And => ActionFn(15);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action15::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 1)
-    }
-    pub(crate) fn __reduce3<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // Factor = Term => ActionFn(7);
-        let __sym0 = __pop_Variant2(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action7::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 2)
-    }
-    pub(crate) fn __reduce4<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // Factor = "(", Expr, ")" => ActionFn(8);
-        assert!(__symbols.len() >= 3);
-        let __sym2 = __pop_Variant0(__symbols);
-        let __sym1 = __pop_Variant1(__symbols);
-        let __sym0 = __pop_Variant0(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym2.2.clone();
-        let __nt = super::__action8::<>(input, __sym0, __sym1, __sym2);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (3, 2)
-    }
-    pub(crate) fn __reduce5<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // Prod = Factor, "*", Prod => ActionFn(9);
-        assert!(__symbols.len() >= 3);
-        let __sym2 = __pop_Variant1(__symbols);
-        let __sym1 = __pop_Variant0(__symbols);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym2.2.clone();
-        let __nt = super::__action9::<>(input, __sym0, __sym1, __sym2);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (3, 3)
-    }
-    pub(crate) fn __reduce6<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // Prod = Factor => ActionFn(10);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action10::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 3)
-    }
-    pub(crate) fn __reduce7<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // Sum = Prod, "+", Sum => ActionFn(11);
-        assert!(__symbols.len() >= 3);
-        let __sym2 = __pop_Variant1(__symbols);
-        let __sym1 = __pop_Variant0(__symbols);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym2.2.clone();
-        let __nt = super::__action11::<>(input, __sym0, __sym1, __sym2);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (3, 4)
-    }
-    pub(crate) fn __reduce8<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // Sum = Prod => ActionFn(12);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action12::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 4)
-    }
-    pub(crate) fn __reduce9<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // Term = r#"[a-z_][a-zA-Z0-9_]*"# => ActionFn(6);
-        let __sym0 = __pop_Variant0(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action6::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant2(__nt), __end));
-        (1, 5)
-    }
-    pub(crate) fn __reduce10<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // __And = And => ActionFn(4);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action4::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 6)
-    }
-    pub(crate) fn __reduce12<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // __Factor = Factor => ActionFn(1);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action1::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 8)
-    }
-    pub(crate) fn __reduce13<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // __Prod = Prod => ActionFn(2);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action2::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 9)
-    }
-    pub(crate) fn __reduce14<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // __Sum = Sum => ActionFn(3);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action3::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 10)
-    }
-    pub(crate) fn __reduce15<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // __Term = Term => ActionFn(0);
-        let __sym0 = __pop_Variant2(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action0::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant2(__nt), __end));
-        (1, 11)
-    }
-}
-pub use self::__parse__Expr::ExprParser;
-
-#[cfg_attr(rustfmt, rustfmt_skip)]
-mod __parse__Factor {
-    #![allow(non_snake_case, non_camel_case_types, unused_mut, unused_variables, unused_imports, unused_parens, clippy::all)]
-
-    use crate::Expr;
-    #[allow(unused_extern_crates)]
-    extern crate lalrpop_util as __lalrpop_util;
-    #[allow(unused_imports)]
-    use self::__lalrpop_util::state_machine as __state_machine;
-    extern crate core;
-    extern crate alloc;
-    use self::__lalrpop_util::lexer::Token;
-    #[allow(dead_code)]
-    pub(crate) enum __Symbol<'input>
-     {
-        Variant0(&'input str),
-        Variant1(Expr),
-        Variant2(String),
-    }
-    const __ACTION: &[i8] = &[
-        // State 0
-        0, 2, 0, 0, 0, 8,
-        // State 1
-        0, 2, 0, 0, 0, 8,
-        // State 2
-        0, 2, 0, 0, 0, 8,
-        // State 3
-        0, 2, 0, 0, 0, 8,
-        // State 4
-        0, 2, 0, 0, 0, 8,
-        // State 5
-        0, 0, 0, 0, 0, 0,
-        // State 6
-        -4, 0, -4, -4, -4, 0,
-        // State 7
-        -10, 0, -10, -10, -10, 0,
-        // State 8
-        0, 0, -3, 0, 0, 0,
-        // State 9
-        0, 0, 14, 0, 0, 0,
-        // State 10
-        -7, 0, -7, 3, -7, 0,
-        // State 11
-        -9, 0, -9, 0, 4, 0,
-        // State 12
-        5, 0, -2, 0, 0, 0,
-        // State 13
-        -5, 0, -5, -5, -5, 0,
-        // State 14
-        -6, 0, -6, 0, -6, 0,
-        // State 15
-        -8, 0, -8, 0, 0, 0,
-        // State 16
-        0, 0, -1, 0, 0, 0,
-    ];
-    fn __action(state: i8, integer: usize) -> i8 {
-        __ACTION[(state as usize) * 6 + integer]
-    }
-    const __EOF_ACTION: &[i8] = &[
-        // State 0
-        0,
-        // State 1
-        0,
-        // State 2
-        0,
-        // State 3
-        0,
-        // State 4
-        0,
-        // State 5
-        -13,
-        // State 6
-        -4,
-        // State 7
-        -10,
-        // State 8
-        0,
-        // State 9
-        0,
-        // State 10
-        0,
-        // State 11
-        0,
-        // State 12
-        0,
-        // State 13
-        -5,
-        // State 14
-        0,
-        // State 15
-        0,
-        // State 16
-        0,
-    ];
-    fn __goto(state: i8, nt: usize) -> i8 {
-        match nt {
-            0 => match state {
-                4 => 16,
-                _ => 8,
-            },
-            1 => 9,
-            2 => match state {
-                1..=4 => 10,
-                _ => 5,
-            },
-            3 => match state {
-                2 => 14,
-                _ => 11,
-            },
-            4 => match state {
-                3 => 15,
-                _ => 12,
-            },
-            5 => 6,
-            _ => 0,
-        }
-    }
-    fn __expected_tokens(__state: i8) -> alloc::vec::Vec<alloc::string::String> {
-        const __TERMINAL: &[&str] = &[
-            r###""&""###,
-            r###""(""###,
-            r###"")""###,
-            r###""*""###,
-            r###""+""###,
-            r###"r#"[a-z_][a-zA-Z0-9_]*"#"###,
-        ];
-        __TERMINAL.iter().enumerate().filter_map(|(index, terminal)| {
-            let next_state = __action(__state, index);
-            if next_state == 0 {
-                None
-            } else {
-                Some(alloc::string::ToString::to_string(terminal))
-            }
-        }).collect()
-    }
-    pub(crate) struct __StateMachine<'input>
-    where 
-    {
-        input: &'input str,
-        __phantom: core::marker::PhantomData<(&'input ())>,
-    }
-    impl<'input> __state_machine::ParserDefinition for __StateMachine<'input>
-    where 
-    {
-        type Location = usize;
-        type Error = &'static str;
-        type Token = Token<'input>;
-        type TokenIndex = usize;
-        type Symbol = __Symbol<'input>;
-        type Success = Expr;
-        type StateIndex = i8;
-        type Action = i8;
-        type ReduceIndex = i8;
-        type NonterminalIndex = usize;
-
-        #[inline]
-        fn start_location(&self) -> Self::Location {
-              Default::default()
-        }
-
-        #[inline]
-        fn start_state(&self) -> Self::StateIndex {
-              0
-        }
-
-        #[inline]
-        fn token_to_index(&self, token: &Self::Token) -> Option<usize> {
-            __token_to_integer(token, core::marker::PhantomData::<(&())>)
-        }
-
-        #[inline]
-        fn action(&self, state: i8, integer: usize) -> i8 {
-            __action(state, integer)
-        }
-
-        #[inline]
-        fn error_action(&self, state: i8) -> i8 {
-            __action(state, 6 - 1)
-        }
-
-        #[inline]
-        fn eof_action(&self, state: i8) -> i8 {
-            __EOF_ACTION[state as usize]
-        }
-
-        #[inline]
-        fn goto(&self, state: i8, nt: usize) -> i8 {
-            __goto(state, nt)
-        }
-
-        fn token_to_symbol(&self, token_index: usize, token: Self::Token) -> Self::Symbol {
-            __token_to_symbol(token_index, token, core::marker::PhantomData::<(&())>)
-        }
-
-        fn expected_tokens(&self, state: i8) -> alloc::vec::Vec<alloc::string::String> {
-            __expected_tokens(state)
-        }
-
-        #[inline]
-        fn uses_error_recovery(&self) -> bool {
-            false
-        }
-
-        #[inline]
-        fn error_recovery_symbol(
-            &self,
-            recovery: __state_machine::ErrorRecovery<Self>,
-        ) -> Self::Symbol {
-            panic!("error recovery not enabled for this grammar")
-        }
-
-        fn reduce(
-            &mut self,
-            action: i8,
-            start_location: Option<&Self::Location>,
-            states: &mut alloc::vec::Vec<i8>,
-            symbols: &mut alloc::vec::Vec<__state_machine::SymbolTriple<Self>>,
-        ) -> Option<__state_machine::ParseResult<Self>> {
-            __reduce(
-                self.input,
-                action,
-                start_location,
-                states,
-                symbols,
-                core::marker::PhantomData::<(&())>,
-            )
-        }
-
-        fn simulate_reduce(&self, action: i8) -> __state_machine::SimulatedReduce<Self> {
-            panic!("error recovery not enabled for this grammar")
-        }
-    }
-    fn __token_to_integer<
-        'input,
-    >(
-        __token: &Token<'input>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> Option<usize>
-    {
-        match *__token {
-            Token(1, _) if true => Some(0),
-            Token(2, _) if true => Some(1),
-            Token(3, _) if true => Some(2),
-            Token(4, _) if true => Some(3),
-            Token(5, _) if true => Some(4),
-            Token(0, _) if true => Some(5),
-            _ => None,
-        }
-    }
-    fn __token_to_symbol<
-        'input,
-    >(
-        __token_index: usize,
-        __token: Token<'input>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> __Symbol<'input>
-    {
-        match __token_index {
-            0 | 1 | 2 | 3 | 4 | 5 => match __token {
-                Token(1, __tok0) | Token(2, __tok0) | Token(3, __tok0) | Token(4, __tok0) | Token(5, __tok0) | Token(0, __tok0) if true => __Symbol::Variant0(__tok0),
-                _ => unreachable!(),
-            },
-            _ => unreachable!(),
-        }
-    }
-    pub struct FactorParser {
-        builder: __lalrpop_util::lexer::MatcherBuilder,
-        _priv: (),
-    }
-
-    impl FactorParser {
-        pub fn new() -> FactorParser {
-            let __builder = super::__intern_token::new_builder();
-            FactorParser {
-                builder: __builder,
-                _priv: (),
-            }
-        }
-
-        #[allow(dead_code)]
-        pub fn parse<
-            'input,
-        >(
-            &self,
-            input: &'input str,
-        ) -> Result<Expr, __lalrpop_util::ParseError<usize, Token<'input>, &'static str>>
-        {
-            let mut __tokens = self.builder.matcher(input);
-            __state_machine::Parser::drive(
-                __StateMachine {
-                    input,
-                    __phantom: core::marker::PhantomData::<(&())>,
-                },
-                __tokens,
-            )
-        }
-    }
-    pub(crate) fn __reduce<
-        'input,
-    >(
-        input: &'input str,
-        __action: i8,
-        __lookahead_start: Option<&usize>,
-        __states: &mut alloc::vec::Vec<i8>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> Option<Result<Expr,__lalrpop_util::ParseError<usize, Token<'input>, &'static str>>>
-    {
-        let (__pop_states, __nonterminal) = match __action {
-            0 => {
-                __reduce0(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            1 => {
-                __reduce1(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            2 => {
-                __reduce2(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            3 => {
-                __reduce3(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            4 => {
-                __reduce4(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            5 => {
-                __reduce5(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            6 => {
-                __reduce6(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            7 => {
-                __reduce7(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            8 => {
-                __reduce8(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            9 => {
-                __reduce9(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            10 => {
-                __reduce10(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            11 => {
-                __reduce11(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            12 => {
-                // __Factor = Factor => ActionFn(1);
-                let __sym0 = __pop_Variant1(__symbols);
-                let __start = __sym0.0.clone();
-                let __end = __sym0.2.clone();
-                let __nt = super::__action1::<>(input, __sym0);
-                return Some(Ok(__nt));
-            }
-            13 => {
-                __reduce13(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            14 => {
-                __reduce14(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            15 => {
-                __reduce15(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            _ => panic!("invalid action code {}", __action)
-        };
-        let __states_len = __states.len();
-        __states.truncate(__states_len - __pop_states);
-        let __state = *__states.last().unwrap();
-        let __next_state = __goto(__state, __nonterminal);
-        __states.push(__next_state);
-        None
-    }
-    #[inline(never)]
-    fn __symbol_type_mismatch() -> ! {
-        panic!("symbol type mismatch")
-    }
-    fn __pop_Variant1<
-      'input,
-    >(
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>
-    ) -> (usize, Expr, usize)
-     {
-        match __symbols.pop() {
-            Some((__l, __Symbol::Variant1(__v), __r)) => (__l, __v, __r),
-            _ => __symbol_type_mismatch()
-        }
-    }
-    fn __pop_Variant2<
-      'input,
-    >(
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>
-    ) -> (usize, String, usize)
-     {
-        match __symbols.pop() {
-            Some((__l, __Symbol::Variant2(__v), __r)) => (__l, __v, __r),
-            _ => __symbol_type_mismatch()
-        }
-    }
-    fn __pop_Variant0<
-      'input,
-    >(
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>
-    ) -> (usize, &'input str, usize)
-     {
-        match __symbols.pop() {
-            Some((__l, __Symbol::Variant0(__v), __r)) => (__l, __v, __r),
-            _ => __symbol_type_mismatch()
-        }
-    }
-    pub(crate) fn __reduce0<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // And = Sum, "&", And => ActionFn(13);
-        assert!(__symbols.len() >= 3);
-        let __sym2 = __pop_Variant1(__symbols);
-        let __sym1 = __pop_Variant0(__symbols);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym2.2.clone();
-        let __nt = super::__action13::<>(input, __sym0, __sym1, __sym2);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (3, 0)
-    }
-    pub(crate) fn __reduce1<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // And = Sum => ActionFn(14);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action14::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 0)
-    }
-    pub(crate) fn __reduce2<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // Expr = And => ActionFn(15);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action15::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 1)
-    }
-    pub(crate) fn __reduce3<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // Factor = Term => ActionFn(7);
-        let __sym0 = __pop_Variant2(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action7::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 2)
-    }
-    pub(crate) fn __reduce4<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // Factor = "(", Expr, ")" => ActionFn(8);
-        assert!(__symbols.len() >= 3);
-        let __sym2 = __pop_Variant0(__symbols);
-        let __sym1 = __pop_Variant1(__symbols);
-        let __sym0 = __pop_Variant0(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym2.2.clone();
-        let __nt = super::__action8::<>(input, __sym0, __sym1, __sym2);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (3, 2)
-    }
-    pub(crate) fn __reduce5<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // Prod = Factor, "*", Prod => ActionFn(9);
-        assert!(__symbols.len() >= 3);
-        let __sym2 = __pop_Variant1(__symbols);
-        let __sym1 = __pop_Variant0(__symbols);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym2.2.clone();
-        let __nt = super::__action9::<>(input, __sym0, __sym1, __sym2);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (3, 3)
-    }
-    pub(crate) fn __reduce6<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // Prod = Factor => ActionFn(10);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action10::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 3)
-    }
-    pub(crate) fn __reduce7<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // Sum = Prod, "+", Sum => ActionFn(11);
-        assert!(__symbols.len() >= 3);
-        let __sym2 = __pop_Variant1(__symbols);
-        let __sym1 = __pop_Variant0(__symbols);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym2.2.clone();
-        let __nt = super::__action11::<>(input, __sym0, __sym1, __sym2);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (3, 4)
-    }
-    pub(crate) fn __reduce8<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // Sum = Prod => ActionFn(12);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action12::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 4)
-    }
-    pub(crate) fn __reduce9<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // Term = r#"[a-z_][a-zA-Z0-9_]*"# => ActionFn(6);
-        let __sym0 = __pop_Variant0(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action6::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant2(__nt), __end));
-        (1, 5)
-    }
-    pub(crate) fn __reduce10<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // __And = And => ActionFn(4);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action4::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 6)
-    }
-    pub(crate) fn __reduce11<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // __Expr = Expr => ActionFn(5);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action5::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 7)
-    }
-    pub(crate) fn __reduce13<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // __Prod = Prod => ActionFn(2);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action2::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 9)
-    }
-    pub(crate) fn __reduce14<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // __Sum = Sum => ActionFn(3);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action3::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 10)
-    }
-    pub(crate) fn __reduce15<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // __Term = Term => ActionFn(0);
-        let __sym0 = __pop_Variant2(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action0::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant2(__nt), __end));
-        (1, 11)
-    }
-}
-pub use self::__parse__Factor::FactorParser;
-
-#[cfg_attr(rustfmt, rustfmt_skip)]
-mod __parse__Prod {
-    #![allow(non_snake_case, non_camel_case_types, unused_mut, unused_variables, unused_imports, unused_parens, clippy::all)]
-
-    use crate::Expr;
-    #[allow(unused_extern_crates)]
-    extern crate lalrpop_util as __lalrpop_util;
-    #[allow(unused_imports)]
-    use self::__lalrpop_util::state_machine as __state_machine;
-    extern crate core;
-    extern crate alloc;
-    use self::__lalrpop_util::lexer::Token;
-    #[allow(dead_code)]
-    pub(crate) enum __Symbol<'input>
-     {
-        Variant0(&'input str),
-        Variant1(Expr),
-        Variant2(String),
-    }
-    const __ACTION: &[i8] = &[
-        // State 0
-        0, 2, 0, 0, 0, 9,
-        // State 1
-        0, 2, 0, 0, 0, 9,
-        // State 2
-        0, 2, 0, 0, 0, 9,
-        // State 3
-        0, 2, 0, 0, 0, 9,
-        // State 4
-        0, 2, 0, 0, 0, 9,
-        // State 5
-        -7, 0, -7, 3, -7, 0,
-        // State 6
-        0, 0, 0, 0, 0, 0,
-        // State 7
-        -4, 0, -4, -4, -4, 0,
-        // State 8
-        -10, 0, -10, -10, -10, 0,
-        // State 9
-        0, 0, -3, 0, 0, 0,
-        // State 10
-        0, 0, 15, 0, 0, 0,
-        // State 11
-        -9, 0, -9, 0, 4, 0,
-        // State 12
-        5, 0, -2, 0, 0, 0,
-        // State 13
-        -6, 0, -6, 0, -6, 0,
-        // State 14
-        -5, 0, -5, -5, -5, 0,
-        // State 15
-        -8, 0, -8, 0, 0, 0,
-        // State 16
-        0, 0, -1, 0, 0, 0,
-    ];
-    fn __action(state: i8, integer: usize) -> i8 {
-        __ACTION[(state as usize) * 6 + integer]
-    }
-    const __EOF_ACTION: &[i8] = &[
-        // State 0
-        0,
-        // State 1
-        0,
-        // State 2
-        0,
-        // State 3
-        0,
-        // State 4
-        0,
-        // State 5
-        -7,
-        // State 6
-        -14,
-        // State 7
-        -4,
-        // State 8
-        -10,
-        // State 9
-        0,
-        // State 10
-        0,
-        // State 11
-        0,
-        // State 12
-        0,
-        // State 13
-        -6,
-        // State 14
-        -5,
-        // State 15
-        0,
-        // State 16
-        0,
-    ];
-    fn __goto(state: i8, nt: usize) -> i8 {
-        match nt {
-            0 => match state {
-                4 => 16,
-                _ => 9,
-            },
-            1 => 10,
-            2 => 5,
-            3 => match state {
-                0 => 6,
-                2 => 13,
-                _ => 11,
-            },
-            4 => match state {
-                3 => 15,
-                _ => 12,
-            },
-            5 => 7,
-            _ => 0,
-        }
-    }
-    fn __expected_tokens(__state: i8) -> alloc::vec::Vec<alloc::string::String> {
-        const __TERMINAL: &[&str] = &[
-            r###""&""###,
-            r###""(""###,
-            r###"")""###,
-            r###""*""###,
-            r###""+""###,
-            r###"r#"[a-z_][a-zA-Z0-9_]*"#"###,
-        ];
-        __TERMINAL.iter().enumerate().filter_map(|(index, terminal)| {
-            let next_state = __action(__state, index);
-            if next_state == 0 {
-                None
-            } else {
-                Some(alloc::string::ToString::to_string(terminal))
-            }
-        }).collect()
-    }
-    pub(crate) struct __StateMachine<'input>
-    where 
-    {
-        input: &'input str,
-        __phantom: core::marker::PhantomData<(&'input ())>,
-    }
-    impl<'input> __state_machine::ParserDefinition for __StateMachine<'input>
-    where 
-    {
-        type Location = usize;
-        type Error = &'static str;
-        type Token = Token<'input>;
-        type TokenIndex = usize;
-        type Symbol = __Symbol<'input>;
-        type Success = Expr;
-        type StateIndex = i8;
-        type Action = i8;
-        type ReduceIndex = i8;
-        type NonterminalIndex = usize;
-
-        #[inline]
-        fn start_location(&self) -> Self::Location {
-              Default::default()
-        }
-
-        #[inline]
-        fn start_state(&self) -> Self::StateIndex {
-              0
-        }
-
-        #[inline]
-        fn token_to_index(&self, token: &Self::Token) -> Option<usize> {
-            __token_to_integer(token, core::marker::PhantomData::<(&())>)
-        }
-
-        #[inline]
-        fn action(&self, state: i8, integer: usize) -> i8 {
-            __action(state, integer)
-        }
-
-        #[inline]
-        fn error_action(&self, state: i8) -> i8 {
-            __action(state, 6 - 1)
-        }
-
-        #[inline]
-        fn eof_action(&self, state: i8) -> i8 {
-            __EOF_ACTION[state as usize]
-        }
-
-        #[inline]
-        fn goto(&self, state: i8, nt: usize) -> i8 {
-            __goto(state, nt)
-        }
-
-        fn token_to_symbol(&self, token_index: usize, token: Self::Token) -> Self::Symbol {
-            __token_to_symbol(token_index, token, core::marker::PhantomData::<(&())>)
-        }
-
-        fn expected_tokens(&self, state: i8) -> alloc::vec::Vec<alloc::string::String> {
-            __expected_tokens(state)
-        }
-
-        #[inline]
-        fn uses_error_recovery(&self) -> bool {
-            false
-        }
-
-        #[inline]
-        fn error_recovery_symbol(
-            &self,
-            recovery: __state_machine::ErrorRecovery<Self>,
-        ) -> Self::Symbol {
-            panic!("error recovery not enabled for this grammar")
-        }
-
-        fn reduce(
-            &mut self,
-            action: i8,
-            start_location: Option<&Self::Location>,
-            states: &mut alloc::vec::Vec<i8>,
-            symbols: &mut alloc::vec::Vec<__state_machine::SymbolTriple<Self>>,
-        ) -> Option<__state_machine::ParseResult<Self>> {
-            __reduce(
-                self.input,
-                action,
-                start_location,
-                states,
-                symbols,
-                core::marker::PhantomData::<(&())>,
-            )
-        }
-
-        fn simulate_reduce(&self, action: i8) -> __state_machine::SimulatedReduce<Self> {
-            panic!("error recovery not enabled for this grammar")
-        }
-    }
-    fn __token_to_integer<
-        'input,
-    >(
-        __token: &Token<'input>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> Option<usize>
-    {
-        match *__token {
-            Token(1, _) if true => Some(0),
-            Token(2, _) if true => Some(1),
-            Token(3, _) if true => Some(2),
-            Token(4, _) if true => Some(3),
-            Token(5, _) if true => Some(4),
-            Token(0, _) if true => Some(5),
-            _ => None,
-        }
-    }
-    fn __token_to_symbol<
-        'input,
-    >(
-        __token_index: usize,
-        __token: Token<'input>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> __Symbol<'input>
-    {
-        match __token_index {
-            0 | 1 | 2 | 3 | 4 | 5 => match __token {
-                Token(1, __tok0) | Token(2, __tok0) | Token(3, __tok0) | Token(4, __tok0) | Token(5, __tok0) | Token(0, __tok0) if true => __Symbol::Variant0(__tok0),
-                _ => unreachable!(),
-            },
-            _ => unreachable!(),
-        }
-    }
-    pub struct ProdParser {
-        builder: __lalrpop_util::lexer::MatcherBuilder,
-        _priv: (),
-    }
-
-    impl ProdParser {
-        pub fn new() -> ProdParser {
-            let __builder = super::__intern_token::new_builder();
-            ProdParser {
-                builder: __builder,
-                _priv: (),
-            }
-        }
-
-        #[allow(dead_code)]
-        pub fn parse<
-            'input,
-        >(
-            &self,
-            input: &'input str,
-        ) -> Result<Expr, __lalrpop_util::ParseError<usize, Token<'input>, &'static str>>
-        {
-            let mut __tokens = self.builder.matcher(input);
-            __state_machine::Parser::drive(
-                __StateMachine {
-                    input,
-                    __phantom: core::marker::PhantomData::<(&())>,
-                },
-                __tokens,
-            )
-        }
-    }
-    pub(crate) fn __reduce<
-        'input,
-    >(
-        input: &'input str,
-        __action: i8,
-        __lookahead_start: Option<&usize>,
-        __states: &mut alloc::vec::Vec<i8>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> Option<Result<Expr,__lalrpop_util::ParseError<usize, Token<'input>, &'static str>>>
-    {
-        let (__pop_states, __nonterminal) = match __action {
-            0 => {
-                __reduce0(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            1 => {
-                __reduce1(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            2 => {
-                __reduce2(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            3 => {
-                __reduce3(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            4 => {
-                __reduce4(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            5 => {
-                __reduce5(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            6 => {
-                __reduce6(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            7 => {
-                __reduce7(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            8 => {
-                __reduce8(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            9 => {
-                __reduce9(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            10 => {
-                __reduce10(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            11 => {
-                __reduce11(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            12 => {
-                __reduce12(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            13 => {
-                // __Prod = Prod => ActionFn(2);
-                let __sym0 = __pop_Variant1(__symbols);
-                let __start = __sym0.0.clone();
-                let __end = __sym0.2.clone();
-                let __nt = super::__action2::<>(input, __sym0);
-                return Some(Ok(__nt));
-            }
-            14 => {
-                __reduce14(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            15 => {
-                __reduce15(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            _ => panic!("invalid action code {}", __action)
-        };
-        let __states_len = __states.len();
-        __states.truncate(__states_len - __pop_states);
-        let __state = *__states.last().unwrap();
-        let __next_state = __goto(__state, __nonterminal);
-        __states.push(__next_state);
-        None
-    }
-    #[inline(never)]
-    fn __symbol_type_mismatch() -> ! {
-        panic!("symbol type mismatch")
-    }
-    fn __pop_Variant1<
-      'input,
-    >(
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>
-    ) -> (usize, Expr, usize)
-     {
-        match __symbols.pop() {
-            Some((__l, __Symbol::Variant1(__v), __r)) => (__l, __v, __r),
-            _ => __symbol_type_mismatch()
-        }
-    }
-    fn __pop_Variant2<
-      'input,
-    >(
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>
-    ) -> (usize, String, usize)
-     {
-        match __symbols.pop() {
-            Some((__l, __Symbol::Variant2(__v), __r)) => (__l, __v, __r),
-            _ => __symbol_type_mismatch()
-        }
-    }
-    fn __pop_Variant0<
-      'input,
-    >(
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>
-    ) -> (usize, &'input str, usize)
-     {
-        match __symbols.pop() {
-            Some((__l, __Symbol::Variant0(__v), __r)) => (__l, __v, __r),
-            _ => __symbol_type_mismatch()
-        }
-    }
-    pub(crate) fn __reduce0<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // And = Sum, "&", And => ActionFn(13);
-        assert!(__symbols.len() >= 3);
-        let __sym2 = __pop_Variant1(__symbols);
-        let __sym1 = __pop_Variant0(__symbols);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym2.2.clone();
-        let __nt = super::__action13::<>(input, __sym0, __sym1, __sym2);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (3, 0)
-    }
-    pub(crate) fn __reduce1<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // And = Sum => ActionFn(14);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action14::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 0)
-    }
-    pub(crate) fn __reduce2<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // Expr = And => ActionFn(15);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action15::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 1)
-    }
-    pub(crate) fn __reduce3<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // Factor = Term => ActionFn(7);
-        let __sym0 = __pop_Variant2(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action7::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 2)
-    }
-    pub(crate) fn __reduce4<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // Factor = "(", Expr, ")" => ActionFn(8);
-        assert!(__symbols.len() >= 3);
-        let __sym2 = __pop_Variant0(__symbols);
-        let __sym1 = __pop_Variant1(__symbols);
-        let __sym0 = __pop_Variant0(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym2.2.clone();
-        let __nt = super::__action8::<>(input, __sym0, __sym1, __sym2);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (3, 2)
-    }
-    pub(crate) fn __reduce5<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // Prod = Factor, "*", Prod => ActionFn(9);
-        assert!(__symbols.len() >= 3);
-        let __sym2 = __pop_Variant1(__symbols);
-        let __sym1 = __pop_Variant0(__symbols);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym2.2.clone();
-        let __nt = super::__action9::<>(input, __sym0, __sym1, __sym2);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (3, 3)
-    }
-    pub(crate) fn __reduce6<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // Prod = Factor => ActionFn(10);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action10::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 3)
-    }
-    pub(crate) fn __reduce7<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // Sum = Prod, "+", Sum => ActionFn(11);
-        assert!(__symbols.len() >= 3);
-        let __sym2 = __pop_Variant1(__symbols);
-        let __sym1 = __pop_Variant0(__symbols);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym2.2.clone();
-        let __nt = super::__action11::<>(input, __sym0, __sym1, __sym2);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (3, 4)
-    }
-    pub(crate) fn __reduce8<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // Sum = Prod => ActionFn(12);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action12::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 4)
-    }
-    pub(crate) fn __reduce9<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // Term = r#"[a-z_][a-zA-Z0-9_]*"# => ActionFn(6);
-        let __sym0 = __pop_Variant0(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action6::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant2(__nt), __end));
-        (1, 5)
-    }
-    pub(crate) fn __reduce10<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // __And = And => ActionFn(4);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action4::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 6)
-    }
-    pub(crate) fn __reduce11<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // __Expr = Expr => ActionFn(5);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action5::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 7)
-    }
-    pub(crate) fn __reduce12<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // __Factor = Factor => ActionFn(1);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action1::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 8)
-    }
-    pub(crate) fn __reduce14<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // __Sum = Sum => ActionFn(3);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action3::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 10)
-    }
-    pub(crate) fn __reduce15<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // __Term = Term => ActionFn(0);
-        let __sym0 = __pop_Variant2(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action0::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant2(__nt), __end));
-        (1, 11)
-    }
-}
-pub use self::__parse__Prod::ProdParser;
-
-#[cfg_attr(rustfmt, rustfmt_skip)]
-mod __parse__Sum {
-    #![allow(non_snake_case, non_camel_case_types, unused_mut, unused_variables, unused_imports, unused_parens, clippy::all)]
-
-    use crate::Expr;
-    #[allow(unused_extern_crates)]
-    extern crate lalrpop_util as __lalrpop_util;
-    #[allow(unused_imports)]
-    use self::__lalrpop_util::state_machine as __state_machine;
-    extern crate core;
-    extern crate alloc;
-    use self::__lalrpop_util::lexer::Token;
-    #[allow(dead_code)]
-    pub(crate) enum __Symbol<'input>
-     {
-        Variant0(&'input str),
-        Variant1(Expr),
-        Variant2(String),
-    }
-    const __ACTION: &[i8] = &[
-        // State 0
-        0, 2, 0, 0, 0, 10,
-        // State 1
-        0, 2, 0, 0, 0, 10,
-        // State 2
-        0, 2, 0, 0, 0, 10,
-        // State 3
-        0, 2, 0, 0, 0, 10,
-        // State 4
-        0, 2, 0, 0, 0, 10,
-        // State 5
-        -7, 0, -7, 3, -7, 0,
-        // State 6
-        -9, 0, -9, 0, 4, 0,
-        // State 7
-        0, 0, 0, 0, 0, 0,
-        // State 8
-        -4, 0, -4, -4, -4, 0,
-        // State 9
-        -10, 0, -10, -10, -10, 0,
-        // State 10
-        0, 0, -3, 0, 0, 0,
-        // State 11
-        0, 0, 16, 0, 0, 0,
-        // State 12
-        5, 0, -2, 0, 0, 0,
-        // State 13
-        -6, 0, -6, 0, -6, 0,
-        // State 14
-        -8, 0, -8, 0, 0, 0,
-        // State 15
-        -5, 0, -5, -5, -5, 0,
-        // State 16
-        0, 0, -1, 0, 0, 0,
-    ];
-    fn __action(state: i8, integer: usize) -> i8 {
-        __ACTION[(state as usize) * 6 + integer]
-    }
-    const __EOF_ACTION: &[i8] = &[
-        // State 0
-        0,
-        // State 1
-        0,
-        // State 2
-        0,
-        // State 3
-        0,
-        // State 4
-        0,
-        // State 5
-        -7,
-        // State 6
-        -9,
-        // State 7
-        -15,
-        // State 8
-        -4,
-        // State 9
-        -10,
-        // State 10
-        0,
-        // State 11
-        0,
-        // State 12
-        0,
-        // State 13
-        -6,
-        // State 14
-        -8,
-        // State 15
-        -5,
-        // State 16
-        0,
-    ];
-    fn __goto(state: i8, nt: usize) -> i8 {
-        match nt {
-            0 => match state {
-                4 => 16,
-                _ => 10,
-            },
-            1 => 11,
-            2 => 5,
-            3 => match state {
-                2 => 13,
-                _ => 6,
-            },
-            4 => match state {
-                0 => 7,
-                3 => 14,
-                _ => 12,
-            },
-            5 => 8,
-            _ => 0,
-        }
-    }
-    fn __expected_tokens(__state: i8) -> alloc::vec::Vec<alloc::string::String> {
-        const __TERMINAL: &[&str] = &[
-            r###""&""###,
-            r###""(""###,
-            r###"")""###,
-            r###""*""###,
-            r###""+""###,
-            r###"r#"[a-z_][a-zA-Z0-9_]*"#"###,
-        ];
-        __TERMINAL.iter().enumerate().filter_map(|(index, terminal)| {
-            let next_state = __action(__state, index);
-            if next_state == 0 {
-                None
-            } else {
-                Some(alloc::string::ToString::to_string(terminal))
-            }
-        }).collect()
-    }
-    pub(crate) struct __StateMachine<'input>
-    where 
-    {
-        input: &'input str,
-        __phantom: core::marker::PhantomData<(&'input ())>,
-    }
-    impl<'input> __state_machine::ParserDefinition for __StateMachine<'input>
-    where 
-    {
-        type Location = usize;
-        type Error = &'static str;
-        type Token = Token<'input>;
-        type TokenIndex = usize;
-        type Symbol = __Symbol<'input>;
-        type Success = Expr;
-        type StateIndex = i8;
-        type Action = i8;
-        type ReduceIndex = i8;
-        type NonterminalIndex = usize;
-
-        #[inline]
-        fn start_location(&self) -> Self::Location {
-              Default::default()
-        }
-
-        #[inline]
-        fn start_state(&self) -> Self::StateIndex {
-              0
-        }
-
-        #[inline]
-        fn token_to_index(&self, token: &Self::Token) -> Option<usize> {
-            __token_to_integer(token, core::marker::PhantomData::<(&())>)
-        }
-
-        #[inline]
-        fn action(&self, state: i8, integer: usize) -> i8 {
-            __action(state, integer)
-        }
-
-        #[inline]
-        fn error_action(&self, state: i8) -> i8 {
-            __action(state, 6 - 1)
-        }
-
-        #[inline]
-        fn eof_action(&self, state: i8) -> i8 {
-            __EOF_ACTION[state as usize]
-        }
-
-        #[inline]
-        fn goto(&self, state: i8, nt: usize) -> i8 {
-            __goto(state, nt)
-        }
-
-        fn token_to_symbol(&self, token_index: usize, token: Self::Token) -> Self::Symbol {
-            __token_to_symbol(token_index, token, core::marker::PhantomData::<(&())>)
-        }
-
-        fn expected_tokens(&self, state: i8) -> alloc::vec::Vec<alloc::string::String> {
-            __expected_tokens(state)
-        }
-
-        #[inline]
-        fn uses_error_recovery(&self) -> bool {
-            false
-        }
-
-        #[inline]
-        fn error_recovery_symbol(
-            &self,
-            recovery: __state_machine::ErrorRecovery<Self>,
-        ) -> Self::Symbol {
-            panic!("error recovery not enabled for this grammar")
-        }
-
-        fn reduce(
-            &mut self,
-            action: i8,
-            start_location: Option<&Self::Location>,
-            states: &mut alloc::vec::Vec<i8>,
-            symbols: &mut alloc::vec::Vec<__state_machine::SymbolTriple<Self>>,
-        ) -> Option<__state_machine::ParseResult<Self>> {
-            __reduce(
-                self.input,
-                action,
-                start_location,
-                states,
-                symbols,
-                core::marker::PhantomData::<(&())>,
-            )
-        }
-
-        fn simulate_reduce(&self, action: i8) -> __state_machine::SimulatedReduce<Self> {
-            panic!("error recovery not enabled for this grammar")
-        }
-    }
-    fn __token_to_integer<
-        'input,
-    >(
-        __token: &Token<'input>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> Option<usize>
-    {
-        match *__token {
-            Token(1, _) if true => Some(0),
-            Token(2, _) if true => Some(1),
-            Token(3, _) if true => Some(2),
-            Token(4, _) if true => Some(3),
-            Token(5, _) if true => Some(4),
-            Token(0, _) if true => Some(5),
-            _ => None,
-        }
-    }
-    fn __token_to_symbol<
-        'input,
-    >(
-        __token_index: usize,
-        __token: Token<'input>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> __Symbol<'input>
-    {
-        match __token_index {
-            0 | 1 | 2 | 3 | 4 | 5 => match __token {
-                Token(1, __tok0) | Token(2, __tok0) | Token(3, __tok0) | Token(4, __tok0) | Token(5, __tok0) | Token(0, __tok0) if true => __Symbol::Variant0(__tok0),
-                _ => unreachable!(),
-            },
-            _ => unreachable!(),
-        }
-    }
-    pub struct SumParser {
-        builder: __lalrpop_util::lexer::MatcherBuilder,
-        _priv: (),
-    }
-
-    impl SumParser {
-        pub fn new() -> SumParser {
-            let __builder = super::__intern_token::new_builder();
-            SumParser {
-                builder: __builder,
-                _priv: (),
-            }
-        }
-
-        #[allow(dead_code)]
-        pub fn parse<
-            'input,
-        >(
-            &self,
-            input: &'input str,
-        ) -> Result<Expr, __lalrpop_util::ParseError<usize, Token<'input>, &'static str>>
-        {
-            let mut __tokens = self.builder.matcher(input);
-            __state_machine::Parser::drive(
-                __StateMachine {
-                    input,
-                    __phantom: core::marker::PhantomData::<(&())>,
-                },
-                __tokens,
-            )
-        }
-    }
-    pub(crate) fn __reduce<
-        'input,
-    >(
-        input: &'input str,
-        __action: i8,
-        __lookahead_start: Option<&usize>,
-        __states: &mut alloc::vec::Vec<i8>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> Option<Result<Expr,__lalrpop_util::ParseError<usize, Token<'input>, &'static str>>>
-    {
-        let (__pop_states, __nonterminal) = match __action {
-            0 => {
-                __reduce0(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            1 => {
-                __reduce1(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            2 => {
-                __reduce2(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            3 => {
-                __reduce3(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            4 => {
-                __reduce4(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            5 => {
-                __reduce5(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            6 => {
-                __reduce6(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            7 => {
-                __reduce7(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            8 => {
-                __reduce8(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            9 => {
-                __reduce9(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            10 => {
-                __reduce10(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            11 => {
-                __reduce11(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            12 => {
-                __reduce12(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            13 => {
-                __reduce13(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            14 => {
-                // __Sum = Sum => ActionFn(3);
-                let __sym0 = __pop_Variant1(__symbols);
-                let __start = __sym0.0.clone();
-                let __end = __sym0.2.clone();
-                let __nt = super::__action3::<>(input, __sym0);
-                return Some(Ok(__nt));
-            }
-            15 => {
-                __reduce15(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            _ => panic!("invalid action code {}", __action)
-        };
-        let __states_len = __states.len();
-        __states.truncate(__states_len - __pop_states);
-        let __state = *__states.last().unwrap();
-        let __next_state = __goto(__state, __nonterminal);
-        __states.push(__next_state);
-        None
-    }
-    #[inline(never)]
-    fn __symbol_type_mismatch() -> ! {
-        panic!("symbol type mismatch")
-    }
-    fn __pop_Variant1<
-      'input,
-    >(
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>
-    ) -> (usize, Expr, usize)
-     {
-        match __symbols.pop() {
-            Some((__l, __Symbol::Variant1(__v), __r)) => (__l, __v, __r),
-            _ => __symbol_type_mismatch()
-        }
-    }
-    fn __pop_Variant2<
-      'input,
-    >(
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>
-    ) -> (usize, String, usize)
-     {
-        match __symbols.pop() {
-            Some((__l, __Symbol::Variant2(__v), __r)) => (__l, __v, __r),
-            _ => __symbol_type_mismatch()
-        }
-    }
-    fn __pop_Variant0<
-      'input,
-    >(
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>
-    ) -> (usize, &'input str, usize)
-     {
-        match __symbols.pop() {
-            Some((__l, __Symbol::Variant0(__v), __r)) => (__l, __v, __r),
-            _ => __symbol_type_mismatch()
-        }
-    }
-    pub(crate) fn __reduce0<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // And = Sum, "&", And => ActionFn(13);
-        assert!(__symbols.len() >= 3);
-        let __sym2 = __pop_Variant1(__symbols);
-        let __sym1 = __pop_Variant0(__symbols);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym2.2.clone();
-        let __nt = super::__action13::<>(input, __sym0, __sym1, __sym2);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (3, 0)
-    }
-    pub(crate) fn __reduce1<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // And = Sum => ActionFn(14);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action14::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 0)
-    }
-    pub(crate) fn __reduce2<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // Expr = And => ActionFn(15);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action15::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 1)
-    }
-    pub(crate) fn __reduce3<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // Factor = Term => ActionFn(7);
-        let __sym0 = __pop_Variant2(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action7::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 2)
-    }
-    pub(crate) fn __reduce4<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // Factor = "(", Expr, ")" => ActionFn(8);
-        assert!(__symbols.len() >= 3);
-        let __sym2 = __pop_Variant0(__symbols);
-        let __sym1 = __pop_Variant1(__symbols);
-        let __sym0 = __pop_Variant0(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym2.2.clone();
-        let __nt = super::__action8::<>(input, __sym0, __sym1, __sym2);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (3, 2)
-    }
-    pub(crate) fn __reduce5<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // Prod = Factor, "*", Prod => ActionFn(9);
-        assert!(__symbols.len() >= 3);
-        let __sym2 = __pop_Variant1(__symbols);
-        let __sym1 = __pop_Variant0(__symbols);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym2.2.clone();
-        let __nt = super::__action9::<>(input, __sym0, __sym1, __sym2);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (3, 3)
-    }
-    pub(crate) fn __reduce6<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // Prod = Factor => ActionFn(10);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action10::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 3)
-    }
-    pub(crate) fn __reduce7<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // Sum = Prod, "+", Sum => ActionFn(11);
-        assert!(__symbols.len() >= 3);
-        let __sym2 = __pop_Variant1(__symbols);
-        let __sym1 = __pop_Variant0(__symbols);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym2.2.clone();
-        let __nt = super::__action11::<>(input, __sym0, __sym1, __sym2);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (3, 4)
-    }
-    pub(crate) fn __reduce8<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // Sum = Prod => ActionFn(12);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action12::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 4)
-    }
-    pub(crate) fn __reduce9<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // Term = r#"[a-z_][a-zA-Z0-9_]*"# => ActionFn(6);
-        let __sym0 = __pop_Variant0(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action6::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant2(__nt), __end));
-        (1, 5)
-    }
-    pub(crate) fn __reduce10<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // __And = And => ActionFn(4);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action4::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 6)
-    }
-    pub(crate) fn __reduce11<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // __Expr = Expr => ActionFn(5);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action5::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 7)
-    }
-    pub(crate) fn __reduce12<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // __Factor = Factor => ActionFn(1);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action1::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 8)
-    }
-    pub(crate) fn __reduce13<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // __Prod = Prod => ActionFn(2);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action2::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 9)
-    }
-    pub(crate) fn __reduce15<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // __Term = Term => ActionFn(0);
-        let __sym0 = __pop_Variant2(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action0::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant2(__nt), __end));
-        (1, 11)
-    }
-}
-pub use self::__parse__Sum::SumParser;
-
-#[cfg_attr(rustfmt, rustfmt_skip)]
-mod __parse__Term {
-    #![allow(non_snake_case, non_camel_case_types, unused_mut, unused_variables, unused_imports, unused_parens, clippy::all)]
-
-    use crate::Expr;
-    #[allow(unused_extern_crates)]
-    extern crate lalrpop_util as __lalrpop_util;
-    #[allow(unused_imports)]
-    use self::__lalrpop_util::state_machine as __state_machine;
-    extern crate core;
-    extern crate alloc;
-    use self::__lalrpop_util::lexer::Token;
-    #[allow(dead_code)]
-    pub(crate) enum __Symbol<'input>
-     {
-        Variant0(&'input str),
-        Variant1(Expr),
-        Variant2(String),
-    }
-    const __ACTION: &[i8] = &[
-        // State 0
-        0, 0, 0, 0, 0, 3,
-        // State 1
-        0, 0, 0, 0, 0, 0,
-        // State 2
-        0, 0, 0, 0, 0, 0,
-    ];
-    fn __action(state: i8, integer: usize) -> i8 {
-        __ACTION[(state as usize) * 6 + integer]
-    }
-    const __EOF_ACTION: &[i8] = &[
-        // State 0
-        0,
-        // State 1
-        -16,
-        // State 2
-        -10,
-    ];
-    fn __goto(state: i8, nt: usize) -> i8 {
-        match nt {
-            5 => 1,
-            _ => 0,
-        }
-    }
-    fn __expected_tokens(__state: i8) -> alloc::vec::Vec<alloc::string::String> {
-        const __TERMINAL: &[&str] = &[
-            r###""&""###,
-            r###""(""###,
-            r###"")""###,
-            r###""*""###,
-            r###""+""###,
-            r###"r#"[a-z_][a-zA-Z0-9_]*"#"###,
-        ];
-        __TERMINAL.iter().enumerate().filter_map(|(index, terminal)| {
-            let next_state = __action(__state, index);
-            if next_state == 0 {
-                None
-            } else {
-                Some(alloc::string::ToString::to_string(terminal))
-            }
-        }).collect()
-    }
-    pub(crate) struct __StateMachine<'input>
-    where 
-    {
-        input: &'input str,
-        __phantom: core::marker::PhantomData<(&'input ())>,
-    }
-    impl<'input> __state_machine::ParserDefinition for __StateMachine<'input>
-    where 
-    {
-        type Location = usize;
-        type Error = &'static str;
-        type Token = Token<'input>;
-        type TokenIndex = usize;
-        type Symbol = __Symbol<'input>;
-        type Success = String;
-        type StateIndex = i8;
-        type Action = i8;
-        type ReduceIndex = i8;
-        type NonterminalIndex = usize;
-
-        #[inline]
-        fn start_location(&self) -> Self::Location {
-              Default::default()
-        }
-
-        #[inline]
-        fn start_state(&self) -> Self::StateIndex {
-              0
-        }
-
-        #[inline]
-        fn token_to_index(&self, token: &Self::Token) -> Option<usize> {
-            __token_to_integer(token, core::marker::PhantomData::<(&())>)
-        }
-
-        #[inline]
-        fn action(&self, state: i8, integer: usize) -> i8 {
-            __action(state, integer)
-        }
-
-        #[inline]
-        fn error_action(&self, state: i8) -> i8 {
-            __action(state, 6 - 1)
-        }
-
-        #[inline]
-        fn eof_action(&self, state: i8) -> i8 {
-            __EOF_ACTION[state as usize]
-        }
-
-        #[inline]
-        fn goto(&self, state: i8, nt: usize) -> i8 {
-            __goto(state, nt)
-        }
-
-        fn token_to_symbol(&self, token_index: usize, token: Self::Token) -> Self::Symbol {
-            __token_to_symbol(token_index, token, core::marker::PhantomData::<(&())>)
-        }
-
-        fn expected_tokens(&self, state: i8) -> alloc::vec::Vec<alloc::string::String> {
-            __expected_tokens(state)
-        }
-
-        #[inline]
-        fn uses_error_recovery(&self) -> bool {
-            false
-        }
-
-        #[inline]
-        fn error_recovery_symbol(
-            &self,
-            recovery: __state_machine::ErrorRecovery<Self>,
-        ) -> Self::Symbol {
-            panic!("error recovery not enabled for this grammar")
-        }
-
-        fn reduce(
-            &mut self,
-            action: i8,
-            start_location: Option<&Self::Location>,
-            states: &mut alloc::vec::Vec<i8>,
-            symbols: &mut alloc::vec::Vec<__state_machine::SymbolTriple<Self>>,
-        ) -> Option<__state_machine::ParseResult<Self>> {
-            __reduce(
-                self.input,
-                action,
-                start_location,
-                states,
-                symbols,
-                core::marker::PhantomData::<(&())>,
-            )
-        }
-
-        fn simulate_reduce(&self, action: i8) -> __state_machine::SimulatedReduce<Self> {
-            panic!("error recovery not enabled for this grammar")
-        }
-    }
-    fn __token_to_integer<
-        'input,
-    >(
-        __token: &Token<'input>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> Option<usize>
-    {
-        match *__token {
-            Token(1, _) if true => Some(0),
-            Token(2, _) if true => Some(1),
-            Token(3, _) if true => Some(2),
-            Token(4, _) if true => Some(3),
-            Token(5, _) if true => Some(4),
-            Token(0, _) if true => Some(5),
-            _ => None,
-        }
-    }
-    fn __token_to_symbol<
-        'input,
-    >(
-        __token_index: usize,
-        __token: Token<'input>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> __Symbol<'input>
-    {
-        match __token_index {
-            0 | 1 | 2 | 3 | 4 | 5 => match __token {
-                Token(1, __tok0) | Token(2, __tok0) | Token(3, __tok0) | Token(4, __tok0) | Token(5, __tok0) | Token(0, __tok0) if true => __Symbol::Variant0(__tok0),
-                _ => unreachable!(),
-            },
-            _ => unreachable!(),
-        }
-    }
-    pub struct TermParser {
-        builder: __lalrpop_util::lexer::MatcherBuilder,
-        _priv: (),
-    }
-
-    impl TermParser {
-        pub fn new() -> TermParser {
-            let __builder = super::__intern_token::new_builder();
-            TermParser {
-                builder: __builder,
-                _priv: (),
-            }
-        }
-
-        #[allow(dead_code)]
-        pub fn parse<
-            'input,
-        >(
-            &self,
-            input: &'input str,
-        ) -> Result<String, __lalrpop_util::ParseError<usize, Token<'input>, &'static str>>
-        {
-            let mut __tokens = self.builder.matcher(input);
-            __state_machine::Parser::drive(
-                __StateMachine {
-                    input,
-                    __phantom: core::marker::PhantomData::<(&())>,
-                },
-                __tokens,
-            )
-        }
-    }
-    pub(crate) fn __reduce<
-        'input,
-    >(
-        input: &'input str,
-        __action: i8,
-        __lookahead_start: Option<&usize>,
-        __states: &mut alloc::vec::Vec<i8>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> Option<Result<String,__lalrpop_util::ParseError<usize, Token<'input>, &'static str>>>
-    {
-        let (__pop_states, __nonterminal) = match __action {
-            0 => {
-                __reduce0(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            1 => {
-                __reduce1(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            2 => {
-                __reduce2(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            3 => {
-                __reduce3(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            4 => {
-                __reduce4(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            5 => {
-                __reduce5(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            6 => {
-                __reduce6(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            7 => {
-                __reduce7(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            8 => {
-                __reduce8(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            9 => {
-                __reduce9(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            10 => {
-                __reduce10(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            11 => {
-                __reduce11(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            12 => {
-                __reduce12(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            13 => {
-                __reduce13(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            14 => {
-                __reduce14(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
-            }
-            15 => {
-                // __Term = Term => ActionFn(0);
-                let __sym0 = __pop_Variant2(__symbols);
-                let __start = __sym0.0.clone();
-                let __end = __sym0.2.clone();
-                let __nt = super::__action0::<>(input, __sym0);
-                return Some(Ok(__nt));
-            }
-            _ => panic!("invalid action code {}", __action)
-        };
-        let __states_len = __states.len();
-        __states.truncate(__states_len - __pop_states);
-        let __state = *__states.last().unwrap();
-        let __next_state = __goto(__state, __nonterminal);
-        __states.push(__next_state);
-        None
-    }
-    #[inline(never)]
-    fn __symbol_type_mismatch() -> ! {
-        panic!("symbol type mismatch")
-    }
-    fn __pop_Variant1<
-      'input,
-    >(
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>
-    ) -> (usize, Expr, usize)
-     {
-        match __symbols.pop() {
-            Some((__l, __Symbol::Variant1(__v), __r)) => (__l, __v, __r),
-            _ => __symbol_type_mismatch()
-        }
-    }
-    fn __pop_Variant2<
-      'input,
-    >(
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>
-    ) -> (usize, String, usize)
-     {
-        match __symbols.pop() {
-            Some((__l, __Symbol::Variant2(__v), __r)) => (__l, __v, __r),
-            _ => __symbol_type_mismatch()
-        }
-    }
-    fn __pop_Variant0<
-      'input,
-    >(
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>
-    ) -> (usize, &'input str, usize)
-     {
-        match __symbols.pop() {
-            Some((__l, __Symbol::Variant0(__v), __r)) => (__l, __v, __r),
-            _ => __symbol_type_mismatch()
-        }
-    }
-    pub(crate) fn __reduce0<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // And = Sum, "&", And => ActionFn(13);
-        assert!(__symbols.len() >= 3);
-        let __sym2 = __pop_Variant1(__symbols);
-        let __sym1 = __pop_Variant0(__symbols);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym2.2.clone();
-        let __nt = super::__action13::<>(input, __sym0, __sym1, __sym2);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (3, 0)
-    }
-    pub(crate) fn __reduce1<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // And = Sum => ActionFn(14);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action14::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 0)
-    }
-    pub(crate) fn __reduce2<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // Expr = And => ActionFn(15);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action15::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 1)
-    }
-    pub(crate) fn __reduce3<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // Factor = Term => ActionFn(7);
-        let __sym0 = __pop_Variant2(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action7::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 2)
-    }
-    pub(crate) fn __reduce4<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // Factor = "(", Expr, ")" => ActionFn(8);
-        assert!(__symbols.len() >= 3);
-        let __sym2 = __pop_Variant0(__symbols);
-        let __sym1 = __pop_Variant1(__symbols);
-        let __sym0 = __pop_Variant0(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym2.2.clone();
-        let __nt = super::__action8::<>(input, __sym0, __sym1, __sym2);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (3, 2)
-    }
-    pub(crate) fn __reduce5<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // Prod = Factor, "*", Prod => ActionFn(9);
-        assert!(__symbols.len() >= 3);
-        let __sym2 = __pop_Variant1(__symbols);
-        let __sym1 = __pop_Variant0(__symbols);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym2.2.clone();
-        let __nt = super::__action9::<>(input, __sym0, __sym1, __sym2);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (3, 3)
-    }
-    pub(crate) fn __reduce6<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // Prod = Factor => ActionFn(10);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action10::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 3)
-    }
-    pub(crate) fn __reduce7<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // Sum = Prod, "+", Sum => ActionFn(11);
-        assert!(__symbols.len() >= 3);
-        let __sym2 = __pop_Variant1(__symbols);
-        let __sym1 = __pop_Variant0(__symbols);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym2.2.clone();
-        let __nt = super::__action11::<>(input, __sym0, __sym1, __sym2);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (3, 4)
-    }
-    pub(crate) fn __reduce8<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // Sum = Prod => ActionFn(12);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action12::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 4)
-    }
-    pub(crate) fn __reduce9<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // Term = r#"[a-z_][a-zA-Z0-9_]*"# => ActionFn(6);
-        let __sym0 = __pop_Variant0(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action6::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant2(__nt), __end));
-        (1, 5)
-    }
-    pub(crate) fn __reduce10<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // __And = And => ActionFn(4);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action4::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 6)
-    }
-    pub(crate) fn __reduce11<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // __Expr = Expr => ActionFn(5);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action5::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 7)
-    }
-    pub(crate) fn __reduce12<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // __Factor = Factor => ActionFn(1);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action1::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 8)
-    }
-    pub(crate) fn __reduce13<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // __Prod = Prod => ActionFn(2);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action2::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 9)
-    }
-    pub(crate) fn __reduce14<
-        'input,
-    >(
-        input: &'input str,
-        __lookahead_start: Option<&usize>,
-        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
-        _: core::marker::PhantomData<(&'input ())>,
-    ) -> (usize, usize)
-    {
-        // __Sum = Sum => ActionFn(3);
-        let __sym0 = __pop_Variant1(__symbols);
-        let __start = __sym0.0.clone();
-        let __end = __sym0.2.clone();
-        let __nt = super::__action3::<>(input, __sym0);
-        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
-        (1, 10)
-    }
-}
-pub use self::__parse__Term::TermParser;
-#[cfg_attr(rustfmt, rustfmt_skip)]
-mod __intern_token {
-    #![allow(unused_imports)]
-    use crate::Expr;
-    #[allow(unused_extern_crates)]
-    extern crate lalrpop_util as __lalrpop_util;
-    #[allow(unused_imports)]
-    use self::__lalrpop_util::state_machine as __state_machine;
-    extern crate core;
-    extern crate alloc;
-    pub fn new_builder() -> __lalrpop_util::lexer::MatcherBuilder {
-        let __strs: &[(&str, bool)] = &[
-            ("^([_a-z][0-9A-Z_a-z]*)", false),
-            ("^(\\&)", false),
-            ("^(\\()", false),
-            ("^(\\))", false),
-            ("^(\\*)", false),
-            ("^(\\+)", false),
-            (r"^(\s*)", true),
-        ];
-        __lalrpop_util::lexer::MatcherBuilder::new(__strs.iter().copied()).unwrap()
+    pub fn new_builder() -> __lalrpop_util::lexer::MatcherBuilder {
+        let __strs: &[(&str, bool)] = &[
+            ("^([0-1])", false),
+            ("^([A-Z_a-z][0-9A-Z_a-z]*)", false),
+            ("^([a-z]+[0-9]+\\[[0-9]+\\])", false),
+            ("^([a-z]+\\[[0-9]+\\])", false),
+            ("^([_a-z]+[0-9A-Z_]+\\|[0-9A-Z_a-z]+)", false),
+            ("^(!)", false),
+            ("^(\\&)", false),
+            ("^(\\()", false),
+            ("^(\\))", false),
+            ("^(\\*)", false),
+            ("^(\\+)", false),
+            ("^(,)", false),
+            ("^(\\^)", false),
+            ("^(maj)", false),
+            ("^(mux)", false),
+            ("^(xnor)", false),
+            (r"^(\s*)", true),
+        ];
+        __lalrpop_util::lexer::MatcherBuilder::new(__strs.iter().copied()).unwrap()
     }
 }
 pub(crate) use self::__lalrpop_util::lexer::Token;
@@ -4026,8 +937,8 @@ fn __action0<
     'input,
 >(
     input: &'input str,
-    (_, __0, _): (usize, String, usize),
-) -> String
+    (_, __0, _): (usize, Expr, usize),
+) -> Expr
 {
     __0
 }
@@ -4037,10 +948,10 @@ fn __action1<
     'input,
 >(
     input: &'input str,
-    (_, __0, _): (usize, Expr, usize),
-) -> Expr
+    (_, s, _): (usize, &'input str, usize),
+) -> String
 {
-    __0
+    s.to_string()
 }
 
 #[allow(unused_variables)]
@@ -4048,10 +959,10 @@ fn __action2<
     'input,
 >(
     input: &'input str,
-    (_, __0, _): (usize, Expr, usize),
-) -> Expr
+    (_, s, _): (usize, &'input str, usize),
+) -> String
 {
-    __0
+    s.to_string()
 }
 
 #[allow(unused_variables)]
@@ -4059,10 +970,10 @@ fn __action3<
     'input,
 >(
     input: &'input str,
-    (_, __0, _): (usize, Expr, usize),
-) -> Expr
+    (_, s, _): (usize, &'input str, usize),
+) -> String
 {
-    __0
+    s.to_string()
 }
 
 #[allow(unused_variables)]
@@ -4070,10 +981,10 @@ fn __action4<
     'input,
 >(
     input: &'input str,
-    (_, __0, _): (usize, Expr, usize),
-) -> Expr
+    (_, s, _): (usize, &'input str, usize),
+) -> String
 {
-    __0
+    s.to_string()
 }
 
 #[allow(unused_variables)]
@@ -4081,10 +992,10 @@ fn __action5<
     'input,
 >(
     input: &'input str,
-    (_, __0, _): (usize, Expr, usize),
-) -> Expr
+    (_, s, _): (usize, &'input str, usize),
+) -> String
 {
-    __0
+    s.to_string()
 }
 
 #[allow(unused_variables)]
@@ -4092,10 +1003,10 @@ fn __action6<
     'input,
 >(
     input: &'input str,
-    (_, s, _): (usize, &'input str, usize),
+    (_, __0, _): (usize, &'input str, usize),
 ) -> String
 {
-    s.to_string()
+    "*".to_string()
 }
 
 #[allow(unused_variables)]
@@ -4103,10 +1014,10 @@ fn __action7<
     'input,
 >(
     input: &'input str,
-    (_, t, _): (usize, String, usize),
-) -> Expr
+    (_, __0, _): (usize, &'input str, usize),
+) -> String
 {
-    Expr::Variable(t)
+    "+".to_string()
 }
 
 #[allow(unused_variables)]
@@ -4114,12 +1025,10 @@ fn __action8<
     'input,
 >(
     input: &'input str,
-    (_, _, _): (usize, &'input str, usize),
-    (_, e, _): (usize, Expr, usize),
-    (_, _, _): (usize, &'input str, usize),
-) -> Expr
+    (_, __0, _): (usize, &'input str, usize),
+) -> String
 {
-    e
+    "&".to_string()
 }
 
 #[allow(unused_variables)]
@@ -4127,12 +1036,10 @@ fn __action9<
     'input,
 >(
     input: &'input str,
-    (_, f, _): (usize, Expr, usize),
-    (_, _, _): (usize, &'input str, usize),
-    (_, p, _): (usize, Expr, usize),
-) -> Expr
+    (_, __0, _): (usize, &'input str, usize),
+) -> String
 {
-    Expr::Mul(Box::new(f), Box::new(p))
+    "^".to_string()
 }
 
 #[allow(unused_variables)]
@@ -4140,10 +1047,10 @@ fn __action10<
     'input,
 >(
     input: &'input str,
-    (_, f, _): (usize, Expr, usize),
-) -> Expr
+    (_, __0, _): (usize, &'input str, usize),
+) -> String
 {
-    f
+    "xnor".to_string()
 }
 
 #[allow(unused_variables)]
@@ -4151,12 +1058,10 @@ fn __action11<
     'input,
 >(
     input: &'input str,
-    (_, p, _): (usize, Expr, usize),
-    (_, _, _): (usize, &'input str, usize),
-    (_, s, _): (usize, Expr, usize),
-) -> Expr
+    (_, __0, _): (usize, &'input str, usize),
+) -> String
 {
-    Expr::Add(Box::new(p), Box::new(s))
+    "mux".to_string()
 }
 
 #[allow(unused_variables)]
@@ -4164,10 +1069,10 @@ fn __action12<
     'input,
 >(
     input: &'input str,
-    (_, p, _): (usize, Expr, usize),
-) -> Expr
+    (_, __0, _): (usize, &'input str, usize),
+) -> String
 {
-    p
+    "maj".to_string()
 }
 
 #[allow(unused_variables)]
@@ -4175,12 +1080,10 @@ fn __action13<
     'input,
 >(
     input: &'input str,
-    (_, s, _): (usize, Expr, usize),
-    (_, _, _): (usize, &'input str, usize),
-    (_, a, _): (usize, Expr, usize),
-) -> Expr
+    (_, __0, _): (usize, &'input str, usize),
+) -> String
 {
-    Expr::And(Box::new(s), Box::new(a))
+    "!".to_string()
 }
 
 #[allow(unused_variables)]
@@ -4188,10 +1091,10 @@ fn __action14<
     'input,
 >(
     input: &'input str,
-    (_, s, _): (usize, Expr, usize),
+    (_, n, _): (usize, String, usize),
 ) -> Expr
 {
-    s
+    Expr::VariableStr(n)
 }
 
 #[allow(unused_variables)]
@@ -4199,10 +1102,75 @@ fn __action15<
     'input,
 >(
     input: &'input str,
-    (_, a, _): (usize, Expr, usize),
+    (_, not, _): (usize, String, usize),
+    (_, e, _): (usize, Expr, usize),
+) -> Expr
+{
+    Expr::Not(Box::new(e))
+}
+
+#[allow(unused_variables)]
+fn __action16<
+    'input,
+>(
+    input: &'input str,
+    (_, _, _): (usize, &'input str, usize),
+    (_, n, _): (usize, String, usize),
+    (_, _, _): (usize, &'input str, usize),
+) -> Expr
+{
+    Expr::VariableStr(n)
+}
+
+#[allow(unused_variables)]
+fn __action17<
+    'input,
+>(
+    input: &'input str,
+    (_, _, _): (usize, &'input str, usize),
+    (_, not, _): (usize, String, usize),
+    (_, _, _): (usize, &'input str, usize),
+    (_, e1, _): (usize, Expr, usize),
+    (_, s, _): (usize, String, usize),
+    (_, e2, _): (usize, Expr, usize),
+    (_, _, _): (usize, &'input str, usize),
+    (_, _, _): (usize, &'input str, usize),
+) -> Expr
+{
+    Expr::Not(Box::new(Expr::Lisp(s, Box::new(e1), Box::new(e2))))
+}
+
+#[allow(unused_variables)]
+fn __action18<
+    'input,
+>(
+    input: &'input str,
+    (_, _, _): (usize, &'input str, usize),
+    (_, e1, _): (usize, Expr, usize),
+    (_, s, _): (usize, String, usize),
+    (_, e2, _): (usize, Expr, usize),
+    (_, _, _): (usize, &'input str, usize),
+) -> Expr
+{
+    Expr::Lisp(s, Box::new(e1), Box::new(e2))
+}
+
+#[allow(unused_variables)]
+fn __action19<
+    'input,
+>(
+    input: &'input str,
+    (_, t, _): (usize, String, usize),
+    (_, _, _): (usize, &'input str, usize),
+    (_, e1, _): (usize, Expr, usize),
+    (_, _, _): (usize, &'input str, usize),
+    (_, e2, _): (usize, Expr, usize),
+    (_, _, _): (usize, &'input str, usize),
+    (_, e3, _): (usize, Expr, usize),
+    (_, _, _): (usize, &'input str, usize),
 ) -> Expr
 {
-    a
+    Expr::Ternary(t, Box::new(e1), Box::new(e2), Box::new(e3))
 }
 
 pub trait __ToTriple<'input, >
@@ -1,5 +1,5 @@
 // auto-generated: "lalrpop 0.19.12"
-// sha3: 17198f525bbf2ff0fbcce78c8c783ba4f00952b4cc0490c74ac2d4557d4a07be
+// sha3: 280342511f8d3c64aea59390d18cf5b3b2e077a8f312375af6c006226f9f847b
 use crate::Expr;
 #[allow(unused_extern_crates)]
 extern crate lalrpop_util as __lalrpop_util;
@@ -29,64 +29,82 @@ mod __parse__Expr {
     }
     const __ACTION: &[i8] = &[
         // State 0
-        11, 0, 3, 0, 0, 0, 12, 13, 14, 15, 16,
+        14, 0, 3, 0, 0, 0, 0, 0, 0, 0, 15, 16, 17, 18, 19,
         // State 1
-        11, 0, 3, 0, 0, 0, 12, 13, 14, 15, 16,
+        14, 0, 3, 0, 0, 0, 0, 0, 0, 0, 15, 16, 17, 18, 19,
         // State 2
-        11, 20, 0, 0, 21, 22, 12, 13, 14, 15, 16,
+        14, 23, 0, 0, 24, 25, 26, 27, 28, 29, 15, 16, 17, 18, 19,
         // State 3
-        11, 0, 3, 0, 0, 0, 12, 13, 14, 15, 16,
+        14, 0, 3, 0, 0, 0, 0, 0, 0, 0, 15, 16, 17, 18, 19,
         // State 4
-        0, 20, 0, 0, 21, 22, 0, 0, 0, 0, 0,
+        14, 0, 3, 0, 0, 0, 0, 0, 0, 0, 15, 16, 17, 18, 19,
         // State 5
-        11, 0, 3, 0, 0, 0, 12, 13, 14, 15, 16,
+        0, 23, 0, 0, 24, 25, 26, 0, 0, 29, 0, 0, 0, 0, 0,
         // State 6
-        11, 0, 3, 0, 0, 0, 12, 13, 14, 15, 16,
+        14, 0, 3, 0, 0, 0, 0, 0, 0, 0, 15, 16, 17, 18, 19,
         // State 7
-        11, 0, 3, 0, 0, 0, 12, 13, 14, 15, 16,
+        14, 0, 3, 0, 0, 0, 0, 0, 0, 0, 15, 16, 17, 18, 19,
         // State 8
-        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        14, 0, 3, 0, 0, 0, 0, 0, 0, 0, 15, 16, 17, 18, 19,
         // State 9
-        -1, 0, -1, -1, 0, 0, -1, -1, -1, -1, -1,
+        14, 0, 3, 0, 0, 0, 0, 0, 0, 0, 15, 16, 17, 18, 19,
         // State 10
-        -6, 0, -6, 0, 0, 0, -6, -6, -6, -6, -6,
+        14, 0, 3, 0, 0, 0, 0, 0, 0, 0, 15, 16, 17, 18, 19,
         // State 11
-        -14, 0, -14, -14, 0, 0, -14, -14, -14, -14, -14,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
         // State 12
-        -11, 0, -11, -11, 0, 0, -11, -11, -11, -11, -11,
+        -1, 0, -1, -1, 0, 0, 0, 0, 0, 0, -1, -1, -1, -1, -1,
         // State 13
-        -13, 0, -13, -13, 0, 0, -13, -13, -13, -13, -13,
+        -7, 0, -7, 0, 0, 0, 0, 0, 0, 0, -7, -7, -7, -7, -7,
         // State 14
-        -12, 0, -12, -12, 0, 0, -12, -12, -12, -12, -12,
+        -19, 0, -19, -19, 0, 0, 0, 0, 0, 0, -19, -19, -19, -19, -19,
         // State 15
-        -10, 0, -10, -10, 0, 0, -10, -10, -10, -10, -10,
+        -16, 0, -16, -16, 0, 0, 0, 0, 0, 0, -16, -16, -16, -16, -16,
         // State 16
-        -2, 0, -2, -2, 0, 0, -2, -2, -2, -2, -2,
+        -18, 0, -18, -18, 0, 0, 0, 0, 0, 0, -18, -18, -18, -18, -18,
         // State 17
-        0, 0, 5, 0, 0, 0, 0, 0, 0, 0, 0,
+        -17, 0, -17, -17, 0, 0, 0, 0, 0, 0, -17, -17, -17, -17, -17,
         // State 18
-        0, 0, 0, 23, 0, 0, 0, 0, 0, 0, 0,
+        -15, 0, -15, -15, 0, 0, 0, 0, 0, 0, -15, -15, -15, -15, -15,
         // State 19
-        -9, 0, -9, 0, 0, 0, -9, -9, -9, -9, -9,
+        -2, 0, -2, -2, 0, 0, 0, 0, 0, 0, -2, -2, -2, -2, -2,
         // State 20
-        -7, 0, -7, 0, 0, 0, -7, -7, -7, -7, -7,
+        0, 0, 6, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
         // State 21
-        -8, 0, -8, 0, 0, 0, -8, -8, -8, -8, -8,
+        0, 0, 0, 30, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
         // State 22
-        -3, 0, -3, -3, 0, 0, -3, -3, -3, -3, -3,
+        -10, 0, -10, 0, 0, 0, 0, 0, 0, 0, -10, -10, -10, -10, -10,
         // State 23
-        0, 0, 0, 25, 0, 0, 0, 0, 0, 0, 0,
+        -8, 0, -8, 0, 0, 0, 0, 0, 0, 0, -8, -8, -8, -8, -8,
         // State 24
-        -5, 0, -5, -5, 0, 0, -5, -5, -5, -5, -5,
+        -9, 0, -9, 0, 0, 0, 0, 0, 0, 0, -9, -9, -9, -9, -9,
         // State 25
-        0, 0, 0, 27, 0, 0, 0, 0, 0, 0, 0,
+        -11, 0, -11, 0, 0, 0, 0, 0, 0, 0, -11, -11, -11, -11, -11,
         // State 26
-        0, 0, 0, 28, 0, 0, 0, 0, 0, 0, 0,
+        -14, 0, -14, 0, 0, 0, 0, 0, 0, 0, -14, -14, -14, -14, -14,
         // State 27
-        -4, 0, -4, -4, 0, 0, -4, -4, -4, -4, -4,
+        -13, 0, -13, 0, 0, 0, 0, 0, 0, 0, -13, -13, -13, -13, -13,
+        // State 28
+        -12, 0, -12, 0, 0, 0, 0, 0, 0, 0, -12, -12, -12, -12, -12,
+        // State 29
+        -3, 0, -3, -3, 0, 0, 0, 0, 0, 0, -3, -3, -3, -3, -3,
+        // State 30
+        0, 0, 0, 32, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        // State 31
+        -5, 0, -5, -5, 0, 0, 0, 0, 0, 0, -5, -5, -5, -5, -5,
+        // State 32
+        0, 0, 0, 35, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        // State 33
+        0, 0, 0, 36, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        // State 34
+        -6, 0, -6, -6, 0, 0, 0, 0, 0, 0, -6, -6, -6, -6, -6,
+        // State 35
+        0, 0, 0, 37, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        // State 36
+        -4, 0, -4, -4, 0, 0, 0, 0, 0, 0, -4, -4, -4, -4, -4,
     ];
     fn __action(state: i8, integer: usize) -> i8 {
-        __ACTION[(state as usize) * 11 + integer]
+        __ACTION[(state as usize) * 15 + integer]
     }
     const __EOF_ACTION: &[i8] = &[
         // State 0
@@ -106,67 +124,89 @@ mod __parse__Expr {
         // State 7
         0,
         // State 8
-        -15,
+        0,
         // State 9
-        -1,
+        0,
         // State 10
         0,
         // State 11
-        -14,
+        -20,
         // State 12
-        -11,
+        -1,
         // State 13
-        -13,
+        0,
         // State 14
-        -12,
+        -19,
         // State 15
-        -10,
+        -16,
         // State 16
-        -2,
+        -18,
         // State 17
-        0,
+        -17,
         // State 18
-        0,
+        -15,
         // State 19
-        0,
+        -2,
         // State 20
         0,
         // State 21
         0,
         // State 22
-        -3,
+        0,
         // State 23
         0,
         // State 24
-        -5,
+        0,
         // State 25
         0,
         // State 26
         0,
         // State 27
+        0,
+        // State 28
+        0,
+        // State 29
+        -3,
+        // State 30
+        0,
+        // State 31
+        -5,
+        // State 32
+        0,
+        // State 33
+        0,
+        // State 34
+        -6,
+        // State 35
+        0,
+        // State 36
         -4,
     ];
     fn __goto(state: i8, nt: usize) -> i8 {
         match nt {
             0 => match state {
-                6 => 7,
-                0 => 8,
-                1 => 16,
-                5 => 23,
-                7 => 25,
-                _ => 5,
+                4 => 7,
+                7 => 9,
+                8 => 10,
+                0 => 11,
+                1 => 19,
+                6 => 30,
+                9 => 32,
+                10 => 33,
+                _ => 6,
             },
             1 => match state {
-                2 => 17,
+                2 => 20,
                 _ => 1,
             },
             2 => match state {
-                4 => 6,
+                5 => 8,
                 _ => 3,
             },
-            3 => match state {
-                2 => 18,
-                _ => 9,
+            3 => 4,
+            4 => match state {
+                2 => 21,
+                _ => 12,
             },
             _ => 0,
         }
@@ -179,6 +219,10 @@ mod __parse__Expr {
             r###"")""###,
             r###""*""###,
             r###""+""###,
+            r###""^""###,
+            r###""maj""###,
+            r###""mux""###,
+            r###""xnor""###,
             r###"r#"[0-1]"#"###,
             r###"r#"[a-zA-Z_][a-zA-Z0-9_]*"#"###,
             r###"r#"[a-zA-Z_][a-zA-Z0-9_]*\\|[a-zA-Z0-9_]+"#"###,
@@ -236,7 +280,7 @@ mod __parse__Expr {
 
         #[inline]
         fn error_action(&self, state: i8) -> i8 {
-            __action(state, 11 - 1)
+            __action(state, 15 - 1)
         }
 
         #[inline]
@@ -305,11 +349,15 @@ mod __parse__Expr {
             Token(8, _) if true => Some(3),
             Token(9, _) if true => Some(4),
             Token(10, _) if true => Some(5),
-            Token(0, _) if true => Some(6),
-            Token(1, _) if true => Some(7),
-            Token(2, _) if true => Some(8),
-            Token(3, _) if true => Some(9),
-            Token(4, _) if true => Some(10),
+            Token(11, _) if true => Some(6),
+            Token(12, _) if true => Some(7),
+            Token(13, _) if true => Some(8),
+            Token(14, _) if true => Some(9),
+            Token(0, _) if true => Some(10),
+            Token(1, _) if true => Some(11),
+            Token(2, _) if true => Some(12),
+            Token(3, _) if true => Some(13),
+            Token(4, _) if true => Some(14),
             _ => None,
         }
     }
@@ -322,8 +370,8 @@ mod __parse__Expr {
     ) -> __Symbol<'input>
     {
         match __token_index {
-            0 | 1 | 2 | 3 | 4 | 5 | 6 | 7 | 8 | 9 | 10 => match __token {
-                Token(5, __tok0) | Token(6, __tok0) | Token(7, __tok0) | Token(8, __tok0) | Token(9, __tok0) | Token(10, __tok0) | Token(0, __tok0) | Token(1, __tok0) | Token(2, __tok0) | Token(3, __tok0) | Token(4, __tok0) if true => __Symbol::Variant0(__tok0),
+            0 | 1 | 2 | 3 | 4 | 5 | 6 | 7 | 8 | 9 | 10 | 11 | 12 | 13 | 14 => match __token {
+                Token(5, __tok0) | Token(6, __tok0) | Token(7, __tok0) | Token(8, __tok0) | Token(9, __tok0) | Token(10, __tok0) | Token(11, __tok0) | Token(12, __tok0) | Token(13, __tok0) | Token(14, __tok0) | Token(0, __tok0) | Token(1, __tok0) | Token(2, __tok0) | Token(3, __tok0) | Token(4, __tok0) if true => __Symbol::Variant0(__tok0),
                 _ => unreachable!(),
             },
             _ => unreachable!(),
@@ -416,6 +464,21 @@ mod __parse__Expr {
                 __reduce13(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
             }
             14 => {
+                __reduce14(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
+            }
+            15 => {
+                __reduce15(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
+            }
+            16 => {
+                __reduce16(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
+            }
+            17 => {
+                __reduce17(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
+            }
+            18 => {
+                __reduce18(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
+            }
+            19 => {
                 // __Expr = Expr => ActionFn(0);
                 let __sym0 = __pop_Variant1(__symbols);
                 let __start = __sym0.0.clone();
@@ -478,11 +541,11 @@ mod __parse__Expr {
         _: core::marker::PhantomData<(&'input ())>,
     ) -> (usize, usize)
     {
-        // Expr = VariableStr => ActionFn(10);
+        // Expr = VariableStr => ActionFn(14);
         let __sym0 = __pop_Variant2(__symbols);
         let __start = __sym0.0.clone();
         let __end = __sym0.2.clone();
-        let __nt = super::__action10::<>(input, __sym0);
+        let __nt = super::__action14::<>(input, __sym0);
         __symbols.push((__start, __Symbol::Variant1(__nt), __end));
         (1, 0)
     }
@@ -495,13 +558,13 @@ mod __parse__Expr {
         _: core::marker::PhantomData<(&'input ())>,
     ) -> (usize, usize)
     {
-        // Expr = NotOp, Expr => ActionFn(11);
+        // Expr = NotOp, Expr => ActionFn(15);
         assert!(__symbols.len() >= 2);
         let __sym1 = __pop_Variant1(__symbols);
         let __sym0 = __pop_Variant2(__symbols);
         let __start = __sym0.0.clone();
         let __end = __sym1.2.clone();
-        let __nt = super::__action11::<>(input, __sym0, __sym1);
+        let __nt = super::__action15::<>(input, __sym0, __sym1);
         __symbols.push((__start, __Symbol::Variant1(__nt), __end));
         (2, 0)
     }
@@ -514,14 +577,14 @@ mod __parse__Expr {
         _: core::marker::PhantomData<(&'input ())>,
     ) -> (usize, usize)
     {
-        // Expr = "(", VariableStr, ")" => ActionFn(12);
+        // Expr = "(", VariableStr, ")" => ActionFn(16);
         assert!(__symbols.len() >= 3);
         let __sym2 = __pop_Variant0(__symbols);
         let __sym1 = __pop_Variant2(__symbols);
         let __sym0 = __pop_Variant0(__symbols);
         let __start = __sym0.0.clone();
         let __end = __sym2.2.clone();
-        let __nt = super::__action12::<>(input, __sym0, __sym1, __sym2);
+        let __nt = super::__action16::<>(input, __sym0, __sym1, __sym2);
         __symbols.push((__start, __Symbol::Variant1(__nt), __end));
         (3, 0)
     }
@@ -534,7 +597,7 @@ mod __parse__Expr {
         _: core::marker::PhantomData<(&'input ())>,
     ) -> (usize, usize)
     {
-        // Expr = "(", NotOp, "(", Op, Expr, Expr, ")", ")" => ActionFn(13);
+        // Expr = "(", NotOp, "(", Op, Expr, Expr, ")", ")" => ActionFn(17);
         assert!(__symbols.len() >= 8);
         let __sym7 = __pop_Variant0(__symbols);
         let __sym6 = __pop_Variant0(__symbols);
@@ -546,7 +609,7 @@ mod __parse__Expr {
         let __sym0 = __pop_Variant0(__symbols);
         let __start = __sym0.0.clone();
         let __end = __sym7.2.clone();
-        let __nt = super::__action13::<>(input, __sym0, __sym1, __sym2, __sym3, __sym4, __sym5, __sym6, __sym7);
+        let __nt = super::__action17::<>(input, __sym0, __sym1, __sym2, __sym3, __sym4, __sym5, __sym6, __sym7);
         __symbols.push((__start, __Symbol::Variant1(__nt), __end));
         (8, 0)
     }
@@ -559,7 +622,7 @@ mod __parse__Expr {
         _: core::marker::PhantomData<(&'input ())>,
     ) -> (usize, usize)
     {
-        // Expr = "(", Op, Expr, Expr, ")" => ActionFn(14);
+        // Expr = "(", Op, Expr, Expr, ")" => ActionFn(18);
         assert!(__symbols.len() >= 5);
         let __sym4 = __pop_Variant0(__symbols);
         let __sym3 = __pop_Variant1(__symbols);
@@ -568,7 +631,7 @@ mod __parse__Expr {
         let __sym0 = __pop_Variant0(__symbols);
         let __start = __sym0.0.clone();
         let __end = __sym4.2.clone();
-        let __nt = super::__action14::<>(input, __sym0, __sym1, __sym2, __sym3, __sym4);
+        let __nt = super::__action18::<>(input, __sym0, __sym1, __sym2, __sym3, __sym4);
         __symbols.push((__start, __Symbol::Variant1(__nt), __end));
         (5, 0)
     }
@@ -581,15 +644,38 @@ mod __parse__Expr {
         _: core::marker::PhantomData<(&'input ())>,
     ) -> (usize, usize)
     {
-        // NotOp = "!" => ActionFn(9);
+        // Expr = "(", TernOp, Expr, Expr, Expr, ")" => ActionFn(19);
+        assert!(__symbols.len() >= 6);
+        let __sym5 = __pop_Variant0(__symbols);
+        let __sym4 = __pop_Variant1(__symbols);
+        let __sym3 = __pop_Variant1(__symbols);
+        let __sym2 = __pop_Variant1(__symbols);
+        let __sym1 = __pop_Variant2(__symbols);
+        let __sym0 = __pop_Variant0(__symbols);
+        let __start = __sym0.0.clone();
+        let __end = __sym5.2.clone();
+        let __nt = super::__action19::<>(input, __sym0, __sym1, __sym2, __sym3, __sym4, __sym5);
+        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
+        (6, 0)
+    }
+    pub(crate) fn __reduce6<
+        'input,
+    >(
+        input: &'input str,
+        __lookahead_start: Option<&usize>,
+        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
+        _: core::marker::PhantomData<(&'input ())>,
+    ) -> (usize, usize)
+    {
+        // NotOp = "!" => ActionFn(13);
         let __sym0 = __pop_Variant0(__symbols);
         let __start = __sym0.0.clone();
         let __end = __sym0.2.clone();
-        let __nt = super::__action9::<>(input, __sym0);
+        let __nt = super::__action13::<>(input, __sym0);
         __symbols.push((__start, __Symbol::Variant2(__nt), __end));
         (1, 1)
     }
-    pub(crate) fn __reduce6<
+    pub(crate) fn __reduce7<
         'input,
     >(
         input: &'input str,
@@ -606,7 +692,7 @@ mod __parse__Expr {
         __symbols.push((__start, __Symbol::Variant2(__nt), __end));
         (1, 2)
     }
-    pub(crate) fn __reduce7<
+    pub(crate) fn __reduce8<
         'input,
     >(
         input: &'input str,
@@ -623,7 +709,7 @@ mod __parse__Expr {
         __symbols.push((__start, __Symbol::Variant2(__nt), __end));
         (1, 2)
     }
-    pub(crate) fn __reduce8<
+    pub(crate) fn __reduce9<
         'input,
     >(
         input: &'input str,
@@ -640,7 +726,75 @@ mod __parse__Expr {
         __symbols.push((__start, __Symbol::Variant2(__nt), __end));
         (1, 2)
     }
-    pub(crate) fn __reduce9<
+    pub(crate) fn __reduce10<
+        'input,
+    >(
+        input: &'input str,
+        __lookahead_start: Option<&usize>,
+        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
+        _: core::marker::PhantomData<(&'input ())>,
+    ) -> (usize, usize)
+    {
+        // Op = "^" => ActionFn(9);
+        let __sym0 = __pop_Variant0(__symbols);
+        let __start = __sym0.0.clone();
+        let __end = __sym0.2.clone();
+        let __nt = super::__action9::<>(input, __sym0);
+        __symbols.push((__start, __Symbol::Variant2(__nt), __end));
+        (1, 2)
+    }
+    pub(crate) fn __reduce11<
+        'input,
+    >(
+        input: &'input str,
+        __lookahead_start: Option<&usize>,
+        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
+        _: core::marker::PhantomData<(&'input ())>,
+    ) -> (usize, usize)
+    {
+        // Op = "xnor" => ActionFn(10);
+        let __sym0 = __pop_Variant0(__symbols);
+        let __start = __sym0.0.clone();
+        let __end = __sym0.2.clone();
+        let __nt = super::__action10::<>(input, __sym0);
+        __symbols.push((__start, __Symbol::Variant2(__nt), __end));
+        (1, 2)
+    }
+    pub(crate) fn __reduce12<
+        'input,
+    >(
+        input: &'input str,
+        __lookahead_start: Option<&usize>,
+        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
+        _: core::marker::PhantomData<(&'input ())>,
+    ) -> (usize, usize)
+    {
+        // TernOp = "mux" => ActionFn(11);
+        let __sym0 = __pop_Variant0(__symbols);
+        let __start = __sym0.0.clone();
+        let __end = __sym0.2.clone();
+        let __nt = super::__action11::<>(input, __sym0);
+        __symbols.push((__start, __Symbol::Variant2(__nt), __end));
+        (1, 3)
+    }
+    pub(crate) fn __reduce13<
+        'input,
+    >(
+        input: &'input str,
+        __lookahead_start: Option<&usize>,
+        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
+        _: core::marker::PhantomData<(&'input ())>,
+    ) -> (usize, usize)
+    {
+        // TernOp = "maj" => ActionFn(12);
+        let __sym0 = __pop_Variant0(__symbols);
+        let __start = __sym0.0.clone();
+        let __end = __sym0.2.clone();
+        let __nt = super::__action12::<>(input, __sym0);
+        __symbols.push((__start, __Symbol::Variant2(__nt), __end));
+        (1, 3)
+    }
+    pub(crate) fn __reduce14<
         'input,
     >(
         input: &'input str,
@@ -655,9 +809,9 @@ mod __parse__Expr {
         let __end = __sym0.2.clone();
         let __nt = super::__action1::<>(input, __sym0);
         __symbols.push((__start, __Symbol::Variant2(__nt), __end));
-        (1, 3)
+        (1, 4)
     }
-    pub(crate) fn __reduce10<
+    pub(crate) fn __reduce15<
         'input,
     >(
         input: &'input str,
@@ -672,9 +826,9 @@ mod __parse__Expr {
         let __end = __sym0.2.clone();
         let __nt = super::__action2::<>(input, __sym0);
         __symbols.push((__start, __Symbol::Variant2(__nt), __end));
-        (1, 3)
+        (1, 4)
     }
-    pub(crate) fn __reduce11<
+    pub(crate) fn __reduce16<
         'input,
     >(
         input: &'input str,
@@ -689,9 +843,9 @@ mod __parse__Expr {
         let __end = __sym0.2.clone();
         let __nt = super::__action3::<>(input, __sym0);
         __symbols.push((__start, __Symbol::Variant2(__nt), __end));
-        (1, 3)
+        (1, 4)
     }
-    pub(crate) fn __reduce12<
+    pub(crate) fn __reduce17<
         'input,
     >(
         input: &'input str,
@@ -706,9 +860,9 @@ mod __parse__Expr {
         let __end = __sym0.2.clone();
         let __nt = super::__action4::<>(input, __sym0);
         __symbols.push((__start, __Symbol::Variant2(__nt), __end));
-        (1, 3)
+        (1, 4)
     }
-    pub(crate) fn __reduce13<
+    pub(crate) fn __reduce18<
         'input,
     >(
         input: &'input str,
@@ -723,7 +877,7 @@ mod __parse__Expr {
         let __end = __sym0.2.clone();
         let __nt = super::__action5::<>(input, __sym0);
         __symbols.push((__start, __Symbol::Variant2(__nt), __end));
-        (1, 3)
+        (1, 4)
     }
 }
 pub use self::__parse__Expr::ExprParser;
@@ -750,6 +904,10 @@ mod __intern_token {
             ("^(\\))", false),
             ("^(\\*)", false),
             ("^(\\+)", false),
+            ("^(\\^)", false),
+            ("^(maj)", false),
+            ("^(mux)", false),
+            ("^(xnor)", false),
             (r"^(\s*)", true),
         ];
         __lalrpop_util::lexer::MatcherBuilder::new(__strs.iter().copied()).unwrap()
@@ -864,12 +1022,56 @@ fn __action9<
     (_, __0, _): (usize, &'input str, usize),
 ) -> String
 {
-    "!".to_string()
+    "^".to_string()
 }
 
 #[allow(unused_variables)]
 fn __action10<
     'input,
+>(
+    input: &'input str,
+    (_, __0, _): (usize, &'input str, usize),
+) -> String
+{
+    "xnor".to_string()
+}
+
+#[allow(unused_variables)]
+fn __action11<
+    'input,
+>(
+    input: &'input str,
+    (_, __0, _): (usize, &'input str, usize),
+) -> String
+{
+    "mux".to_string()
+}
+
+#[allow(unused_variables)]
+fn __action12<
+    'input,
+>(
+    input: &'input str,
+    (_, __0, _): (usize, &'input str, usize),
+) -> String
+{
+    "maj".to_string()
+}
+
+#[allow(unused_variables)]
+fn __action13<
+    'input,
+>(
+    input: &'input str,
+    (_, __0, _): (usize, &'input str, usize),
+) -> String
+{
+    "!".to_string()
+}
+
+#[allow(unused_variables)]
+fn __action14<
+    'input,
 >(
     input: &'input str,
     (_, n, _): (usize, String, usize),
@@ -879,7 +1081,7 @@ fn __action10<
 }
 
 #[allow(unused_variables)]
-fn __action11<
+fn __action15<
     'input,
 >(
     input: &'input str,
@@ -891,7 +1093,7 @@ fn __action11<
 }
 
 #[allow(unused_variables)]
-fn __action12<
+fn __action16<
     'input,
 >(
     input: &'input str,
@@ -904,7 +1106,7 @@ fn __action12<
 }
 
 #[allow(unused_variables)]
-fn __action13<
+fn __action17<
     'input,
 >(
     input: &'input str,
@@ -922,7 +1124,7 @@ fn __action13<
 }
 
 #[allow(unused_variables)]
-fn __action14<
+fn __action18<
     'input,
 >(
     input: &'input str,
@@ -936,6 +1138,22 @@ fn __action14<
     Expr::Infix(s, Box::new(e1), Box::new(e2))
 }
 
+#[allow(unused_variables)]
+fn __action19<
+    'input,
+>(
+    input: &'input str,
+    (_, _, _): (usize, &'input str, usize),
+    (_, t, _): (usize, String, usize),
+    (_, e1, _): (usize, Expr, usize),
+    (_, e2, _): (usize, Expr, usize),
+    (_, e3, _): (usize, Expr, usize),
+    (_, _, _): (usize, &'input str, usize),
+) -> Expr
+{
+    Expr::Ternary(t, Box::new(e1), Box::new(e2), Box::new(e3))
+}
+
 pub trait __ToTriple<'input, >
 {
     fn to_triple(value: Self) -> Result<(usize,Token<'input>,usize), __lalrpop_util::ParseError<usize, Token<'input>, &'static str>>;
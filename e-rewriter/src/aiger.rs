@@ -0,0 +1,404 @@
+//! Combinational AIGER files, ASCII (`aag`) and binary (`aig`).
+//!
+//! Reading gives one expression per output over `*`, `!`, the inputs and
+//! the constants `0`/`1`, in DAG form so that and-gates feeding several
+//! outputs or fanouts are not unfolded. Writing lowers any of the circuit
+//! operators (`+`, `^`, `mux`, `maj`, ...) back to and-gates with structural
+//! hashing. Latches and the AIGER 1.9 property sections are rejected, the
+//! flows in this repository are purely combinational.
+
+use egg::*;
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+
+use crate::netlist::{all_inputs, default_names, Netlist, NodeTable};
+
+pub fn is_aiger(bytes: &[u8]) -> bool {
+    bytes.starts_with(b"aag ") || bytes.starts_with(b"aig ")
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn at_end(&self) -> bool {
+        self.pos >= self.bytes.len()
+    }
+
+    fn line(&mut self) -> Result<&'a str, String> {
+        if self.at_end() {
+            return Err("unexpected end of file".to_string());
+        }
+        let rest = &self.bytes[self.pos..];
+        let len = rest.iter().position(|&b| b == b'\n').unwrap_or(rest.len());
+        self.pos += len + 1;
+        std::str::from_utf8(&rest[..len]).map_err(|_| "invalid UTF-8 in a text line".to_string())
+    }
+
+    fn numbers(&mut self, count: usize, what: &str) -> Result<Vec<usize>, String> {
+        let line = self.line()?;
+        let numbers: Vec<usize> = line
+            .split_whitespace()
+            .map(|n| n.parse().map_err(|_| format!("{}: `{}` is not a number", what, n)))
+            .collect::<Result<_, _>>()?;
+        if numbers.len() != count {
+            return Err(format!("{}: expected {} numbers, found `{}`", what, count, line));
+        }
+        Ok(numbers)
+    }
+
+    // the binary format's 7-bit little-endian deltas
+    fn varint(&mut self) -> Result<usize, String> {
+        let mut value = 0usize;
+        let mut shift = 0;
+        loop {
+            let byte = *self.bytes.get(self.pos).ok_or("unexpected end of file in an and-gate delta")?;
+            self.pos += 1;
+            if shift >= usize::BITS {
+                return Err("and-gate delta does not fit in a word".to_string());
+            }
+            value |= ((byte & 0x7f) as usize) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+            shift += 7;
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Def {
+    Input(usize),
+    And(usize, usize),
+}
+
+/// Read an AIGER file. Inputs and outputs without a symbol get ABC's default
+/// names (`pi0`, `po0`, ... zero-padded to the same width), so they line up
+/// with what `read_aiger; write_eqn` gives.
+pub fn parse_aiger<L: FromOp>(bytes: &[u8]) -> Result<Netlist<L>, String> {
+    let mut reader = Reader { bytes, pos: 0 };
+    let header = reader.line()?;
+    let mut fields = header.split_whitespace();
+    let binary = match fields.next() {
+        Some("aag") => false,
+        Some("aig") => true,
+        _ => return Err(format!("not an AIGER file, header `{}`", header)),
+    };
+    let counts: Vec<usize> = fields
+        .map(|n| n.parse().map_err(|_| format!("header: `{}` is not a number", n)))
+        .collect::<Result<_, _>>()?;
+    if counts.len() < 5 {
+        return Err(format!("header: expected `M I L O A`, found `{}`", header));
+    }
+    let (max_var, num_inputs, num_latches, num_outputs, num_ands) = (counts[0], counts[1], counts[2], counts[3], counts[4]);
+    if num_latches > 0 {
+        return Err(format!("{} latches, only combinational AIGER is supported", num_latches));
+    }
+    if counts[5..].iter().any(|&n| n > 0) {
+        return Err("bad-state, constraint, justice and fairness sections are not supported".to_string());
+    }
+    if num_inputs + num_ands > max_var {
+        return Err(format!("header: {} inputs and {} and-gates do not fit in {} variables", num_inputs, num_ands, max_var));
+    }
+
+    let max_lit = 2 * max_var + 1;
+    let check = |lit: usize, what: &str| {
+        if lit > max_lit {
+            Err(format!("{}: literal {} is above the maximum {}", what, lit, max_lit))
+        } else {
+            Ok(lit)
+        }
+    };
+    let mut defs: Vec<Option<Def>> = vec![None; max_var + 1];
+    let mut define = |lit: usize, def: Def, what: &str| {
+        if lit < 2 || lit % 2 == 1 {
+            return Err(format!("{}: {} is not a positive variable literal", what, lit));
+        }
+        match defs.get_mut(lit / 2) {
+            Some(slot @ None) => {
+                *slot = Some(def);
+                Ok(())
+            }
+            Some(Some(_)) => Err(format!("{}: variable {} is defined twice", what, lit / 2)),
+            None => Err(format!("{}: literal {} is above the maximum {}", what, lit, max_lit)),
+        }
+    };
+
+    for i in 0..num_inputs {
+        let lit = if binary { 2 * (i + 1) } else { reader.numbers(1, "input")?[0] };
+        define(lit, Def::Input(i), "input")?;
+    }
+    let mut output_lits = Vec::with_capacity(num_outputs);
+    for _ in 0..num_outputs {
+        output_lits.push(check(reader.numbers(1, "output")?[0], "output")?);
+    }
+    for i in 0..num_ands {
+        let (lhs, rhs0, rhs1) = if binary {
+            let lhs = 2 * (num_inputs + i + 1);
+            let rhs0 = lhs.checked_sub(reader.varint()?).ok_or("and-gate delta below zero")?;
+            let rhs1 = rhs0.checked_sub(reader.varint()?).ok_or("and-gate delta below zero")?;
+            (lhs, rhs0, rhs1)
+        } else {
+            let n = reader.numbers(3, "and-gate")?;
+            (n[0], check(n[1], "and-gate")?, check(n[2], "and-gate")?)
+        };
+        define(lhs, Def::And(rhs0, rhs1), "and-gate")?;
+    }
+
+    let mut inputs = default_names("pi", num_inputs);
+    let mut outputs = default_names("po", num_outputs);
+    while !reader.at_end() {
+        let line = reader.line()?;
+        if line.starts_with('c') {
+            break;
+        }
+        let (position, name) = line.split_once(' ').ok_or_else(|| format!("symbol: expected `i0 name`, found `{}`", line))?;
+        let (kind, index) = position.split_at(1);
+        let index: usize = index.parse().map_err(|_| format!("symbol: bad position `{}`", position))?;
+        let names = match kind {
+            "i" => &mut inputs,
+            "o" => &mut outputs,
+            _ => return Err(format!("symbol: unsupported kind in `{}`", line)),
+        };
+        let slot = names.get_mut(index).ok_or_else(|| format!("symbol: `{}` is out of range", position))?;
+        *slot = name.to_string();
+    }
+
+    let mut table = NodeTable::new();
+    let mut var_ids: Vec<Option<Id>> = vec![None; max_var + 1];
+    var_ids[0] = Some(table.add("0", vec![])?);
+    let one = table.add("1", vec![])?;
+    // literal to node, through a `!` for odd literals
+    let lit_id = |table: &mut NodeTable<L>, var_ids: &[Option<Id>], lit: usize| -> Result<Id, String> {
+        let id = var_ids[lit / 2].ok_or_else(|| format!("variable {} is never defined", lit / 2))?;
+        match lit {
+            1 => Ok(one),
+            lit if lit % 2 == 1 => table.add("!", vec![id]),
+            _ => Ok(id),
+        }
+    };
+
+    // Build every gate after its fanins. The ASCII format does not order
+    // the gates, and deep circuits would overflow a recursive build.
+    let mut in_progress = HashSet::new();
+    for &root in &output_lits {
+        let mut stack = vec![(root / 2, false)];
+        while let Some((var, expanded)) = stack.pop() {
+            if var_ids[var].is_some() {
+                continue;
+            }
+            let def = defs[var].ok_or_else(|| format!("variable {} is never defined", var))?;
+            let (rhs0, rhs1) = match def {
+                Def::Input(i) => {
+                    var_ids[var] = Some(table.add(&inputs[i], vec![])?);
+                    continue;
+                }
+                Def::And(rhs0, rhs1) => (rhs0, rhs1),
+            };
+            if expanded {
+                let a = lit_id(&mut table, &var_ids, rhs0)?;
+                let b = lit_id(&mut table, &var_ids, rhs1)?;
+                var_ids[var] = Some(table.add("*", vec![a, b])?);
+                in_progress.remove(&var);
+                continue;
+            }
+            if !in_progress.insert(var) {
+                return Err(format!("combinational loop through variable {}", var));
+            }
+            stack.push((var, true));
+            for fanin in [rhs0 / 2, rhs1 / 2] {
+                if in_progress.contains(&fanin) {
+                    return Err(format!("combinational loop through variable {}", fanin));
+                }
+                if var_ids[fanin].is_none() {
+                    stack.push((fanin, false));
+                }
+            }
+        }
+    }
+
+    let mut exprs = Vec::with_capacity(num_outputs);
+    for &lit in &output_lits {
+        let root = lit_id(&mut table, &var_ids, lit)?;
+        exprs.push(table.cone(root));
+    }
+    Ok(Netlist { inputs, outputs, exprs })
+}
+
+/// And-gates under construction, with constant propagation and structural hashing.
+struct AigBuilder {
+    num_inputs: usize,
+    ands: Vec<(usize, usize)>,
+    memo: HashMap<(usize, usize), usize>,
+}
+
+impl AigBuilder {
+    fn and(&mut self, a: usize, b: usize) -> usize {
+        if a == 0 || b == 0 || a == b ^ 1 {
+            return 0;
+        }
+        if a == 1 || a == b {
+            return b;
+        }
+        if b == 1 {
+            return a;
+        }
+        let key = (a.max(b), a.min(b));
+        if let Some(lit) = self.memo.get(&key) {
+            return *lit;
+        }
+        self.ands.push(key);
+        let lit = 2 * (self.num_inputs + self.ands.len());
+        self.memo.insert(key, lit);
+        lit
+    }
+
+    fn or(&mut self, a: usize, b: usize) -> usize {
+        self.and(a ^ 1, b ^ 1) ^ 1
+    }
+
+    fn xor(&mut self, a: usize, b: usize) -> usize {
+        let only_a = self.and(a, b ^ 1);
+        let only_b = self.and(a ^ 1, b);
+        self.or(only_a, only_b)
+    }
+
+    fn lower<L: Language + Display>(&mut self, expr: &RecExpr<L>, input_lits: &HashMap<String, usize>) -> Result<usize, String> {
+        let mut lits: Vec<usize> = Vec::with_capacity(expr.as_ref().len());
+        for node in expr.as_ref() {
+            let x: Vec<usize> = node.children().iter().map(|c| lits[usize::from(*c)]).collect();
+            let op = node.to_string();
+            let lit = match (op.as_str(), x.as_slice()) {
+                ("0" | "false", []) => 0,
+                ("1" | "true", []) => 1,
+                (name, []) => input_lits[name],
+                ("!", [a]) => a ^ 1,
+                ("*", [a, b]) => self.and(*a, *b),
+                ("+", [a, b]) => self.or(*a, *b),
+                ("->", [a, b]) => self.or(a ^ 1, *b),
+                ("let" | "xnor", [a, b]) => self.xor(*a, *b) ^ 1,
+                ("&", [a, b]) => self.and(*a, b ^ 1),
+                ("^", [a, b]) => self.xor(*a, *b),
+                ("mux", [s, a, b]) => {
+                    let then = self.and(*s, *a);
+                    let other = self.and(s ^ 1, *b);
+                    self.or(then, other)
+                }
+                ("maj", [a, b, c]) => {
+                    let ab = self.and(*a, *b);
+                    let either = self.or(*a, *b);
+                    let carry = self.and(*c, either);
+                    self.or(ab, carry)
+                }
+                (op, children) => return Err(format!("cannot write `{}` with {} inputs to AIGER", op, children.len())),
+            };
+            lits.push(lit);
+        }
+        Ok(*lits.last().ok_or("empty expression")?)
+    }
+}
+
+/// Lower a multi-output circuit to and-gates and write it as AIGER, binary
+/// (`aig`) or ASCII (`aag`).
+///
+/// `inputs` fixes the order of the inputs, symbols of the expressions that
+/// are not in it follow sorted by name. Outputs are named from `outputs`
+/// when it is not empty.
+pub fn write_aiger<L: Language + Display>(inputs: &[String], outputs: &[String], exprs: &[RecExpr<L>], binary: bool) -> Result<Vec<u8>, String> {
+    let inputs = all_inputs(inputs, exprs);
+
+    let input_lits: HashMap<String, usize> = inputs.iter().enumerate().map(|(i, name)| (name.clone(), 2 * (i + 1))).collect();
+    let mut builder = AigBuilder { num_inputs: inputs.len(), ands: Vec::new(), memo: HashMap::new() };
+    let output_lits = exprs.iter().map(|expr| builder.lower(expr, &input_lits)).collect::<Result<Vec<_>, _>>()?;
+
+    let (num_inputs, num_ands) = (inputs.len(), builder.ands.len());
+    let mut out = Vec::new();
+    let format = if binary { "aig" } else { "aag" };
+    out.extend(format!("{} {} {} 0 {} {}\n", format, num_inputs + num_ands, num_inputs, output_lits.len(), num_ands).bytes());
+    if !binary {
+        for i in 0..num_inputs {
+            out.extend(format!("{}\n", 2 * (i + 1)).bytes());
+        }
+    }
+    for lit in &output_lits {
+        out.extend(format!("{}\n", lit).bytes());
+    }
+    for (i, (rhs0, rhs1)) in builder.ands.iter().enumerate() {
+        let lhs = 2 * (num_inputs + i + 1);
+        if binary {
+            for mut delta in [lhs - rhs0, rhs0 - rhs1] {
+                while delta >= 0x80 {
+                    out.push((delta & 0x7f) as u8 | 0x80);
+                    delta >>= 7;
+                }
+                out.push(delta as u8);
+            }
+        } else {
+            out.extend(format!("{} {} {}\n", lhs, rhs0, rhs1).bytes());
+        }
+    }
+    for (i, name) in inputs.iter().enumerate() {
+        out.extend(format!("i{} {}\n", i, name).bytes());
+    }
+    for (i, name) in outputs.iter().enumerate().take(output_lits.len()) {
+        out.extend(format!("o{} {}\n", i, name).bytes());
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cec;
+    use crate::eqn::parse_eqn;
+    use crate::prop::{Circuit, Prop};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn parse(bytes: &[u8]) -> Result<Circuit, String> {
+        parse_aiger(bytes)
+    }
+
+    #[test]
+    fn reads_ascii_aiger() {
+        // y = !(a * !b), the second input without a symbol
+        let circuit = parse(b"aag 3 2 0 1 1\n2\n4\n7\n6 2 5\ni0 a\no0 y\nc\nanything\n").unwrap();
+        assert_eq!(circuit.inputs, ["a", "pi1"]);
+        assert_eq!(circuit.outputs, ["y"]);
+        assert_eq!(circuit.exprs[0].to_string(), "(! (* a (! pi1)))");
+    }
+
+    #[test]
+    fn writes_c17_back() {
+        let c17: Circuit = parse_eqn(include_str!("../../benchmark/converted_circuit/ISCAS85/c17.eqn")).unwrap();
+        for binary in [false, true] {
+            let bytes = write_aiger(&c17.inputs, &c17.outputs, &c17.exprs, binary).unwrap();
+            // six and-gates, `pi2 * pi3` and `pi1 * !(pi2 * pi3)` shared
+            assert!(bytes.starts_with(if binary { b"aig 11 5 0 2 6\n" } else { b"aag 11 5 0 2 6\n" }));
+            let back = parse(&bytes).unwrap();
+            assert_eq!((&back.inputs, &back.outputs), (&c17.inputs, &c17.outputs));
+            let cex = cec::check_equivalence(&c17.exprs, &back.exprs, &c17.inputs, &mut StdRng::seed_from_u64(1)).unwrap();
+            assert!(cex.is_none());
+        }
+    }
+
+    #[test]
+    fn lowers_every_operator() {
+        let exprs: Vec<RecExpr<Prop>> = ["(^ a b)", "(maj a b (mux s a b))", "(-> a (& b true))", "(let a (+ b false))"].iter().map(|e| e.parse().unwrap()).collect();
+        let back = parse(&write_aiger(&[], &[], &exprs, true).unwrap()).unwrap();
+        assert_eq!(back.inputs, ["a", "b", "s"]);
+        for (expr, back) in exprs.iter().zip(&back.exprs) {
+            assert!(cec::prove_equal(expr, back).unwrap(), "{} became {}", expr, back);
+        }
+    }
+
+    #[test]
+    fn rejects_latches_and_loops() {
+        let error = |bytes: &[u8]| parse(bytes).err().unwrap_or_else(|| panic!("read {}", String::from_utf8_lossy(bytes)));
+        assert!(error(b"aag 1 0 1 1 0\n2 3\n2\n").contains("latches"));
+        assert!(error(b"aag 2 0 0 1 2\n2\n2 4 1\n4 2 1\n").contains("combinational loop"));
+        assert!(error(b"aag 1 1 0 1 0\n2\n5\n").contains("above the maximum"));
+    }
+}
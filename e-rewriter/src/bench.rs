@@ -0,0 +1,193 @@
+//! ISCAS `.bench` netlists.
+//!
+//! `INPUT(x)`, `OUTPUT(x)` and `y = GATE(a, b, ...)` with the gates AND,
+//! NAND, OR, NOR, XOR, XNOR, NOT, BUFF and DFF. Flip-flops are cut like
+//! BLIF latches: the DFF output is read as an input and its data signal is
+//! an output, so ISCAS89 circuits are optimised as their combinational part.
+//! Net names are kept as written, only a net named like a constant (`1` is
+//! an input of c17 and c432) gets an `N` in front, as in the Verilog
+//! versions of the suites.
+
+use egg::*;
+
+use std::collections::{HashMap, HashSet};
+
+use crate::netlist::{build_signals, is_constant, Netlist, NodeTable};
+
+pub fn is_bench(contents: &str) -> bool {
+    contents.lines().any(|line| line.trim_start().starts_with("INPUT("))
+}
+
+struct Gate {
+    kind: String,
+    fanins: Vec<String>,
+}
+
+// `NAME(args)` split into the name and the comma-separated arguments.
+fn call(s: &str) -> Option<(&str, Vec<&str>)> {
+    let (name, rest) = s.split_once('(')?;
+    let args = rest.trim_end().strip_suffix(')')?;
+    Some((name.trim(), args.split(',').map(str::trim).filter(|a| !a.is_empty()).collect()))
+}
+
+fn add_gate<L: FromOp>(table: &mut NodeTable<L>, gate: &Gate, ids: &[Id]) -> Result<Id, String> {
+    // fold a gate of any width from its 2-input operator
+    let fold = |table: &mut NodeTable<L>, op: &str| -> Result<Id, String> {
+        let mut acc = ids[0];
+        for &id in &ids[1..] {
+            acc = table.add(op, vec![acc, id])?;
+        }
+        Ok(acc)
+    };
+    let (op, inverted) = match gate.kind.as_str() {
+        "AND" => ("*", false),
+        "NAND" => ("*", true),
+        "OR" => ("+", false),
+        "NOR" => ("+", true),
+        "XOR" => ("^", false),
+        "XNOR" => ("^", true),
+        "BUFF" | "BUF" => return Ok(ids[0]),
+        "NOT" => return table.add("!", vec![ids[0]]),
+        kind => unreachable!("gate `{}` is rejected by the parser", kind),
+    };
+    let id = fold(table, op)?;
+    if inverted {
+        table.add("!", vec![id])
+    } else {
+        Ok(id)
+    }
+}
+
+/// Read a `.bench` netlist.
+pub fn parse_bench<L: FromOp>(contents: &str) -> Result<Netlist<L>, String> {
+    let mut inputs = Vec::new();
+    let mut outputs = Vec::new();
+    let mut gates: HashMap<String, Gate> = HashMap::new();
+    let mut next_states = Vec::new();
+
+    for (number, line) in contents.lines().enumerate() {
+        let line = line.split('#').next().unwrap().trim();
+        if line.is_empty() {
+            continue;
+        }
+        let err = |msg: &str| format!("line {}: {}: `{}`", number + 1, msg, line);
+        if let Some((lhs, rhs)) = line.split_once('=') {
+            let (kind, fanins) = call(rhs).ok_or_else(|| err("expected `name = GATE(a, b, ...)`"))?;
+            let kind = kind.to_ascii_uppercase();
+            let arity_ok = match kind.as_str() {
+                "NOT" | "BUFF" | "BUF" | "DFF" => fanins.len() == 1,
+                "AND" | "NAND" | "OR" | "NOR" | "XOR" | "XNOR" => !fanins.is_empty(),
+                _ => return Err(err(&format!("unknown gate `{}`", kind))),
+            };
+            if !arity_ok {
+                return Err(err(&format!("{} with {} inputs", kind, fanins.len())));
+            }
+            let name = lhs.trim().to_string();
+            if kind == "DFF" {
+                inputs.push(name);
+                next_states.push(fanins[0].to_string());
+                continue;
+            }
+            let gate = Gate { kind, fanins: fanins.into_iter().map(String::from).collect() };
+            if gates.insert(name.clone(), gate).is_some() {
+                return Err(err(&format!("net `{}` is driven twice", name)));
+            }
+        } else {
+            match call(line) {
+                Some(("INPUT", args)) if args.len() == 1 => inputs.push(args[0].to_string()),
+                Some(("OUTPUT", args)) if args.len() == 1 => outputs.push(args[0].to_string()),
+                _ => return Err(err("expected `INPUT(x)`, `OUTPUT(x)` or `name = GATE(...)`")),
+            }
+        }
+    }
+    outputs.extend(next_states);
+    let mut seen = HashSet::new();
+    outputs.retain(|o| seen.insert(o.clone()));
+
+    // nets named like a constant get `N` prefixes until the name is free
+    let nets: HashSet<&str> = inputs.iter().chain(gates.keys()).map(String::as_str).collect();
+    let rename = |name: &String| {
+        let mut symbol = name.clone();
+        while is_constant(&symbol) || (symbol != *name && nets.contains(symbol.as_str())) {
+            symbol.insert(0, 'N');
+        }
+        symbol
+    };
+
+    let mut table = NodeTable::new();
+    let mut signals: HashMap<String, Id> = HashMap::new();
+    for name in &inputs {
+        if gates.contains_key(name) {
+            return Err(format!("input `{}` is also driven by a gate", name));
+        }
+        signals.insert(name.clone(), table.add(&rename(name), vec![])?);
+    }
+    build_signals(&outputs, &gates, &mut signals, |gate| &gate.fanins, |gate, signals| {
+        let ids: Vec<Id> = gate.fanins.iter().map(|f| signals[f]).collect();
+        add_gate(&mut table, gate, &ids)
+    })?;
+
+    let exprs = outputs.iter().map(|o| table.cone(signals[o])).collect();
+    Ok(Netlist { inputs: inputs.iter().map(rename).collect(), outputs: outputs.iter().map(rename).collect(), exprs })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cec;
+    use crate::eqn::parse_eqn;
+    use crate::prop::{Circuit, Prop};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    const C17: &str = "# c17\nINPUT(1)\nINPUT(2)\nINPUT(3)\nINPUT(6)\nINPUT(7)\nOUTPUT(22)\nOUTPUT(23)\n\
+        10 = NAND(1, 3)\n11 = NAND(3, 6)\n16 = NAND(2, 11)\n19 = NAND(11, 7)\n22 = NAND(10, 16)\n23 = NAND(16, 19)\n";
+
+    fn parse(contents: &str) -> Result<Circuit, String> {
+        parse_bench(contents)
+    }
+
+    #[test]
+    fn reads_c17_like_abc() {
+        let c17 = parse(C17).unwrap();
+        // `1` would read as the constant
+        assert_eq!(c17.inputs, ["N1", "2", "3", "6", "7"]);
+        assert_eq!(c17.outputs, ["22", "23"]);
+        // the ABC conversion names the same inputs pi0..pi4
+        let eqn: Circuit = parse_eqn(include_str!("../../benchmark/converted_circuit/ISCAS85/c17.eqn")).unwrap();
+        let renamed: Vec<RecExpr<Prop>> = eqn
+            .exprs
+            .iter()
+            .map(|e| {
+                let mut text = e.to_string();
+                for (pi, name) in eqn.inputs.iter().zip(&c17.inputs) {
+                    text = text.replace(pi.as_str(), name);
+                }
+                text.parse().unwrap()
+            })
+            .collect();
+        let cex = cec::check_equivalence(&c17.exprs, &renamed, &c17.inputs, &mut StdRng::seed_from_u64(1)).unwrap();
+        assert!(cex.is_none());
+    }
+
+    #[test]
+    fn reads_every_gate_and_cuts_flip_flops() {
+        let circuit = parse(
+            "INPUT(a)\nINPUT(b)\nINPUT(c)\nOUTPUT(y)\nq = DFF(d)\nd = xor(a, q, c)\nn = NOR(a, b, c)\nx = XNOR(a, b)\nt = BUFF(x)\ny = OR(n, t, e)\ne = NOT(q)\n",
+        )
+        .unwrap();
+        assert_eq!(circuit.inputs, ["a", "b", "c", "q"]);
+        assert_eq!(circuit.outputs, ["y", "d"]);
+        let equal = |a: &RecExpr<Prop>, b: &str| cec::prove_equal(a, &b.parse().unwrap()).unwrap();
+        assert!(equal(&circuit.exprs[0], "(+ (+ (! (+ (+ a b) c)) (xnor a b)) (! q))"));
+        assert!(equal(&circuit.exprs[1], "(^ (^ a q) c)"));
+    }
+
+    #[test]
+    fn rejects_broken_netlists() {
+        let error = |contents: &str| parse(contents).err().unwrap_or_else(|| panic!("read {}", contents));
+        assert!(error("INPUT(a)\nOUTPUT(y)\ny = MUX(a, a, a)\n").contains("unknown gate"));
+        assert!(error("INPUT(a)\nOUTPUT(y)\ny = NOT(a, a)\n").contains("NOT with 2 inputs"));
+        assert!(error("INPUT(a)\nOUTPUT(y)\ny = NOT(a)\ny = BUFF(a)\n").contains("driven twice"));
+        assert!(error("INPUT(a)\nOUTPUT(y)\ny = AND(a, z)\nz = NOT(y)\n").contains("combinational loop"));
+    }
+}
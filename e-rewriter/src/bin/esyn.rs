@@ -0,0 +1,364 @@
+//! `esyn`: the whole flow of `run_beta.py` in one process. Circuits are
+//! read once into typed netlists and passed between the steps in memory;
+//! files are only written where the command line says.
+
+use std::collections::HashSet;
+use std::error::Error;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command as Process, ExitCode};
+use std::time::Instant;
+
+use clap::{Parser, Subcommand};
+use e_rewriter::cec;
+use e_rewriter::cli::{OptimizeArgs, RuleArgs, VerbosityArgs};
+use e_rewriter::liberty::Library;
+use e_rewriter::netlist::{read_netlist, write_netlist};
+use e_rewriter::optimize::Optimizer;
+use e_rewriter::proof;
+use e_rewriter::prop::{Circuit, Prop};
+use e_rewriter::stats::Stats;
+use egg::RecExpr;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[derive(Parser, Debug)]
+#[command(name = "esyn", about = "Optimise, convert, analyse and check circuits with e-rewriter")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+
+    #[command(flatten)]
+    verbosity: VerbosityArgs,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Optimise a circuit and write the best result in the format of OUTPUT's extension
+    Optimize {
+        input: String,
+        output: String,
+
+        /// Directory for the other top-K results, `<output stem>_{i}.<ext>`
+        #[arg(short = 'd', long, value_name = "DIR")]
+        work_dir: Option<String>,
+
+        #[command(flatten)]
+        optimize: OptimizeArgs,
+    },
+    /// Convert a circuit to the format of OUTPUT's extension
+    Convert { input: String, output: String },
+    /// Print the size, depth and operators of a circuit
+    Analyze {
+        input: String,
+
+        /// Liberty library for the cell area and delay
+        #[arg(long, value_name = "FILE")]
+        liberty: Option<String>,
+    },
+    /// Check that two circuits are equivalent, matching outputs by name
+    Verify {
+        first: String,
+        second: String,
+
+        /// Seed of the random simulation
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+    /// Replay a proof written with `--proof` against a rule set, independently of the optimiser
+    CheckProof {
+        input: String,
+        result: String,
+        proof: String,
+
+        /// Prove `fraig` and `analysis` merges, and results that spell out
+        /// operators their format lacks, by simulation or SAT
+        #[arg(long)]
+        cec: bool,
+
+        #[command(flatten)]
+        rules: RuleArgs,
+    },
+    /// Optimise every circuit under PATHS and print a table of the results
+    Bench {
+        /// Circuit files, or directories searched for them
+        #[arg(required = true)]
+        paths: Vec<String>,
+
+        /// Directory for the optimised circuits, as eqn
+        #[arg(short = 'd', long, value_name = "DIR")]
+        work_dir: Option<String>,
+
+        /// Also write the table as CSV
+        #[arg(long, value_name = "FILE")]
+        csv: Option<String>,
+
+        /// ABC binary: map both circuits with the script of run_beta.py and report `stime`
+        #[arg(long, value_name = "PATH", requires = "liberty")]
+        abc: Option<String>,
+
+        #[command(flatten)]
+        optimize: OptimizeArgs,
+    },
+}
+
+// Errors of the file-level steps name the file they are about.
+fn read_circuit(path: &str) -> Result<Circuit, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("{}: {}", path, e))?;
+    read_netlist(&bytes).map_err(|e| format!("{}: {}", path, e))
+}
+
+fn write_circuit(path: &Path, circuit: &Circuit, exprs: &[RecExpr<Prop>]) -> Result<(), String> {
+    let bytes = write_netlist(path, &circuit.inputs, &circuit.outputs, exprs).map_err(|e| format!("{}: {}", path.display(), e))?;
+    File::create(path).and_then(|mut f| f.write_all(&bytes)).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+fn optimize(input: &str, output: &str, work_dir: Option<&str>, args: &OptimizeArgs) -> Result<(), Box<dyn Error>> {
+    let output = Path::new(output);
+    // an output format without a writer fails before the run rather than after it
+    write_netlist::<Prop>(output, &[], &[], &[]).map_err(|e| format!("{}: {}", output.display(), e))?;
+    let mut optimizer = Optimizer::new(args)?;
+    let circuit = read_circuit(input)?;
+    let results = optimizer.run(&circuit)?;
+    if let Some(best) = results.first() {
+        write_circuit(output, &circuit, best)?;
+    }
+    if let Some(dir) = work_dir {
+        std::fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir, e))?;
+        let stem = output.file_stem().and_then(|s| s.to_str()).unwrap_or("output");
+        let extension = output.extension().and_then(|e| e.to_str()).unwrap_or("txt");
+        for (i, result) in results.iter().enumerate() {
+            write_circuit(&Path::new(dir).join(format!("{}_{}.{}", stem, i, extension)), &circuit, result)?;
+        }
+    }
+    Ok(())
+}
+
+fn analyze(input: &str, liberty: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let library = match liberty {
+        Some(path) => Some(Library::parse(&std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?)?),
+        None => None,
+    };
+    let circuit = read_circuit(input)?;
+    print!("{}", Stats::new(&circuit.inputs, &circuit.exprs, library.as_ref()));
+    Ok(())
+}
+
+fn verify(first: &str, second: &str, seed: Option<u64>) -> Result<(), Box<dyn Error>> {
+    let a = read_circuit(first)?;
+    let b = read_circuit(second)?;
+    if a.exprs.len() != b.exprs.len() {
+        return Err(format!("{} has {} outputs, {} has {}", first, a.exprs.len(), second, b.exprs.len()).into());
+    }
+    // by name if both files name their outputs, by position otherwise
+    let exprs = if a.outputs.is_empty() || b.outputs.is_empty() {
+        b.exprs
+    } else {
+        let mut exprs = Vec::new();
+        for name in &a.outputs {
+            let i = b.outputs.iter().position(|o| o == name).ok_or_else(|| format!("output `{}` is missing from {}", name, second))?;
+            exprs.push(b.exprs[i].clone());
+        }
+        exprs
+    };
+    let mut rng = StdRng::seed_from_u64(seed.unwrap_or_else(rand::random));
+    // the counterexample is printed, the error only needs to say what failed
+    cec::check_result(&a, &exprs, &mut rng).map_err(|_| format!("{} and {} are not equivalent", first, second))?;
+    println!("equivalent");
+    Ok(())
+}
+
+fn check_proof(input: &str, result: &str, proof: &str, cec: bool, rules: &RuleArgs) -> Result<(), Box<dyn Error>> {
+    let specs = rules.load()?;
+    let enabled: HashSet<String> = rules.enable.iter().cloned().collect();
+    let text = std::fs::read_to_string(proof).map_err(|e| format!("{}: {}", proof, e))?;
+    let report = proof::check_proof(&text, &specs, &enabled, &read_circuit(input)?, &read_circuit(result)?, cec).map_err(|e| format!("{}: {}", proof, e))?;
+    println!(
+        "proof checked: {} outputs, {} steps, {} rewrites by {} rules, {} constants folded",
+        report.outputs, report.steps, report.rewrites, report.rules, report.folds
+    );
+    if cec {
+        println!(
+            "proven by simulation or SAT: {} merges, {} outputs that differ from {} in form only",
+            report.cec_merges, report.cec_outputs, result
+        );
+    }
+    Ok(())
+}
+
+const CIRCUIT_EXTENSIONS: &[&str] = &["eqn", "aig", "aag", "blif", "bench"];
+
+// The circuit files among `paths`, directories searched recursively, sorted.
+fn circuit_files(paths: &[String]) -> Result<Vec<PathBuf>, String> {
+    let mut files = Vec::new();
+    let mut stack: Vec<PathBuf> = paths.iter().map(PathBuf::from).collect();
+    while let Some(path) = stack.pop() {
+        if path.is_dir() {
+            let entries = std::fs::read_dir(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+            for entry in entries {
+                let entry = entry.map_err(|e| format!("{}: {}", path.display(), e))?;
+                let child = entry.path();
+                let known = child.extension().and_then(|e| e.to_str()).is_some_and(|e| CIRCUIT_EXTENSIONS.contains(&e));
+                if child.is_dir() || known {
+                    stack.push(child);
+                }
+            }
+        } else if path.exists() {
+            files.push(path);
+        } else {
+            return Err(format!("{}: no such file or directory", path.display()));
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+// ABC colours its reports with ANSI escape sequences.
+fn strip_escapes(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            chars.by_ref().find(|c| c.is_ascii_alphabetic());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+// Area and delay after the mapping script of run_beta.py, from ABC's `stime` report.
+fn abc_map(abc: &str, liberty: &str, circuit: &Path) -> Result<(f64, f64), String> {
+    let script = format!(
+        "read \"{}\"; strash; dch -f; read_lib \"{}\"; map; topo; upsize; dnsize; stime",
+        circuit.display(),
+        liberty
+    );
+    let output = Process::new(abc).arg("-c").arg(&script).output().map_err(|e| format!("{}: {}", abc, e))?;
+    let stdout = strip_escapes(&String::from_utf8_lossy(&output.stdout));
+    let report = stdout
+        .lines()
+        .rev()
+        .find(|line| line.contains("Area =") && line.contains("Delay ="))
+        .ok_or_else(|| format!("ABC printed no `stime` report for {}", circuit.display()))?;
+    let value = |key: &str| -> Result<f64, String> {
+        report
+            .split(key)
+            .nth(1)
+            .and_then(|rest| rest.split_whitespace().next())
+            .and_then(|v| v.parse().ok())
+            .ok_or_else(|| format!("cannot read `{}` from ABC's `{}`", key, report.trim()))
+    };
+    Ok((value("Area =")?, value("Delay =")?))
+}
+
+struct BenchOptions<'a> {
+    work_dir: &'a Path,
+    abc: Option<&'a str>,
+    liberty: Option<&'a str>,
+}
+
+// One row of the table: stats before and after, then ABC's, then the run time.
+fn bench_row(optimizer: &mut Optimizer, file: &Path, options: &BenchOptions) -> Result<Vec<String>, String> {
+    let circuit = read_circuit(&file.display().to_string())?;
+    let start = Instant::now();
+    let results = optimizer.run(&circuit).map_err(|e| format!("{}: {}", file.display(), e))?;
+    let seconds = start.elapsed().as_secs_f64();
+    let best = results.first().ok_or_else(|| format!("{}: no result", file.display()))?;
+
+    let before = Stats::new(&circuit.inputs, &circuit.exprs, optimizer.library());
+    let after = Stats::new(&circuit.inputs, best, optimizer.library());
+    let mut row = vec![
+        before.inputs.to_string(),
+        before.outputs.to_string(),
+        before.nodes.to_string(),
+        after.nodes.to_string(),
+        before.depth.to_string(),
+        after.depth.to_string(),
+    ];
+    for (x, y) in [(before.area, after.area), (before.delay, after.delay)] {
+        if let (Some(x), Some(y)) = (x, y) {
+            row.extend([format!("{:.2}", x), format!("{:.2}", y)]);
+        }
+    }
+    let stem = file.file_stem().and_then(|s| s.to_str()).unwrap_or("circuit");
+    let optimized = options.work_dir.join(format!("{}.eqn", stem));
+    write_circuit(&optimized, &circuit, best)?;
+    if let (Some(abc), Some(liberty)) = (options.abc, options.liberty) {
+        let (area, delay) = abc_map(abc, liberty, file)?;
+        let (area_opt, delay_opt) = abc_map(abc, liberty, &optimized)?;
+        row.extend([area, area_opt, delay, delay_opt].iter().map(|v| format!("{:.2}", v)));
+    }
+    row.push(format!("{:.2}", seconds));
+    Ok(row)
+}
+
+fn bench(paths: &[String], work_dir: Option<&str>, csv: Option<&str>, abc: Option<&str>, args: &OptimizeArgs) -> Result<(), Box<dyn Error>> {
+    let files = circuit_files(paths)?;
+    // a directory of its own unless one is given, so that runs side by side do not share files
+    let work_dir = match work_dir {
+        Some(dir) => PathBuf::from(dir),
+        None => std::env::temp_dir().join(format!("esyn-{}", std::process::id())),
+    };
+    std::fs::create_dir_all(&work_dir).map_err(|e| format!("{}: {}", work_dir.display(), e))?;
+    let mut optimizer = Optimizer::new(args)?;
+    let options = BenchOptions { work_dir: &work_dir, abc, liberty: args.liberty.as_deref() };
+
+    let mut header = vec!["circuit", "inputs", "outputs", "nodes", "nodes_opt", "depth", "depth_opt"];
+    if optimizer.library().is_some() {
+        header.extend(["area", "area_opt", "delay", "delay_opt"]);
+    }
+    if abc.is_some() {
+        header.extend(["abc_area", "abc_area_opt", "abc_delay", "abc_delay_opt"]);
+    }
+    header.push("seconds");
+    let mut table = vec![header.iter().map(|h| h.to_string()).collect::<Vec<_>>()];
+    println!("{}", header.join("\t"));
+
+    let mut failed = 0;
+    for file in &files {
+        let name = file.display().to_string();
+        match bench_row(&mut optimizer, file, &options) {
+            Ok(row) => {
+                let row: Vec<String> = std::iter::once(name).chain(row).collect();
+                println!("{}", row.join("\t"));
+                table.push(row);
+            }
+            Err(e) => {
+                failed += 1;
+                println!("{}\terror: {}", name, e);
+            }
+        }
+    }
+    if let Some(path) = csv {
+        let text: String = table.iter().map(|row| row.join(",") + "\n").collect();
+        std::fs::write(path, text).map_err(|e| format!("{}: {}", path, e))?;
+    }
+    if failed > 0 {
+        return Err(format!("{} of {} circuits failed", failed, files.len()).into());
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    e_rewriter::set_verbosity(cli.verbosity.level());
+    let result = match &cli.command {
+        Command::Optimize { input, output, work_dir, optimize: args } => optimize(input, output, work_dir.as_deref(), args),
+        Command::Convert { input, output } => read_circuit(input)
+            .and_then(|circuit| write_circuit(Path::new(output), &circuit, &circuit.exprs))
+            .map_err(Into::into),
+        Command::Analyze { input, liberty } => analyze(input, liberty.as_deref()),
+        Command::Verify { first, second, seed } => verify(first, second, *seed),
+        Command::CheckProof { input, result, proof, cec, rules } => check_proof(input, result, proof, *cec, rules),
+        Command::Bench { paths, work_dir, csv, abc, optimize: args } => bench(paths, work_dir.as_deref(), csv.as_deref(), abc.as_deref(), args),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("esyn: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,330 @@
+//! Berkeley Logic Interchange Format (BLIF).
+//!
+//! The reader takes the first `.model` of a file: `.inputs`, `.outputs`,
+//! `.names` covers (on-set or off-set) and `.latch`. Registers are cut, a
+//! latch output becomes an input and its next-state signal an output, so
+//! sequential benchmarks are optimised as their combinational part. The
+//! writer emits one `.names` cover per operator node, sharing logic between
+//! outputs through `new_n*_` signals.
+
+use egg::*;
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+
+use crate::netlist::{all_inputs, build_signals, default_names, Netlist, NodeTable};
+
+pub fn is_blif(contents: &str) -> bool {
+    contents.lines().any(|line| {
+        let line = line.trim_start();
+        line.starts_with(".model") || line.starts_with(".inputs") || line.starts_with(".names")
+    })
+}
+
+/// A `.names` block: the fanins, and the cubes of the cover over them.
+struct Cover {
+    fanins: Vec<String>,
+    cubes: Vec<Vec<u8>>,
+    // false when the rows list the off-set (output column `0`)
+    on_set: bool,
+}
+
+// Logical lines: comments dropped and `\` continuations joined.
+fn logical_lines(contents: &str) -> Vec<(usize, String)> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    let mut start = 0;
+    for (number, line) in contents.lines().enumerate() {
+        let line = line.split('#').next().unwrap();
+        if current.is_empty() {
+            start = number + 1;
+        }
+        match line.trim_end().strip_suffix('\\') {
+            Some(part) => {
+                current.push_str(part);
+                current.push(' ');
+            }
+            None => {
+                current.push_str(line);
+                if !current.trim().is_empty() {
+                    lines.push((start, std::mem::take(&mut current)));
+                }
+            }
+        }
+    }
+    if !current.trim().is_empty() {
+        lines.push((start, current));
+    }
+    lines
+}
+
+fn parse_cover(fanins: Vec<String>, rows: &[String]) -> Result<Cover, String> {
+    let mut cubes = Vec::new();
+    let mut on_set = None;
+    for row in rows {
+        let fields: Vec<&str> = row.split_whitespace().collect();
+        let (cube, value) = match fields.as_slice() {
+            [value] if fanins.is_empty() => ("", *value),
+            [cube, value] => (*cube, *value),
+            _ => return Err(format!("bad cover row `{}`", row.trim())),
+        };
+        if cube.len() != fanins.len() || !cube.bytes().all(|c| matches!(c, b'0' | b'1' | b'-')) {
+            return Err(format!("cover row `{}` does not match {} fanins", row.trim(), fanins.len()));
+        }
+        let value = match value {
+            "1" => true,
+            "0" => false,
+            _ => return Err(format!("bad output value in `{}`", row.trim())),
+        };
+        if on_set.is_some_and(|v| v != value) {
+            return Err("a cover mixes on-set and off-set rows".to_string());
+        }
+        on_set = Some(value);
+        cubes.push(cube.as_bytes().to_vec());
+    }
+    // an empty cover is constant 0
+    Ok(Cover { fanins, cubes, on_set: on_set.unwrap_or(true) })
+}
+
+// Sum of products of a cover, with `ids` the nodes of its fanins.
+fn add_cover<L: FromOp>(table: &mut NodeTable<L>, cover: &Cover, ids: &[Id]) -> Result<Id, String> {
+    let mut sum: Option<Id> = None;
+    for cube in &cover.cubes {
+        let mut product: Option<Id> = None;
+        for (&c, &id) in cube.iter().zip(ids) {
+            let literal = match c {
+                b'1' => id,
+                b'0' => table.add("!", vec![id])?,
+                _ => continue,
+            };
+            product = Some(match product {
+                Some(p) => table.add("*", vec![p, literal])?,
+                None => literal,
+            });
+        }
+        let product = match product {
+            Some(p) => p,
+            None => table.add("1", vec![])?,
+        };
+        sum = Some(match sum {
+            Some(s) => table.add("+", vec![s, product])?,
+            None => product,
+        });
+    }
+    let sum = match sum {
+        Some(s) => s,
+        None => table.add("0", vec![])?,
+    };
+    if cover.on_set {
+        Ok(sum)
+    } else {
+        table.add("!", vec![sum])
+    }
+}
+
+/// Read the first model of a BLIF file.
+pub fn parse_blif<L: FromOp>(contents: &str) -> Result<Netlist<L>, String> {
+    let mut inputs = Vec::new();
+    let mut outputs = Vec::new();
+    let mut covers: HashMap<String, Cover> = HashMap::new();
+
+    let lines = logical_lines(contents);
+    let mut i = 0;
+    let mut seen_model = false;
+    while i < lines.len() {
+        let (number, line) = &lines[i];
+        let err = |msg: String| format!("line {}: {}", number, msg);
+        let mut words = line.split_whitespace();
+        let command = words.next().unwrap();
+        let args: Vec<String> = words.map(String::from).collect();
+        i += 1;
+        match command {
+            ".model" if seen_model => break,
+            ".model" => seen_model = true,
+            ".end" => break,
+            ".inputs" => inputs.extend(args),
+            ".outputs" => outputs.extend(args),
+            ".names" => {
+                let mut fanins = args;
+                let output = fanins.pop().ok_or_else(|| err("`.names` without signals".to_string()))?;
+                let mut rows = Vec::new();
+                while i < lines.len() && !lines[i].1.trim_start().starts_with('.') {
+                    rows.push(lines[i].1.clone());
+                    i += 1;
+                }
+                let cover = parse_cover(fanins, &rows).map_err(|e| err(format!("`{}`: {}", output, e)))?;
+                if covers.insert(output.clone(), cover).is_some() {
+                    return Err(err(format!("signal `{}` is defined twice", output)));
+                }
+            }
+            ".latch" => {
+                // cut the register: its output is read like an input, its next state is an output
+                let (next, current) = match args.as_slice() {
+                    [next, current, ..] => (next.clone(), current.clone()),
+                    _ => return Err(err("`.latch` needs an input and an output".to_string())),
+                };
+                inputs.push(current);
+                outputs.push(next);
+            }
+            ".clock" | ".wire_load_slope" | ".default_input_arrival" | ".default_output_required" | ".input_arrival" | ".output_required" => {}
+            other => return Err(err(format!("`{}` is not supported", other))),
+        }
+    }
+    let mut seen = HashSet::new();
+    outputs.retain(|o| seen.insert(o.clone()));
+
+    let mut table = NodeTable::new();
+    let mut signals: HashMap<String, Id> = HashMap::new();
+    for name in &inputs {
+        if covers.contains_key(name) {
+            return Err(format!("input `{}` is also driven by a `.names`", name));
+        }
+        let id = table.add(name, vec![])?;
+        signals.insert(name.clone(), id);
+    }
+
+    build_signals(&outputs, &covers, &mut signals, |cover| &cover.fanins, |cover, signals| {
+        let ids: Vec<Id> = cover.fanins.iter().map(|f| signals[f]).collect();
+        add_cover(&mut table, cover, &ids)
+    })?;
+
+    let exprs = outputs.iter().map(|o| table.cone(signals[o])).collect();
+    Ok(Netlist { inputs, outputs, exprs })
+}
+
+// The on-set of an operator over its children, None for operators BLIF cannot express.
+fn operator_cover(op: &str, arity: usize) -> Option<&'static [&'static str]> {
+    let cover: &[&str] = match (op, arity) {
+        ("!", 1) => &["0"],
+        ("*", 2) => &["11"],
+        ("+", 2) => &["1-", "-1"],
+        ("->", 2) => &["0-", "-1"],
+        ("let" | "xnor", 2) => &["00", "11"],
+        ("&", 2) => &["10"],
+        ("^", 2) => &["01", "10"],
+        ("mux", 3) => &["11-", "0-1"],
+        ("maj", 3) => &["11-", "1-1", "-11"],
+        _ => return None,
+    };
+    Some(cover)
+}
+
+/// Write a multi-output circuit as a BLIF model.
+///
+/// `inputs` fixes the order of the inputs, symbols of the expressions that
+/// are not in it follow sorted by name. Without `outputs` names, the
+/// outputs are called `po0`, `po1`, ...
+pub fn write_blif<L: Language + Display>(model: &str, inputs: &[String], outputs: &[String], exprs: &[RecExpr<L>]) -> Result<String, String> {
+    let inputs = all_inputs(inputs, exprs);
+    let outputs = if outputs.is_empty() { default_names("po", exprs.len()) } else { outputs.to_vec() };
+
+    let mut table = NodeTable::new();
+    let roots: Vec<Id> = exprs.iter().map(|expr| table.add_expr(expr)).collect();
+
+    // operator nodes driving an output take its name, the others get ABC-style `new_n*_` names
+    let mut names: HashMap<Id, String> = HashMap::new();
+    for (id, node) in table.nodes.iter().enumerate() {
+        if node.is_leaf() {
+            names.insert(Id::from(id), node.to_string());
+        }
+    }
+    for (root, name) in roots.iter().zip(&outputs) {
+        names.entry(*root).or_insert_with(|| name.clone());
+    }
+    for id in 0..table.nodes.len() {
+        names.entry(Id::from(id)).or_insert_with(|| format!("new_n{}_", id));
+    }
+
+    let mut out = format!(".model {}\n.inputs {}\n.outputs {}\n", model, inputs.join(" "), outputs.join(" "));
+    for (id, node) in table.nodes.iter().enumerate() {
+        let name = &names[&Id::from(id)];
+        if node.is_leaf() {
+            // constants are driven by an empty cover (0) or a single `1` row
+            match name.as_str() {
+                "1" | "true" => out.push_str(&format!(".names {}\n1\n", name)),
+                "0" | "false" => out.push_str(&format!(".names {}\n", name)),
+                _ => {}
+            }
+            continue;
+        }
+        let op = node.to_string();
+        let cover = operator_cover(&op, node.len()).ok_or_else(|| format!("cannot write `{}` to BLIF", op))?;
+        let fanins: Vec<&str> = node.children().iter().map(|c| names[c].as_str()).collect();
+        out.push_str(&format!(".names {} {}\n", fanins.join(" "), name));
+        for cube in cover {
+            out.push_str(&format!("{} 1\n", cube));
+        }
+    }
+    // outputs that are an input, a constant or share their driver with an earlier output
+    for (root, name) in roots.iter().zip(&outputs) {
+        if &names[root] != name {
+            out.push_str(&format!(".names {} {}\n1 1\n", names[root], name));
+        }
+    }
+    out.push_str(".end\n");
+    Ok(out)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cec;
+    use crate::eqn::parse_eqn;
+    use crate::prop::{Circuit, Prop};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn parse(contents: &str) -> Result<Circuit, String> {
+        parse_blif(contents)
+    }
+
+    fn equal(a: &RecExpr<Prop>, b: &str) -> bool {
+        cec::prove_equal(a, &b.parse().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn reads_covers_and_cuts_latches() {
+        let circuit = parse(concat!(
+            "# a comment\n.model t\n.inputs a b \\\n c\n.outputs y\n.latch n q 0\n",
+            ".names a b t\n1- 1\n-1 1\n",
+            ".names t c y\n11 0\n",
+            ".names q a n\n10 1\n",
+            ".end\n.model ignored\n",
+        ))
+        .unwrap();
+        assert_eq!(circuit.inputs, ["a", "b", "c", "q"]);
+        assert_eq!(circuit.outputs, ["y", "n"]);
+        assert!(equal(&circuit.exprs[0], "(! (* (+ a b) c))"));
+        assert!(equal(&circuit.exprs[1], "(* q (! a))"));
+    }
+
+    #[test]
+    fn writes_c17_back() {
+        let c17: Circuit = parse_eqn(include_str!("../../benchmark/converted_circuit/ISCAS85/c17.eqn")).unwrap();
+        let text = write_blif("c17", &c17.inputs, &c17.outputs, &c17.exprs).unwrap();
+        assert!(text.starts_with(".model c17\n.inputs pi0 pi1 pi2 pi3 pi4\n.outputs po0 po1\n"), "{}", text);
+        let back = parse(&text).unwrap();
+        assert_eq!((&back.inputs, &back.outputs), (&c17.inputs, &c17.outputs));
+        let cex = cec::check_equivalence(&c17.exprs, &back.exprs, &c17.inputs, &mut StdRng::seed_from_u64(1)).unwrap();
+        assert!(cex.is_none());
+    }
+
+    #[test]
+    fn writes_every_operator() {
+        let exprs = ["(^ a b)", "(maj a b (mux s a b))", "(-> a (& b true))", "(xnor a (+ b false))", "a", "(^ a b)"];
+        let parsed: Vec<RecExpr<Prop>> = exprs.iter().map(|e| e.parse().unwrap()).collect();
+        let back = parse(&write_blif("t", &[], &[], &parsed).unwrap()).unwrap();
+        assert_eq!(back.outputs, ["po0", "po1", "po2", "po3", "po4", "po5"]);
+        for (expr, back) in exprs.iter().zip(&back.exprs) {
+            assert!(equal(back, expr), "{} became {}", expr, back);
+        }
+    }
+
+    #[test]
+    fn rejects_broken_netlists() {
+        let error = |contents: &str| parse(contents).err().unwrap_or_else(|| panic!("read {}", contents));
+        assert!(error(".model t\n.inputs a\n.outputs y\n.names a y\n1 1\n.names y a\n1 1\n").contains("also driven"));
+        assert!(error(".model t\n.inputs a\n.outputs y\n.names a n y\n11 1\n").contains("`n` is never defined"));
+        assert!(error(".model t\n.inputs a\n.outputs y\n.subckt and2 A=a Y=y\n").contains("not supported"));
+    }
+}
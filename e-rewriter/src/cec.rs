@@ -0,0 +1,373 @@
+use egg::*;
+
+use rand::Rng;
+use varisat::{ExtendFormula, Lit, Solver};
+
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
+
+use crate::prop::{Circuit, Prop};
+
+/// Input assignment on which an output of the two circuits differs.
+pub struct Counterexample {
+    pub output: usize,
+    pub inputs: Vec<(String, bool)>,
+}
+
+/// How many 64-pattern words of random simulation run before the SAT miter.
+pub const SIM_WORDS: usize = 64;
+
+// Free signals of the circuits, `order` first, then the rest sorted.
+fn collect_inputs(order: &[String], exprs: &[&RecExpr<Prop>]) -> Vec<String> {
+    let mut inputs: Vec<String> = order.to_vec();
+    let mut seen: HashSet<String> = inputs.iter().cloned().collect();
+    let mut extra = Vec::new();
+    for expr in exprs {
+        for node in expr.as_ref() {
+            if let Prop::Symbol(s) = node {
+                let name = s.as_str();
+                if name != "0" && name != "1" && seen.insert(name.to_string()) {
+                    extra.push(name.to_string());
+                }
+            }
+        }
+    }
+    extra.sort();
+    inputs.extend(extra);
+    inputs
+}
+
+// Evaluate 64 patterns at once, one bit per pattern.
+fn simulate(expr: &RecExpr<Prop>, words: &HashMap<&str, u64>) -> u64 {
+    let mut values: Vec<u64> = Vec::with_capacity(expr.as_ref().len());
+    for node in expr.as_ref() {
+        let x = |i: &Id| values[usize::from(*i)];
+        let v = match node {
+            Prop::Bool(c) => if *c { !0 } else { 0 },
+            Prop::Symbol(s) => match s.as_str() {
+                "0" => 0,
+                "1" => !0,
+                name => words[name],
+            },
+            Prop::And([a, b]) => x(a) & x(b),
+            Prop::Or([a, b]) => x(a) | x(b),
+            Prop::Not(a) => !x(a),
+            Prop::Implies([a, b]) => !x(a) | x(b),
+            Prop::Let([a, b]) => !(x(a) ^ x(b)),
+            Prop::Concat([a, b]) => x(a) & !x(b),
+            Prop::Xor([a, b]) => x(a) ^ x(b),
+            Prop::Xnor([a, b]) => !(x(a) ^ x(b)),
+            Prop::Mux([s, a, b]) => (x(s) & x(a)) | (!x(s) & x(b)),
+            Prop::Maj([a, b, c]) => (x(a) & x(b)) | (x(c) & (x(a) | x(b))),
+        };
+        values.push(v);
+    }
+    *values.last().unwrap()
+}
+
+/// Tseitin encoding with structural hashing of AND and XOR gates.
+struct Encoder<'a> {
+    solver: Solver<'a>,
+    one: Lit,
+    inputs: HashMap<String, Lit>,
+    ands: HashMap<(Lit, Lit), Lit>,
+    xors: HashMap<(Lit, Lit), Lit>,
+}
+
+impl Encoder<'_> {
+    fn new(inputs: &[String]) -> Self {
+        let mut solver = Solver::new();
+        let one = solver.new_lit();
+        solver.add_clause(&[one]);
+        let inputs = inputs.iter().map(|name| (name.clone(), solver.new_lit())).collect();
+        Encoder { solver, one, inputs, ands: HashMap::new(), xors: HashMap::new() }
+    }
+
+    fn and(&mut self, a: Lit, b: Lit) -> Lit {
+        let zero = !self.one;
+        if a == zero || b == zero || a == !b {
+            return zero;
+        }
+        if a == self.one || a == b {
+            return b;
+        }
+        if b == self.one {
+            return a;
+        }
+        let key = (a.min(b), a.max(b));
+        if let Some(y) = self.ands.get(&key) {
+            return *y;
+        }
+        let y = self.solver.new_lit();
+        self.solver.add_clause(&[!y, a]);
+        self.solver.add_clause(&[!y, b]);
+        self.solver.add_clause(&[y, !a, !b]);
+        self.ands.insert(key, y);
+        y
+    }
+
+    fn or(&mut self, a: Lit, b: Lit) -> Lit {
+        !self.and(!a, !b)
+    }
+
+    fn xor(&mut self, a: Lit, b: Lit) -> Lit {
+        let zero = !self.one;
+        if a == b {
+            return zero;
+        }
+        if a == !b {
+            return self.one;
+        }
+        for (c, d) in [(a, b), (b, a)] {
+            if c == zero {
+                return d;
+            }
+            if c == self.one {
+                return !d;
+            }
+        }
+        let key = (a.min(b), a.max(b));
+        if let Some(y) = self.xors.get(&key) {
+            return *y;
+        }
+        let y = self.solver.new_lit();
+        self.solver.add_clause(&[!y, a, b]);
+        self.solver.add_clause(&[!y, !a, !b]);
+        self.solver.add_clause(&[y, !a, b]);
+        self.solver.add_clause(&[y, a, !b]);
+        self.xors.insert(key, y);
+        y
+    }
+
+    fn encode(&mut self, expr: &RecExpr<Prop>) -> Lit {
+        let mut lits: Vec<Lit> = Vec::with_capacity(expr.as_ref().len());
+        for node in expr.as_ref() {
+            let x = |i: &Id| lits[usize::from(*i)];
+            let lit = match node {
+                Prop::Bool(c) => if *c { self.one } else { !self.one },
+                Prop::Symbol(s) => match s.as_str() {
+                    "0" => !self.one,
+                    "1" => self.one,
+                    name => self.inputs[name],
+                },
+                Prop::And([a, b]) => self.and(x(a), x(b)),
+                Prop::Or([a, b]) => self.or(x(a), x(b)),
+                Prop::Not(a) => !x(a),
+                Prop::Implies([a, b]) => self.or(!x(a), x(b)),
+                Prop::Let([a, b]) => !self.xor(x(a), x(b)),
+                Prop::Concat([a, b]) => self.and(x(a), !x(b)),
+                Prop::Xor([a, b]) => self.xor(x(a), x(b)),
+                Prop::Xnor([a, b]) => !self.xor(x(a), x(b)),
+                Prop::Mux([s, a, b]) => {
+                    let (s, a, b) = (x(s), x(a), x(b));
+                    let then = self.and(s, a);
+                    let other = self.and(!s, b);
+                    self.or(then, other)
+                }
+                Prop::Maj([a, b, c]) => {
+                    let (a, b, c) = (x(a), x(b), x(c));
+                    let ab = self.and(a, b);
+                    let either = self.or(a, b);
+                    let carry = self.and(c, either);
+                    self.or(ab, carry)
+                }
+            };
+            lits.push(lit);
+        }
+        *lits.last().unwrap()
+    }
+}
+
+// An input assignment on which some output pair differs, None if equivalent.
+fn sat_miter(
+    original: &[RecExpr<Prop>],
+    optimized: &[RecExpr<Prop>],
+    inputs: &[String],
+) -> Result<Option<Vec<(String, bool)>>, String> {
+    let mut encoder = Encoder::new(inputs);
+    let mut diffs = Vec::new();
+    for (a, b) in original.iter().zip(optimized) {
+        let a = encoder.encode(a);
+        let b = encoder.encode(b);
+        diffs.push(encoder.xor(a, b));
+    }
+    encoder.solver.add_clause(&diffs);
+    if !encoder.solver.solve().map_err(|e| e.to_string())? {
+        return Ok(None);
+    }
+    let model: HashSet<Lit> = encoder.solver.model().unwrap_or_default().into_iter().collect();
+    Ok(Some(inputs.iter().map(|name| (name.clone(), model.contains(&encoder.inputs[name]))).collect()))
+}
+
+/// Up to this many inputs, `prove_equal` enumerates the whole truth table.
+pub const EXHAUSTIVE_VARS: usize = 12;
+
+/// Are two single-output circuits equivalent? Small supports are decided by
+/// exhaustive simulation, larger ones by SAT.
+pub fn prove_equal(a: &RecExpr<Prop>, b: &RecExpr<Prop>) -> Result<bool, String> {
+    let inputs = collect_inputs(&[], &[a, b]);
+    if inputs.len() > EXHAUSTIVE_VARS {
+        let (a, b) = (std::slice::from_ref(a), std::slice::from_ref(b));
+        return Ok(sat_miter(a, b, &inputs)?.is_none());
+    }
+    // the low 6 inputs vary within a word, the others across words
+    const PATTERNS: [u64; 6] = [
+        0xaaaa_aaaa_aaaa_aaaa,
+        0xcccc_cccc_cccc_cccc,
+        0xf0f0_f0f0_f0f0_f0f0,
+        0xff00_ff00_ff00_ff00,
+        0xffff_0000_ffff_0000,
+        0xffff_ffff_0000_0000,
+    ];
+    let words = 1u64 << inputs.len().saturating_sub(6);
+    for w in 0..words {
+        let values: HashMap<&str, u64> = inputs
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let word = if i < 6 { PATTERNS[i] } else if w >> (i - 6) & 1 == 1 { !0 } else { 0 };
+                (name.as_str(), word)
+            })
+            .collect();
+        if simulate(a, &values) != simulate(b, &values) {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Combinational equivalence check of two multi-output circuits.
+///
+/// Bit-parallel random simulation catches most differences cheaply; if it
+/// finds none, a SAT miter (the OR of the XORs of all output pairs) decides
+/// equivalence. `input_order` fixes the order of the inputs in the
+/// counterexample.
+pub fn check_equivalence<R: Rng>(
+    original: &[RecExpr<Prop>],
+    optimized: &[RecExpr<Prop>],
+    input_order: &[String],
+    rng: &mut R,
+) -> Result<Option<Counterexample>, String> {
+    if original.len() != optimized.len() {
+        return Err(format!("{} outputs against {}", original.len(), optimized.len()));
+    }
+    let all: Vec<&RecExpr<Prop>> = original.iter().chain(optimized).collect();
+    let inputs = collect_inputs(input_order, &all);
+
+    for _ in 0..SIM_WORDS {
+        let words: HashMap<&str, u64> = inputs.iter().map(|name| (name.as_str(), rng.gen())).collect();
+        for (output, (a, b)) in original.iter().zip(optimized).enumerate() {
+            let diff = simulate(a, &words) ^ simulate(b, &words);
+            if diff != 0 {
+                let bit = diff.trailing_zeros();
+                let inputs = inputs.iter().map(|name| (name.clone(), words[name.as_str()] >> bit & 1 == 1)).collect();
+                return Ok(Some(Counterexample { output, inputs }));
+            }
+        }
+    }
+
+    let inputs = match sat_miter(original, optimized, &inputs)? {
+        Some(inputs) => inputs,
+        None => return Ok(None),
+    };
+    // report the first output that actually differs under the model
+    let words: HashMap<&str, u64> = inputs.iter().map(|(name, v)| (name.as_str(), if *v { !0 } else { 0 })).collect();
+    let output = original
+        .iter()
+        .zip(optimized)
+        .position(|(a, b)| simulate(a, &words) != simulate(b, &words))
+        .unwrap_or(0);
+    Ok(Some(Counterexample { output, inputs }))
+}
+
+/// Check `result` against the outputs of `circuit`, printing a counterexample
+/// under its output and input names if they differ.
+pub fn check_result<R: Rng>(circuit: &Circuit, result: &[RecExpr<Prop>], rng: &mut R) -> Result<(), String> {
+    let start = Instant::now();
+    match check_equivalence(&circuit.exprs, result, &circuit.inputs, rng)? {
+        None => {
+            log!(1, "CEC: equivalent, time: {:?}", start.elapsed());
+            Ok(())
+        }
+        Some(cex) => {
+            let output = circuit.outputs.get(cex.output).cloned().unwrap_or_else(|| format!("output {}", cex.output));
+            let vector: String = cex.inputs.iter().map(|(_, v)| if *v { '1' } else { '0' }).collect();
+            let assignment: Vec<String> = cex.inputs.iter().map(|(name, v)| format!("{}={}", name, *v as u8)).collect();
+            println!("CEC: NOT equivalent, `{}` differs", output);
+            println!("counterexample: {}", vector);
+            println!("  {}", assignment.join(" "));
+            Err("extracted circuit is not equivalent to the input".to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eqn::parse_eqn;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn c17() -> Circuit {
+        parse_eqn(include_str!("../../benchmark/converted_circuit/ISCAS85/c17.eqn")).unwrap()
+    }
+
+    fn exprs(texts: &[&str]) -> Vec<RecExpr<Prop>> {
+        texts.iter().map(|e| e.parse().unwrap()).collect()
+    }
+
+    fn check(original: &[RecExpr<Prop>], optimized: &[RecExpr<Prop>], inputs: &[String]) -> Option<Counterexample> {
+        check_equivalence(original, optimized, inputs, &mut StdRng::seed_from_u64(1)).unwrap()
+    }
+
+    #[test]
+    fn c17_matches_its_nand_form() {
+        let c17 = c17();
+        // the ISCAS netlist: six NAND gates
+        let n11 = "(! (* pi2 pi3))";
+        let n16 = format!("(! (* pi1 {}))", n11);
+        let nands = exprs(&[
+            &format!("(! (* (! (* pi0 pi2)) {}))", n16),
+            &format!("(! (* {} (! (* {} pi4))))", n16, n11),
+        ]);
+        assert!(check(&c17.exprs, &nands, &c17.inputs).is_none());
+    }
+
+    #[test]
+    fn finds_a_counterexample() {
+        let c17 = c17();
+        let mut wrong = c17.exprs.clone();
+        wrong[1] = "(+ (* pi1 (! (* pi2 pi3))) (* pi4 (* pi2 pi3)))".parse().unwrap();
+        let cex = check(&c17.exprs, &wrong, &c17.inputs).unwrap();
+        assert_eq!(cex.output, 1);
+        let names: Vec<&str> = cex.inputs.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["pi0", "pi1", "pi2", "pi3", "pi4"]);
+        let words: HashMap<&str, u64> = cex.inputs.iter().map(|(name, v)| (name.as_str(), if *v { !0 } else { 0 })).collect();
+        assert_ne!(simulate(&c17.exprs[1], &words), simulate(&wrong[1], &words));
+    }
+
+    #[test]
+    fn sat_finds_what_simulation_misses() {
+        // one minterm of 24 inputs, which random patterns almost never hit
+        let inputs: Vec<String> = (0..24).map(|i| format!("x{}", i)).collect();
+        let and = inputs.iter().skip(1).fold(inputs[0].clone(), |acc, x| format!("(* {} {})", acc, x));
+        let (and, zero) = (exprs(&[&and]), exprs(&["0"]));
+        let cex = sat_miter(&and, &zero, &inputs).unwrap().unwrap();
+        assert!(cex.iter().all(|(_, v)| *v));
+        assert!(check(&and, &zero, &inputs).is_some());
+        assert!(!prove_equal(&and[0], &zero[0]).unwrap());
+    }
+
+    #[test]
+    fn proves_small_and_large_supports() {
+        let small = exprs(&["(maj a b c)", "(+ (* a b) (* c (^ a b)))"]);
+        assert!(prove_equal(&small[0], &small[1]).unwrap());
+        // past EXHAUSTIVE_VARS, decided by SAT
+        let inputs: Vec<String> = (0..16).map(|i| format!("x{}", i)).collect();
+        let or = inputs.iter().skip(1).fold(inputs[0].clone(), |acc, x| format!("(+ {} {})", acc, x));
+        let nand = inputs.iter().skip(1).fold(format!("(! {})", inputs[0]), |acc, x| format!("(* {} (! {}))", acc, x));
+        let large = exprs(&[&or, &format!("(! {})", nand)]);
+        assert!(prove_equal(&large[0], &large[1]).unwrap());
+        let broken = exprs(&[&or.replacen("(+ x0 x1)", "(* x0 x1)", 1)]);
+        assert!(!prove_equal(&large[0], &broken[0]).unwrap());
+    }
+}
@@ -0,0 +1,232 @@
+//! Command-line options shared by `e-rewriter` and `esyn`.
+
+use clap::{Args, ValueEnum};
+
+use crate::extract::Policy;
+use crate::rules;
+
+#[derive(Args, Debug, Clone)]
+pub struct RuleArgs {
+    /// Rule file, overrides --ruleset
+    #[arg(long, value_name = "FILE")]
+    pub rules: Option<String>,
+
+    /// Built-in rule set (enhance, basic)
+    #[arg(long, value_name = "NAME", default_value = rules::DEFAULT_RULESET)]
+    pub ruleset: String,
+
+    /// Load rules tagged with any of these tags
+    #[arg(long, value_name = "TAGS", value_delimiter = ',')]
+    pub enable: Vec<String>,
+}
+
+impl RuleArgs {
+    /// The rule set, from `--rules <file>` or the built-in `--ruleset <name>`.
+    pub fn load(&self) -> Result<Vec<rules::RuleSpec>, String> {
+        let rule_text = match &self.rules {
+            Some(path) => std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?,
+            None => rules::builtin(&self.ruleset)?.to_string(),
+        };
+        rules::parse_rules(&rule_text)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Extraction {
+    /// sample candidates with the randomised tree extractor
+    Tree,
+    /// count shared sub-circuits once
+    Dag,
+    /// exact DAG extraction by branch and bound
+    Ilp,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum CostKind {
+    AstDepth,
+    AstSize,
+    /// average of size and randomly tie-broken depth
+    Mixcost,
+    /// total cell area from `--liberty`
+    Area,
+    /// critical-path cell delay from `--liberty`
+    Delay,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Scheduler {
+    /// egg's exponential backoff
+    Backoff,
+    /// throttle rules that only permute operands, boost rules that shrank the result
+    Yield,
+    /// pull the rules that paid off as arms of a multi-armed bandit
+    Bandit,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum BanditArms {
+    /// one arm per group of rules, such as `de-morgan` or `distributivity`
+    Group,
+    /// one arm per rule
+    Rule,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum PartitionKind {
+    /// runs of consecutive gates of the output cones, with several outputs
+    Cone,
+    /// maximum fanout-free cones, each with its root as the only output
+    Mffc,
+}
+
+fn parse_policy(s: &str) -> Result<Policy, String> {
+    match s {
+        "ties" => Ok(Policy::Ties),
+        t => t
+            .parse()
+            .map(Policy::Temperature)
+            .map_err(|_| format!("expected `ties` or a temperature, found `{}`", s)),
+    }
+}
+
+/// How a circuit is rewritten and extracted.
+#[derive(Args, Debug, Clone)]
+pub struct OptimizeArgs {
+    #[command(flatten)]
+    pub rules: RuleArgs,
+
+    /// Do not merge simulation-equivalent e-classes during the run
+    #[arg(long)]
+    pub no_sweep: bool,
+
+    /// Runner time limit in seconds
+    #[arg(long, value_name = "SECS", default_value_t = 100)]
+    pub time_limit: u64,
+
+    /// Runner e-node limit
+    #[arg(long, value_name = "N", default_value_t = 25_000_000)]
+    pub node_limit: usize,
+
+    /// Runner iteration limit
+    #[arg(long, value_name = "N", default_value_t = 10_000_000)]
+    pub iter_limit: usize,
+
+    /// How rules are scheduled over the iterations
+    #[arg(long, value_enum, default_value_t = Scheduler::Backoff)]
+    pub scheduler: Scheduler,
+
+    /// What the bandit scheduler treats as an arm
+    #[arg(long, value_enum, default_value_t = BanditArms::Group)]
+    pub bandit_arms: BanditArms,
+
+    /// Policy file of the bandit scheduler, read if it exists and written after the run
+    #[arg(long, value_name = "FILE")]
+    pub bandit_policy: Option<String>,
+
+    /// Rewrite, extract the smallest DAG and start again from it, until N rounds bring no improvement
+    #[arg(long, value_name = "N", default_value_t = 0, conflicts_with_all = ["explain", "proof"])]
+    pub reseed: usize,
+
+    /// Most rounds of --reseed, each within the runner limits
+    #[arg(long, value_name = "N", default_value_t = 20)]
+    pub max_rounds: usize,
+
+    /// Split the circuit into windows rewritten in parallel, each within the runner limits
+    #[arg(long, value_enum, conflicts_with_all = ["explain", "proof"])]
+    pub partition: Option<PartitionKind>,
+
+    /// Most inputs of a --partition window
+    #[arg(long, value_name = "N", default_value_t = 32)]
+    pub window_inputs: usize,
+
+    /// Most gates of a --partition window
+    #[arg(long, value_name = "N", default_value_t = 1000)]
+    pub window_nodes: usize,
+
+    /// Rule sets, built-in names or files, to run a portfolio of concurrently, one thread per rule set and cost
+    #[arg(long, value_name = "RULES", value_delimiter = ',', conflicts_with_all = ["explain", "proof"])]
+    pub portfolio_rules: Vec<String>,
+
+    /// Costs to run a portfolio of, with each of --portfolio-rules or else the rule set alone
+    #[arg(long, value_enum, value_name = "COSTS", value_delimiter = ',', conflicts_with_all = ["explain", "proof"])]
+    pub portfolio_costs: Vec<CostKind>,
+
+    #[arg(long, value_enum, default_value_t = Extraction::Tree)]
+    pub extraction: Extraction,
+
+    /// Cost function for tree and ILP extraction
+    #[arg(long, value_enum, default_value_t = CostKind::AstDepth)]
+    pub cost: CostKind,
+
+    /// Liberty library for the area and delay costs
+    #[arg(long, value_name = "FILE", required_if_eq_any = [("cost", "area"), ("cost", "delay")])]
+    pub liberty: Option<String>,
+
+    /// Candidate sampling for tree extraction: `ties` or a temperature
+    #[arg(long, value_parser = parse_policy, default_value = "0.05")]
+    pub policy: Policy,
+
+    /// Number of candidates sampled by tree extraction
+    #[arg(long, value_name = "N", default_value_t = 501)]
+    pub candidates: usize,
+
+    /// Number of best results kept, each checked against the input
+    #[arg(long, value_name = "K", default_value_t = 10)]
+    pub top_k: usize,
+
+    /// Time limit of the ILP search in seconds
+    #[arg(long, value_name = "SECS", default_value_t = 60)]
+    pub ilp_time_limit: u64,
+
+    /// Seed for every random decision, printed at the start of the run
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// Write the rule applications behind the best result, as JSON for a `.json` file
+    #[arg(long, value_name = "FILE")]
+    pub explain: Option<String>,
+
+    /// Write a proof of the best result that `esyn check-proof` can replay
+    #[arg(long, value_name = "FILE")]
+    pub proof: Option<String>,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct VerbosityArgs {
+    /// Print more (per-iteration runner and sweep reports)
+    #[arg(short, long, global = true, action = clap::ArgAction::Count, conflicts_with = "quiet")]
+    pub verbose: u8,
+
+    /// Only print errors
+    #[arg(short, long, global = true)]
+    pub quiet: bool,
+}
+
+impl VerbosityArgs {
+    /// 0 with `--quiet`, 1 by default, more with each `-v`.
+    pub fn level(&self) -> u8 {
+        if self.quiet {
+            0
+        } else {
+            1 + self.verbose
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct Cli {
+        #[command(flatten)]
+        optimize: OptimizeArgs,
+    }
+
+    #[test]
+    fn schedules_by_backoff_unless_asked() {
+        assert_eq!(Cli::parse_from(["esyn"]).optimize.scheduler, Scheduler::Backoff);
+        assert_eq!(Cli::parse_from(["esyn", "--scheduler", "yield"]).optimize.scheduler, Scheduler::Yield);
+    }
+}
@@ -0,0 +1,438 @@
+//! ABC's EQN format: `INORDER`, `OUTORDER` and one `name = expr;` per
+//! signal, over `!`, `*` and `+`.
+//!
+//! Writing lowers the other operators to those three and puts logic that
+//! is read more than once on its own `new_n*_` signal, the way ABC's
+//! `write_eqn` does, so the file reads back into ABC without any
+//! post-processing.
+
+use egg::*;
+
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use crate::netlist::{all_inputs, build_signals, default_names, is_constant, Netlist, NodeTable};
+
+#[derive(Debug)]
+enum Ast {
+    Var(String),
+    Not(Box<Ast>),
+    And(Box<Ast>, Box<Ast>),
+    Or(Box<Ast>, Box<Ast>),
+}
+
+impl Ast {
+    fn vars<'a>(&'a self, out: &mut Vec<&'a str>) {
+        match self {
+            Ast::Var(v) => out.push(v),
+            Ast::Not(a) => a.vars(out),
+            Ast::And(a, b) | Ast::Or(a, b) => {
+                a.vars(out);
+                b.vars(out);
+            }
+        }
+    }
+}
+
+pub fn is_eqn(contents: &str) -> bool {
+    contents.lines().any(|line| line.trim_start().starts_with("INORDER"))
+}
+
+fn tokenize(s: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    for c in s.chars() {
+        match c {
+            '(' | ')' | '!' | '*' | '+' => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+                tokens.push(c.to_string());
+            }
+            c if c.is_whitespace() => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+// precedence: `!` binds tighter than `*`, which binds tighter than `+`
+struct ExprParser {
+    tokens: Vec<String>,
+    pos: usize,
+}
+
+impl ExprParser {
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.pos).map(|t| t.as_str())
+    }
+
+    fn next(&mut self) -> Result<String, String> {
+        let token = self.tokens.get(self.pos).cloned().ok_or("unexpected end of expression")?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn parse_or(&mut self) -> Result<Ast, String> {
+        let mut lhs = self.parse_and()?;
+        while self.peek() == Some("+") {
+            self.pos += 1;
+            let rhs = self.parse_and()?;
+            lhs = Ast::Or(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_and(&mut self) -> Result<Ast, String> {
+        let mut lhs = self.parse_not()?;
+        while self.peek() == Some("*") {
+            self.pos += 1;
+            let rhs = self.parse_not()?;
+            lhs = Ast::And(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_not(&mut self) -> Result<Ast, String> {
+        match self.next()?.as_str() {
+            "!" => Ok(Ast::Not(Box::new(self.parse_not()?))),
+            "(" => {
+                let inner = self.parse_or()?;
+                match self.next()?.as_str() {
+                    ")" => Ok(inner),
+                    t => Err(format!("expected `)`, found `{}`", t)),
+                }
+            }
+            t @ (")" | "*" | "+") => Err(format!("unexpected `{}`", t)),
+            name => Ok(Ast::Var(name.to_string())),
+        }
+    }
+}
+
+fn parse_expr(s: &str) -> Result<Ast, String> {
+    let mut parser = ExprParser { tokens: tokenize(s), pos: 0 };
+    let ast = parser.parse_or()?;
+    if let Some(t) = parser.peek() {
+        return Err(format!("trailing token `{}`", t));
+    }
+    Ok(ast)
+}
+
+/// A signal's expression and the signals it reads.
+struct Definition {
+    ast: Ast,
+    fanins: Vec<String>,
+}
+
+fn add_ast<L: FromOp>(table: &mut NodeTable<L>, ast: &Ast, signals: &HashMap<String, Id>) -> Result<Id, String> {
+    match ast {
+        Ast::Var(v) => Ok(signals[v]),
+        Ast::Not(a) => {
+            let a = add_ast(table, a, signals)?;
+            table.add("!", vec![a])
+        }
+        Ast::And(a, b) => {
+            let ids = vec![add_ast(table, a, signals)?, add_ast(table, b, signals)?];
+            table.add("*", ids)
+        }
+        Ast::Or(a, b) => {
+            let ids = vec![add_ast(table, a, signals)?, add_ast(table, b, signals)?];
+            table.add("+", ids)
+        }
+    }
+}
+
+fn split_assignment(statement: &str) -> Result<(&str, &str), String> {
+    let (lhs, rhs) = statement
+        .split_once('=')
+        .ok_or_else(|| format!("expected `name = expr;`, found `{}`", statement))?;
+    Ok((lhs.trim(), rhs.trim()))
+}
+
+/// Read an EQN file.
+pub fn parse_eqn<L: FromOp>(contents: &str) -> Result<Netlist<L>, String> {
+    let mut inputs = Vec::new();
+    let mut outputs = Vec::new();
+    let mut definitions: HashMap<String, Definition> = HashMap::new();
+
+    let body: String = contents
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .collect::<Vec<_>>()
+        .join(" ");
+    for statement in body.split(';').map(str::trim).filter(|s| !s.is_empty()) {
+        let (lhs, rhs) = split_assignment(statement)?;
+        match lhs {
+            "INORDER" => inputs.extend(rhs.split_whitespace().map(String::from)),
+            "OUTORDER" => outputs.extend(rhs.split_whitespace().map(String::from)),
+            name => {
+                let ast = parse_expr(rhs).map_err(|e| format!("in `{}`: {}", name, e))?;
+                let mut fanins = Vec::new();
+                ast.vars(&mut fanins);
+                let fanins = fanins.into_iter().map(String::from).collect();
+                if definitions.insert(name.to_string(), Definition { ast, fanins }).is_some() {
+                    return Err(format!("signal `{}` is defined twice", name));
+                }
+            }
+        }
+    }
+
+    let mut table = NodeTable::new();
+    let mut signals: HashMap<String, Id> = HashMap::new();
+    for name in &inputs {
+        let id = table.add(name, vec![])?;
+        signals.insert(name.clone(), id);
+    }
+    for constant in ["0", "1"] {
+        if !definitions.contains_key(constant) {
+            let id = table.add(constant, vec![])?;
+            signals.insert(constant.to_string(), id);
+        }
+    }
+
+    // ABC writes the equations in topological order, but we do not rely on it
+    build_signals(&outputs, &definitions, &mut signals, |def| &def.fanins, |def, signals| {
+        add_ast(&mut table, &def.ast, signals)
+    })?;
+
+    let exprs = outputs.iter().map(|o| table.cone(signals[o])).collect();
+    Ok(Netlist { inputs, outputs, exprs })
+}
+
+// How tightly an EQN expression binds, to know when it needs parentheses.
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
+enum Level {
+    Atom,
+    // a complemented signal name
+    Literal,
+    Not,
+    Product,
+    Sum,
+}
+
+// An operator as a sum of products of its (possibly complemented) operands:
+// every cube lists (operand, complemented) pairs.
+fn operator_sop(op: &str, arity: usize) -> Option<&'static [&'static [(usize, bool)]]> {
+    let sop: &[&[(usize, bool)]] = match (op, arity) {
+        ("*", 2) => &[&[(0, false), (1, false)]],
+        ("+", 2) => &[&[(0, false)], &[(1, false)]],
+        ("->", 2) => &[&[(0, true)], &[(1, false)]],
+        ("&", 2) => &[&[(0, false), (1, true)]],
+        ("^", 2) => &[&[(0, false), (1, true)], &[(0, true), (1, false)]],
+        ("let" | "xnor", 2) => &[&[(0, false), (1, false)], &[(0, true), (1, true)]],
+        ("mux", 3) => &[&[(0, false), (1, false)], &[(0, true), (2, false)]],
+        ("maj", 3) => &[&[(0, false), (1, false)], &[(0, false), (2, false)], &[(1, false), (2, false)]],
+        _ => return None,
+    };
+    Some(sop)
+}
+
+// ABC does not read `!!`, so complemented text loses its `!` instead.
+fn complement(text: &str, level: Level) -> (String, Level) {
+    match (level, text.strip_prefix('!')) {
+        (Level::Literal | Level::Not, Some(inner)) => (inner.to_string(), Level::Atom),
+        (Level::Atom, _) => (format!("!{}", text), Level::Literal),
+        _ => (format!("!{}", wrap(text, level, Level::Not)), Level::Not),
+    }
+}
+
+fn wrap(text: &str, level: Level, max: Level) -> String {
+    if level > max {
+        format!("({})", text)
+    } else {
+        text.to_string()
+    }
+}
+
+/// Write a multi-output circuit as an EQN file.
+///
+/// `inputs` and `outputs` become INORDER and OUTORDER; symbols of the
+/// expressions that are not in `inputs` follow sorted by name, and without
+/// `outputs` names the outputs are called `po0`, `po1`, ...
+pub fn write_eqn<L: Language + Display>(model: &str, inputs: &[String], outputs: &[String], exprs: &[RecExpr<L>]) -> Result<String, String> {
+    let inputs = all_inputs(inputs, exprs);
+    let outputs = if outputs.is_empty() { default_names("po", exprs.len()) } else { outputs.to_vec() };
+
+    let mut table = NodeTable::new();
+    let roots: Vec<Id> = exprs.iter().map(|expr| table.add_expr(expr)).collect();
+
+    // how often the text of each node is read once lowered to `!`, `*` and `+`
+    let mut reads = vec![0usize; table.nodes.len()];
+    for node in &table.nodes {
+        let op = node.to_string();
+        match operator_sop(&op, node.len()) {
+            Some(sop) => {
+                for (operand, _) in sop.iter().flat_map(|cube| cube.iter()) {
+                    reads[usize::from(node.children()[*operand])] += 1;
+                }
+            }
+            None => {
+                for c in node.children() {
+                    reads[usize::from(*c)] += 1;
+                }
+            }
+        }
+    }
+    let logic_reads = reads.clone();
+    for root in &roots {
+        reads[usize::from(*root)] += 1;
+    }
+
+    let mut out = format!("# Equations for \"{}\" written by e-rewriter\n", model);
+    out.push_str(&format!("INORDER = {};\n", inputs.join(" ")));
+    out.push_str(&format!("OUTORDER = {};\n", outputs.join(" ")));
+
+    let mut text: Vec<(String, Level)> = Vec::with_capacity(table.nodes.len());
+    for (id, node) in table.nodes.iter().enumerate() {
+        let op = node.to_string();
+        let (expr, level) = if node.is_leaf() {
+            match op.as_str() {
+                "false" => ("0".to_string(), Level::Atom),
+                "true" => ("1".to_string(), Level::Atom),
+                name => (name.to_string(), Level::Atom),
+            }
+        } else {
+            // operators written inline are read exactly once, so their text can be moved
+            let x: Vec<(String, Level)> = node
+                .children()
+                .iter()
+                .map(|c| {
+                    let c = usize::from(*c);
+                    if reads[c] == 1 && !table.nodes[c].is_leaf() {
+                        std::mem::replace(&mut text[c], (String::new(), Level::Atom))
+                    } else {
+                        text[c].clone()
+                    }
+                })
+                .collect();
+            match (op.as_str(), x.as_slice()) {
+                ("!", [(a, level)]) => complement(a, *level),
+                (op, x) => {
+                    let sop = operator_sop(op, x.len()).ok_or_else(|| format!("cannot write `{}` to EQN", op))?;
+                    let cubes: Vec<String> = sop
+                        .iter()
+                        .map(|cube| {
+                            let literals: Vec<String> = cube
+                                .iter()
+                                .map(|&(i, complemented)| {
+                                    let (a, level) = &x[i];
+                                    if complemented {
+                                        let (a, level) = complement(a, *level);
+                                        wrap(&a, level, Level::Product)
+                                    } else {
+                                        wrap(a, *level, Level::Product)
+                                    }
+                                })
+                                .collect();
+                            literals.join(" * ")
+                        })
+                        .collect();
+                    let level = if cubes.len() > 1 { Level::Sum } else { Level::Product };
+                    let cubes: Vec<String> = cubes.iter().map(|c| wrap(c, Level::Product, Level::Sum)).collect();
+                    (cubes.join(" + "), level)
+                }
+            }
+        };
+        // like ABC, complemented signals are written inline rather than named;
+        // ABC reads `0` and `1` only as a whole formula, so constants read by logic get a signal too
+        let constant_read = node.is_leaf() && is_constant(&op) && logic_reads[id] > 0;
+        if constant_read || (reads[id] > 1 && level > Level::Literal) {
+            let name = format!("new_n{}_", id);
+            out.push_str(&format!("{} = {};\n", name, expr));
+            text.push((name, Level::Atom));
+        } else {
+            text.push((expr, level));
+        }
+    }
+    for (root, name) in roots.iter().zip(&outputs) {
+        out.push_str(&format!("{} = {};\n", name, text[usize::from(*root)].0));
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cec;
+    use crate::prop::Prop;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    const C17: &str = include_str!("../../benchmark/converted_circuit/ISCAS85/c17.eqn");
+
+    fn parse(contents: &str) -> Result<Netlist<Prop>, String> {
+        parse_eqn(contents)
+    }
+
+    #[test]
+    fn reads_c17() {
+        let c17 = parse(C17).unwrap();
+        assert_eq!(c17.inputs, ["pi0", "pi1", "pi2", "pi3", "pi4"]);
+        assert_eq!(c17.outputs, ["po0", "po1"]);
+        assert_eq!(c17.exprs[0].to_string(), "(+ (* pi0 pi2) (* pi1 (! (* pi2 pi3))))");
+        assert_eq!(c17.exprs[1].to_string(), "(+ (* pi1 (! (* pi2 pi3))) (* pi4 (! (* pi2 pi3))))");
+    }
+
+    #[test]
+    fn reads_precedence_and_any_order() {
+        let netlist = parse("INORDER = a b c;\nOUTORDER = y;\ny = n + !a * (b + c);\nn = !!c;\n").unwrap();
+        assert_eq!(netlist.exprs[0].to_string(), "(+ (! (! c)) (* (! a) (+ b c)))");
+    }
+
+    #[test]
+    fn rejects_broken_netlists() {
+        let error = |contents: &str| parse(contents).err().unwrap_or_else(|| panic!("read {}", contents));
+        assert!(error("INORDER = a;\nOUTORDER = y;\ny = a * n;\n").contains("`n` is never defined"));
+        assert!(error("INORDER = a;\nOUTORDER = y;\ny = a;\ny = !a;\n").contains("defined twice"));
+        assert!(error("INORDER = a;\nOUTORDER = y;\ny = a * n;\nn = !y;\n").contains("combinational loop"));
+        assert!(error("INORDER = a;\nOUTORDER = y;\ny = (a * a;\n").contains("in `y`"));
+    }
+
+    fn write(exprs: &[&str]) -> String {
+        let exprs: Vec<RecExpr<Prop>> = exprs.iter().map(|e| e.parse().unwrap()).collect();
+        write_eqn("t", &[], &[], &exprs).unwrap()
+    }
+
+    #[test]
+    fn writes_c17_back() {
+        let c17 = parse(C17).unwrap();
+        let text = write_eqn("c17", &c17.inputs, &c17.outputs, &c17.exprs).unwrap();
+        // `pi1 * !(pi2 * pi3)` feeds both outputs
+        assert!(text.contains("new_n"), "{}", text);
+        let back = parse(&text).unwrap();
+        assert_eq!((&back.inputs, &back.outputs), (&c17.inputs, &c17.outputs));
+        let cex = cec::check_equivalence(&c17.exprs, &back.exprs, &c17.inputs, &mut StdRng::seed_from_u64(1)).unwrap();
+        assert!(cex.is_none());
+    }
+
+    #[test]
+    fn names_unnamed_outputs_and_constants() {
+        let text = write(&["(* b false)", "true"]);
+        assert!(text.contains("INORDER = b;") && text.contains("OUTORDER = po0 po1;"), "{}", text);
+        // ABC only reads a constant as a whole formula
+        assert!(text.contains("new_n1_ = 0;") && text.contains("po0 = b * new_n1_;") && text.contains("po1 = 1;"), "{}", text);
+    }
+
+    #[test]
+    fn writes_no_double_negation() {
+        let text = write(&["(! (! a))", "(^ (! b) (! (! a)))", "(mux (! s) (! a) b)"]);
+        assert!(!text.contains("!!") && !text.contains("!(!"), "{}", text);
+    }
+
+    #[test]
+    fn lowers_the_operators_it_lacks() {
+        let exprs = ["(^ a b)", "(xnor a b)", "(mux s a b)", "(maj a b c)", "(! (maj (! a) b (^ a c)))"];
+        let text = write(&exprs);
+        assert!(!text.contains('^') && !text.contains("mux") && !text.contains("maj"), "{}", text);
+        let read: Netlist<Prop> = parse_eqn(&text).unwrap();
+        for (expr, back) in exprs.iter().zip(&read.exprs) {
+            assert!(cec::prove_equal(&expr.parse().unwrap(), back).unwrap(), "{} became {}", expr, back);
+        }
+    }
+}
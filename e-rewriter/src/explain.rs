@@ -0,0 +1,214 @@
+//! Rewrite explanations: how each extracted output follows from the input.
+//!
+//! egg explains an equivalence as a tree of proofs, one per pair of terms it
+//! rewrote into each other. This flattens the tree into the sequence of rule
+//! applications, each naming its `rewrite!` rule, or `fraig` and `analysis`
+//! for the merges made by the sweep and by constant folding. A rewrite of
+//! logic that several gates of an output share is listed once, where it is
+//! first needed.
+
+use egg::*;
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::{Display, Write};
+use std::path::Path;
+use std::rc::Rc;
+
+// subterms below this depth are printed as `...`
+const TERM_DEPTH: usize = 4;
+
+/// One rule application: `before` rewritten into `after`, with `rule` read
+/// left to right if `forward`, and right to left otherwise.
+pub struct Step {
+    pub rule: String,
+    pub forward: bool,
+    pub before: String,
+    pub after: String,
+}
+
+/// The rule applications that turn the input cone of an output into its
+/// extracted one.
+pub struct OutputExplanation {
+    pub name: String,
+    pub steps: Vec<Step>,
+}
+
+pub fn flatten<L: Language + Display>(name: &str, explanation: &Explanation<L>) -> OutputExplanation {
+    let mut steps = Vec::new();
+    let mut seen = HashSet::new();
+    // (proof, index of the term to visit), without recursion since circuits get deep
+    let mut stack: Vec<(&[Rc<TreeTerm<L>>], usize)> = vec![(&explanation.explanation_trees, 0)];
+    while let Some((proof, k)) = stack.pop() {
+        if k >= proof.len() {
+            continue;
+        }
+        stack.push((proof, k + 1));
+        let term = &proof[k];
+        if !seen.insert(Rc::as_ptr(term)) {
+            continue;
+        }
+        let rule = term.forward_rule.map(|r| (r, true)).or(term.backward_rule.map(|r| (r, false)));
+        if let (Some((rule, forward)), Some(previous)) = (rule, k.checked_sub(1).map(|i| &proof[i])) {
+            steps.push(Step {
+                rule: rule.to_string(),
+                forward,
+                before: render(previous, false, TERM_DEPTH),
+                after: render(term, true, TERM_DEPTH),
+            });
+        }
+        // the stack is last in, first out
+        for child in term.child_proofs.iter().rev() {
+            stack.push((child, 0));
+        }
+    }
+    OutputExplanation { name: name.to_string(), steps }
+}
+
+// the first (`initial`) or last term of a proof step, cut at `depth`
+fn render<L: Language + Display>(term: &TreeTerm<L>, initial: bool, depth: usize) -> String {
+    if term.child_proofs.is_empty() {
+        return term.node.to_string();
+    }
+    if depth == 0 {
+        return "...".to_string();
+    }
+    let mut text = format!("({}", term.node);
+    for child in &term.child_proofs {
+        let child = if initial { child.first() } else { child.last() };
+        text.push(' ');
+        text.push_str(&render(child.unwrap(), initial, depth - 1));
+    }
+    text.push(')');
+    text
+}
+
+/// Rule applications per rule, over all outputs.
+pub fn rule_counts(explanations: &[OutputExplanation]) -> BTreeMap<&str, usize> {
+    let mut counts = BTreeMap::new();
+    for step in explanations.iter().flat_map(|e| &e.steps) {
+        *counts.entry(step.rule.as_str()).or_insert(0) += 1;
+    }
+    counts
+}
+
+pub fn write_text(explanations: &[OutputExplanation]) -> String {
+    let mut text = String::new();
+    for explanation in explanations {
+        writeln!(text, "output {}: {} rewrites", explanation.name, explanation.steps.len()).unwrap();
+        for (i, step) in explanation.steps.iter().enumerate() {
+            let arrow = if step.forward { "=>" } else { "<=" };
+            writeln!(text, "  {:>4}  {} {}  {}  ->  {}", i + 1, step.rule, arrow, step.before, step.after).unwrap();
+        }
+    }
+    let mut counts: Vec<(&str, usize)> = rule_counts(explanations).into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    writeln!(text, "rules:").unwrap();
+    for (rule, count) in counts {
+        writeln!(text, "  {:>6}  {}", count, rule).unwrap();
+    }
+    text
+}
+
+pub fn write_json(explanations: &[OutputExplanation]) -> String {
+    let mut text = String::from("{\n  \"outputs\": [");
+    for (i, explanation) in explanations.iter().enumerate() {
+        let separator = if i == 0 { "" } else { "," };
+        write!(text, "{}\n    {{\"name\": {}, \"steps\": [", separator, quote(&explanation.name)).unwrap();
+        for (j, step) in explanation.steps.iter().enumerate() {
+            let separator = if j == 0 { "" } else { "," };
+            write!(
+                text,
+                "{}\n      {{\"rule\": {}, \"direction\": \"{}\", \"before\": {}, \"after\": {}}}",
+                separator,
+                quote(&step.rule),
+                if step.forward { "forward" } else { "backward" },
+                quote(&step.before),
+                quote(&step.after)
+            )
+            .unwrap();
+        }
+        text.push_str(if explanation.steps.is_empty() { "]}" } else { "\n    ]}" });
+    }
+    text.push_str("\n  ],\n  \"rules\": {");
+    for (i, (rule, count)) in rule_counts(explanations).into_iter().enumerate() {
+        let separator = if i == 0 { "" } else { "," };
+        write!(text, "{}\n    {}: {}", separator, quote(rule), count).unwrap();
+    }
+    text.push_str("\n  }\n}\n");
+    text
+}
+
+fn quote(s: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            c if (c as u32) < 0x20 => write!(quoted, "\\u{:04x}", c as u32).unwrap(),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// JSON for a `.json` path, text otherwise.
+pub fn write_explanations(path: &Path, explanations: &[OutputExplanation]) -> String {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("json") => write_json(explanations),
+        _ => write_text(explanations),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prop::{ConstantFold, Prop};
+
+    fn explain(input: &str, result: &str) -> OutputExplanation {
+        let rules: Vec<Rewrite<Prop, ConstantFold>> = vec![rewrite!("and-comm"; "(* ?a ?b)" => "(* ?b ?a)"), rewrite!("or-comm"; "(+ ?a ?b)" => "(+ ?b ?a)")];
+        let (input, result): (RecExpr<Prop>, RecExpr<Prop>) = (input.parse().unwrap(), result.parse().unwrap());
+        let mut runner = Runner::<Prop, ConstantFold>::new(ConstantFold::default()).with_explanations_enabled().with_expr(&input).run(&rules);
+        flatten("y", &runner.explain_equivalence(&input, &result))
+    }
+
+    #[test]
+    fn flattens_into_rule_applications() {
+        let explanation = explain("(+ (* a b) b)", "(+ b (* b a))");
+        assert_eq!(
+            write_text(&[explanation]),
+            concat!(
+                "output y: 2 rewrites\n",
+                "     1  or-comm =>  (+ (* a b) b)  ->  (+ b (* a b))\n",
+                "     2  and-comm =>  (* a b)  ->  (* b a)\n",
+                "rules:\n       1  and-comm\n       1  or-comm\n",
+            )
+        );
+    }
+
+    #[test]
+    fn lists_shared_rewrites_once() {
+        let explanation = explain("(+ (* a b) (! (* a b)))", "(+ (* b a) (! (* b a)))");
+        let rules: Vec<&str> = explanation.steps.iter().map(|s| s.rule.as_str()).collect();
+        assert_eq!(rules, ["and-comm"]);
+    }
+
+    #[test]
+    fn writes_json() {
+        let mut explanation = explain("(+ (* a b) b)", "(+ b (* b a))");
+        explanation.name = "y\"1\\".to_string();
+        let empty = OutputExplanation { name: "z".to_string(), steps: vec![] };
+        assert_eq!(
+            write_json(&[explanation, empty]),
+            concat!(
+                "{\n  \"outputs\": [\n",
+                "    {\"name\": \"y\\\"1\\\\\", \"steps\": [\n",
+                "      {\"rule\": \"or-comm\", \"direction\": \"forward\", \"before\": \"(+ (* a b) b)\", \"after\": \"(+ b (* a b))\"},\n",
+                "      {\"rule\": \"and-comm\", \"direction\": \"forward\", \"before\": \"(* a b)\", \"after\": \"(* b a)\"}\n",
+                "    ]},\n",
+                "    {\"name\": \"z\", \"steps\": []}\n",
+                "  ],\n  \"rules\": {\n    \"and-comm\": 1,\n    \"or-comm\": 1\n  }\n}\n",
+            )
+        );
+    }
+}
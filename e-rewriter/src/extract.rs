@@ -0,0 +1,458 @@
+use egg::*;
+
+use rand::Rng;
+
+use std::collections::{HashMap, HashSet};
+
+/// Size and depth of a node once the writers expand it into AND/OR/NOT
+/// gates, which the size and depth costs charge instead of one per node.
+pub trait GateCost {
+    fn gates(&self) -> usize;
+    fn levels(&self) -> usize;
+}
+
+/// Extraction that counts a shared sub-circuit once.
+///
+/// The cost of a selection is the number of gates of the distinct e-classes
+/// reachable from all roots, which is what ABC reports as the gate count
+/// after `strash`. A greedy tree-size pass gives the initial selection, then every
+/// reachable e-class tries its other e-nodes and keeps a switch only if the
+/// shared DAG gets strictly smaller.
+pub struct DagExtractor<'a, L: Language, N: Analysis<L>> {
+    egraph: &'a EGraph<L, N>,
+    choices: HashMap<Id, L>,
+    refs: HashMap<Id, usize>,
+    // over the selection: a height above that of every child, and the classes
+    // selecting a node that reads each class, once per read
+    height: HashMap<Id, usize>,
+    parents: HashMap<Id, Vec<Id>>,
+}
+
+impl<'a, L, N> DagExtractor<'a, L, N>
+where
+    L: Language + GateCost,
+    N: Analysis<L>,
+{
+    pub fn new(egraph: &'a EGraph<L, N>, roots: &[Id], max_passes: usize) -> Self {
+        let mut extractor = DagExtractor {
+            egraph,
+            choices: HashMap::new(),
+            refs: HashMap::new(),
+            height: HashMap::new(),
+            parents: HashMap::new(),
+        };
+        extractor.greedy();
+        for root in roots {
+            let root = egraph.find(*root);
+            extractor.inc(root);
+        }
+        for _ in 0..max_passes {
+            if !extractor.refine() {
+                break;
+            }
+        }
+        extractor
+    }
+
+    // Plain bottom-up AstSize extraction. Strictly increasing costs keep the
+    // selection acyclic.
+    fn greedy(&mut self) {
+        let mut costs: HashMap<Id, usize> = HashMap::new();
+        let mut did_something = true;
+        while did_something {
+            did_something = false;
+            for class in self.egraph.classes() {
+                for node in class.iter() {
+                    if !node.all(|child| costs.contains_key(&self.egraph.find(child))) {
+                        continue;
+                    }
+                    let cost = node.fold(node.gates(), |sum, child| sum.saturating_add(costs[&self.egraph.find(child)]));
+                    if costs.get(&class.id).is_none_or(|old| cost < *old) {
+                        costs.insert(class.id, cost);
+                        self.choices.insert(class.id, node.clone());
+                        did_something = true;
+                    }
+                }
+            }
+        }
+    }
+
+    // Gates of the node selected for `class`, inputs and constants are free.
+    fn weight(&self, class: Id) -> usize {
+        let node = &self.choices[&class];
+        if node.is_leaf() { 0 } else { node.gates() }
+    }
+
+    fn children(&self, class: Id) -> Vec<Id> {
+        self.choices[&class].children().iter().map(|c| self.egraph.find(*c)).collect()
+    }
+
+    // Add a reference to `class`, returns the gates that became reachable.
+    fn inc(&mut self, class: Id) -> usize {
+        let mut added = 0;
+        let mut stack = vec![class];
+        while let Some(id) = stack.pop() {
+            let count = self.refs.entry(id).or_insert(0);
+            *count += 1;
+            if *count == 1 {
+                added += self.weight(id);
+                stack.extend(self.children(id));
+            }
+        }
+        added
+    }
+
+    // Drop a reference to `class`, returns the gates that became unreachable.
+    fn dec(&mut self, class: Id) -> usize {
+        let mut freed = 0;
+        let mut stack = vec![class];
+        while let Some(id) = stack.pop() {
+            let count = self.refs.get_mut(&id).unwrap();
+            *count -= 1;
+            if *count == 0 {
+                self.refs.remove(&id);
+                freed += self.weight(id);
+                stack.extend(self.children(id));
+            }
+        }
+        freed
+    }
+
+    // Heights and parents of the whole selection, children before parents.
+    fn index(&mut self) {
+        self.height.clear();
+        self.parents.clear();
+        let mut stack: Vec<(Id, bool)> = self.choices.keys().map(|id| (*id, false)).collect();
+        while let Some((id, expanded)) = stack.pop() {
+            if self.height.contains_key(&id) {
+                continue;
+            }
+            let children = self.children(id);
+            if expanded {
+                let height = children.iter().map(|c| self.height[c] + 1).max().unwrap_or(0);
+                self.height.insert(id, height);
+                for child in children {
+                    self.parents.entry(child).or_default().push(id);
+                }
+            } else {
+                stack.push((id, true));
+                stack.extend(children.into_iter().filter(|c| !self.height.contains_key(c)).map(|c| (c, false)));
+            }
+        }
+    }
+
+    // Does the selection reach `target` from any of `from`? Heights fall
+    // along the selection, so only classes above `target` are walked.
+    fn reaches(&self, from: &[Id], target: Id) -> bool {
+        let bound = self.height[&target];
+        let mut seen = HashSet::new();
+        let mut stack = from.to_vec();
+        while let Some(id) = stack.pop() {
+            if id == target {
+                return true;
+            }
+            if self.height[&id] > bound && seen.insert(id) {
+                stack.extend(self.children(id));
+            }
+        }
+        false
+    }
+
+    // Select `node` for `class`, keeping heights and parents up to date.
+    fn select(&mut self, class: Id, node: L) {
+        for child in self.children(class) {
+            let parents = self.parents.get_mut(&child).unwrap();
+            let i = parents.iter().position(|p| *p == class).unwrap();
+            parents.swap_remove(i);
+        }
+        self.choices.insert(class, node);
+        let children = self.children(class);
+        let height = children.iter().map(|c| self.height[c] + 1).max().unwrap_or(0);
+        for child in children {
+            self.parents.entry(child).or_default().push(class);
+        }
+        if height <= self.height[&class] {
+            return;
+        }
+        self.height.insert(class, height);
+        let mut stack = vec![class];
+        while let Some(id) = stack.pop() {
+            let above = self.height[&id] + 1;
+            for parent in self.parents.get(&id).cloned().unwrap_or_default() {
+                if self.height[&parent] < above {
+                    self.height.insert(parent, above);
+                    stack.push(parent);
+                }
+            }
+        }
+    }
+
+    fn refine(&mut self) -> bool {
+        let mut improved = false;
+        let egraph = self.egraph;
+        self.index();
+        let mut classes: Vec<Id> = self.refs.keys().copied().collect();
+        classes.sort();
+        for class in classes {
+            if !self.refs.contains_key(&class) {
+                continue;
+            }
+            for node in egraph[class].iter() {
+                if *node == self.choices[&class] {
+                    continue;
+                }
+                let new_children: Vec<Id> = node.children().iter().map(|c| egraph.find(*c)).collect();
+                if !new_children.iter().all(|c| self.choices.contains_key(c))
+                    || self.reaches(&new_children, class)
+                {
+                    continue;
+                }
+                let old_children = self.children(class);
+                let added: usize = new_children.iter().map(|c| self.inc(*c)).sum();
+                let freed: usize = old_children.iter().map(|c| self.dec(*c)).sum();
+                let gates = if node.is_leaf() { 0 } else { node.gates() };
+                if added + gates < freed + self.weight(class) {
+                    self.select(class, node.clone());
+                    improved = true;
+                } else {
+                    for c in &old_children {
+                        self.inc(*c);
+                    }
+                    for c in &new_children {
+                        self.dec(*c);
+                    }
+                }
+            }
+        }
+        improved
+    }
+
+    /// Gates of the distinct e-nodes in the extracted DAG.
+    pub fn dag_size(&self) -> usize {
+        self.refs.keys().map(|class| self.weight(*class)).sum()
+    }
+
+    /// The e-node selected for `class`.
+    pub fn node(&self, class: Id) -> &L {
+        &self.choices[&self.egraph.find(class)]
+    }
+
+    pub fn find_best(&self, root: Id) -> RecExpr<L> {
+        let root = self.egraph.find(root);
+        self.choices[&root].build_recexpr(|id| self.choices[&self.egraph.find(id)].clone())
+    }
+}
+
+/// Numeric view of a `CostFunction::Cost`, used for temperature sampling.
+pub trait CostValue {
+    fn value(&self) -> f64;
+}
+
+impl CostValue for usize {
+    fn value(&self) -> f64 {
+        *self as f64
+    }
+}
+
+impl CostValue for i32 {
+    fn value(&self) -> f64 {
+        *self as f64
+    }
+}
+
+impl CostValue for f64 {
+    fn value(&self) -> f64 {
+        *self
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Policy {
+    /// Always take a cheapest e-node, breaking ties uniformly at random.
+    Ties,
+    /// Sample e-nodes with probability `exp(-(cost - best) / (t * best))`.
+    Temperature(f64),
+}
+
+/// Randomised extraction for generating alternative candidates.
+///
+/// Costs are computed exactly as in egg's `Extractor`, then every e-class
+/// samples its e-node according to the policy. Only e-nodes whose children
+/// are all strictly cheaper than the e-class itself are eligible, which keeps
+/// the sampled selection acyclic for any monotonic cost function.
+pub struct RandomExtractor<'a, CF: CostFunction<L>, L: Language, N: Analysis<L>> {
+    egraph: &'a EGraph<L, N>,
+    costs: HashMap<Id, CF::Cost>,
+    choices: HashMap<Id, L>,
+}
+
+impl<'a, CF, L, N> RandomExtractor<'a, CF, L, N>
+where
+    CF: CostFunction<L>,
+    CF::Cost: CostValue,
+    L: Language,
+    N: Analysis<L>,
+{
+    pub fn new<R: Rng>(egraph: &'a EGraph<L, N>, mut cost_function: CF, policy: Policy, rng: &mut R) -> Self {
+        let mut cheapest: HashMap<Id, (CF::Cost, L)> = HashMap::new();
+        let mut did_something = true;
+        while did_something {
+            did_something = false;
+            for class in egraph.classes() {
+                for node in class.iter() {
+                    if !node.all(|c| cheapest.contains_key(&egraph.find(c))) {
+                        continue;
+                    }
+                    let cost = cost_function.cost(node, |c| cheapest[&egraph.find(c)].0.clone());
+                    if cheapest.get(&class.id).is_none_or(|(old, _)| cost < *old) {
+                        cheapest.insert(class.id, (cost, node.clone()));
+                        did_something = true;
+                    }
+                }
+            }
+        }
+        let costs: HashMap<Id, CF::Cost> = cheapest.iter().map(|(id, (c, _))| (*id, c.clone())).collect();
+
+        let mut choices = HashMap::new();
+        // visit classes in e-graph order so that a seed always draws the same choices
+        for class in egraph.classes() {
+            let id = class.id;
+            let (best, node) = match cheapest.remove(&id) {
+                Some(choice) => choice,
+                None => continue,
+            };
+            let eligible: Vec<(f64, &L)> = egraph[id]
+                .iter()
+                .filter(|n| n.all(|c| costs.get(&egraph.find(c)).is_some_and(|cc| *cc < best)))
+                .map(|n| (cost_function.cost(n, |c| costs[&egraph.find(c)].clone()).value(), n))
+                .collect();
+            let best = best.value();
+            let weights: Vec<f64> = match policy {
+                Policy::Ties => eligible.iter().map(|(c, _)| if *c <= best { 1.0 } else { 0.0 }).collect(),
+                Policy::Temperature(t) => {
+                    let scale = t.max(f64::MIN_POSITIVE) * best.abs().max(1.0);
+                    eligible.iter().map(|(c, _)| (-(c - best) / scale).exp()).collect()
+                }
+            };
+            let total: f64 = weights.iter().sum();
+            // cost functions that are not strictly monotonic keep the cheapest e-node
+            let mut chosen = node;
+            if total > 0.0 {
+                let mut pick = rng.gen_range(0.0..total);
+                for ((_, n), w) in eligible.iter().zip(&weights) {
+                    if pick < *w {
+                        chosen = (*n).clone();
+                        break;
+                    }
+                    pick -= w;
+                }
+            }
+            choices.insert(id, chosen);
+        }
+
+        RandomExtractor { egraph, costs, choices }
+    }
+
+    /// Cost of the cheapest term in `eclass`, the sampled term may be worse.
+    pub fn find_best_cost(&self, eclass: Id) -> CF::Cost {
+        self.costs[&self.egraph.find(eclass)].clone()
+    }
+
+    pub fn find_best(&self, eclass: Id) -> RecExpr<L> {
+        let root = self.egraph.find(eclass);
+        self.choices[&root].build_recexpr(|id| self.choices[&self.egraph.find(id)].clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::optimize::AstSize;
+    use crate::prop::Prop;
+    use rand::{rngs::StdRng, SeedableRng};
+    use std::collections::HashSet;
+
+    #[test]
+    fn dag_extraction_shares_logic() {
+        // r1 is either `c + d` or `a * b`, r2 reads `a * b`: sharing it is one gate cheaper
+        let mut egraph: EGraph<Prop, ()> = EGraph::default();
+        let r1 = egraph.add_expr(&"(+ c d)".parse().unwrap());
+        let ab = egraph.add_expr(&"(* a b)".parse().unwrap());
+        let r2 = egraph.add(Prop::Not(ab));
+        egraph.union(r1, ab);
+        egraph.rebuild();
+        let extractor = DagExtractor::new(&egraph, &[r1, r2], 10);
+        assert_eq!(extractor.find_best(r1).to_string(), "(* a b)");
+        assert_eq!(extractor.find_best(r2).to_string(), "(! (* a b))");
+        assert_eq!(extractor.dag_size(), 2);
+    }
+
+    #[test]
+    fn dag_extraction_avoids_cycles() {
+        // x = !!x, the class reads itself through its `!` node
+        let mut egraph: EGraph<Prop, ()> = EGraph::default();
+        let x = egraph.add_expr(&"x".parse().unwrap());
+        let nn = egraph.add_expr(&"(! (! x))".parse().unwrap());
+        let y = egraph.add_expr(&"(* (! (! x)) y)".parse().unwrap());
+        egraph.union(x, nn);
+        egraph.rebuild();
+        let extractor = DagExtractor::new(&egraph, &[y], 10);
+        assert_eq!(extractor.find_best(y).to_string(), "(* x y)");
+        assert_eq!(extractor.dag_size(), 1);
+    }
+
+    #[test]
+    fn dag_extraction_counts_expanded_gates() {
+        // `^` is written as five gates, so the sum of products costs no more
+        let mut egraph: EGraph<Prop, ()> = EGraph::default();
+        let xor = egraph.add_expr(&"(^ a b)".parse().unwrap());
+        let sop = egraph.add_expr(&"(+ (* a (! b)) (* (! a) b))".parse().unwrap());
+        egraph.union(xor, sop);
+        egraph.rebuild();
+        let extractor = DagExtractor::new(&egraph, &[xor], 10);
+        assert_eq!(extractor.dag_size(), 5);
+    }
+
+    // `a * b`, `b * a` (3 each with the leaves) and `a ^ b` (7) in one class
+    fn and_class() -> (EGraph<Prop, ()>, Id) {
+        let mut egraph: EGraph<Prop, ()> = EGraph::default();
+        let root = egraph.add_expr(&"(* a b)".parse().unwrap());
+        for other in ["(* b a)", "(^ a b)"] {
+            let id = egraph.add_expr(&other.parse().unwrap());
+            egraph.union(root, id);
+        }
+        egraph.rebuild();
+        (egraph, root)
+    }
+
+    fn samples(policy: Policy) -> HashSet<String> {
+        let (egraph, root) = and_class();
+        (0..64)
+            .map(|seed| {
+                let extractor = RandomExtractor::new(&egraph, AstSize, policy, &mut StdRng::seed_from_u64(seed));
+                assert_eq!(extractor.find_best_cost(root), 3);
+                extractor.find_best(root).to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn random_extraction_breaks_ties() {
+        assert_eq!(samples(Policy::Ties), HashSet::from(["(* a b)".to_string(), "(* b a)".to_string()]));
+    }
+
+    #[test]
+    fn random_extraction_follows_the_temperature() {
+        assert!(samples(Policy::Temperature(10.0)).contains("(^ a b)"));
+        assert!(!samples(Policy::Temperature(0.0)).contains("(^ a b)"));
+    }
+
+    #[test]
+    fn random_extraction_repeats_with_the_seed() {
+        let (egraph, root) = and_class();
+        let draw = |seed| RandomExtractor::new(&egraph, AstSize, Policy::Temperature(1.0), &mut StdRng::seed_from_u64(seed)).find_best(root);
+        for seed in 0..8 {
+            assert_eq!(draw(seed), draw(seed));
+        }
+    }
+}
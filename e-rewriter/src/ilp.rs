@@ -0,0 +1,392 @@
+use egg::*;
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::extract::{DagExtractor, GateCost};
+
+// the closure of necessary classes takes classes² bits, so it is skipped past this
+const CLOSURE_LIMIT: usize = 20_000;
+
+/// Exact extraction as a 0-1 integer program.
+///
+/// One binary `x_n` per e-node, minimise `sum cost(n) * x_n` subject to
+///   - every root class selects an e-node,
+///   - a selected e-node requires one selected e-node in each child class,
+///   - the selected e-nodes form an acyclic graph.
+///
+/// The program is solved by depth-first branch and bound, branching on the
+/// e-node of one required class at a time. An e-node is dropped up front if
+/// another of its class costs no more and needs a subset of its children.
+/// Every class also knows the classes that any selection for it needs,
+/// whichever e-nodes are picked, so the lower bound is the cost so far plus
+/// the cheapest e-node of each class that the undecided required classes
+/// need, counted once however many of them need it. Cycles are cut as soon
+/// as a branch would close one. The DAG extractor's selection is the first
+/// incumbent, so the search can stop at the time limit and still return a
+/// valid (possibly suboptimal) result.
+pub struct IlpExtractor<'a, L: Language, N: Analysis<L>> {
+    egraph: &'a EGraph<L, N>,
+    // classes by dense index
+    ids: Vec<Id>,
+    index: HashMap<Id, usize>,
+    // the weight of every e-node of a class, in the class's order
+    weights: Vec<Vec<f64>>,
+    min_weight: Vec<f64>,
+    // the e-nodes worth branching on, cheapest first, with their child classes
+    candidates: Vec<Vec<(usize, Vec<usize>)>>,
+    // per class, a bit set of the classes any selection for it includes
+    necessary: Option<Vec<Vec<u64>>>,
+    chosen: Vec<Option<usize>>,
+    pending: Vec<bool>,
+    best: HashMap<Id, usize>,
+    best_cost: f64,
+    pub initial_cost: f64,
+    /// No selection costs less than this.
+    pub lower_bound: f64,
+    pub optimal: bool,
+    deadline: Instant,
+    visited: usize,
+}
+
+// One class being branched on.
+struct Frame {
+    class: usize,
+    // classes popped from the stack of required classes that were chosen already
+    skipped: Vec<usize>,
+    next: usize,
+    cost: f64,
+    // the branch in progress: length of the stack before it, and the classes it required
+    branch: Option<(usize, Vec<usize>)>,
+}
+
+impl<'a, L, N> IlpExtractor<'a, L, N>
+where
+    L: Language + GateCost,
+    N: Analysis<L>,
+{
+    pub fn new<F>(egraph: &'a EGraph<L, N>, roots: &[Id], mut node_cost: F, time_limit: Duration) -> Self
+    where
+        F: FnMut(&L) -> f64,
+    {
+        let ids: Vec<Id> = egraph.classes().map(|c| c.id).collect();
+        let index: HashMap<Id, usize> = ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+        let weights: Vec<Vec<f64>> = ids.iter().map(|id| egraph[*id].iter().map(&mut node_cost).collect()).collect();
+        let min_weight = weights.iter().map(|w| w.iter().copied().fold(f64::INFINITY, f64::min)).collect();
+        let candidates = ids
+            .iter()
+            .enumerate()
+            .map(|(c, id)| {
+                let mut nodes: Vec<(usize, Vec<usize>)> = egraph[*id]
+                    .iter()
+                    .enumerate()
+                    .map(|(k, node)| {
+                        let mut children: Vec<usize> = node.children().iter().map(|child| index[&egraph.find(*child)]).collect();
+                        children.sort();
+                        children.dedup();
+                        (k, children)
+                    })
+                    .collect();
+                nodes.sort_by(|a, b| weights[c][a.0].total_cmp(&weights[c][b.0]).then(a.1.len().cmp(&b.1.len())));
+                let mut kept: Vec<(usize, Vec<usize>)> = Vec::new();
+                for (k, children) in nodes {
+                    let dominated = kept.iter().any(|(m, other)| weights[c][*m] <= weights[c][k] && other.iter().all(|o| children.binary_search(o).is_ok()));
+                    if !dominated {
+                        kept.push((k, children));
+                    }
+                }
+                kept
+            })
+            .collect();
+
+        let classes = ids.len();
+        let mut extractor = IlpExtractor {
+            egraph,
+            ids,
+            index,
+            weights,
+            min_weight,
+            candidates,
+            necessary: None,
+            chosen: vec![None; classes],
+            pending: vec![false; classes],
+            best: HashMap::new(),
+            best_cost: f64::INFINITY,
+            initial_cost: f64::INFINITY,
+            lower_bound: 0.0,
+            optimal: false,
+            deadline: Instant::now() + time_limit,
+            visited: 0,
+        };
+        if classes <= CLOSURE_LIMIT {
+            extractor.necessary = Some(extractor.closure());
+        }
+
+        let roots: Vec<Id> = roots.iter().map(|r| egraph.find(*r)).collect();
+        extractor.seed(&roots);
+        let mut todo = Vec::new();
+        for root in &roots {
+            let root = extractor.index[root];
+            extractor.require(root, &mut todo);
+        }
+        extractor.lower_bound = extractor.bound(&todo);
+        extractor.optimal = extractor.search(&mut todo);
+        if extractor.optimal {
+            extractor.lower_bound = extractor.best_cost;
+        }
+        extractor
+    }
+
+    // The largest sets with necessary[c] = {c} + the intersection, over the
+    // candidates of c, of the union of necessary[d] over their children d.
+    fn closure(&self) -> Vec<Vec<u64>> {
+        let classes = self.ids.len();
+        let words = classes.div_ceil(64);
+        let mut necessary = vec![vec![u64::MAX; words]; classes];
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for c in 0..classes {
+                let mut meet = vec![u64::MAX; words];
+                for (_, children) in &self.candidates[c] {
+                    let mut join = vec![0u64; words];
+                    for d in children {
+                        for (j, n) in join.iter_mut().zip(&necessary[*d]) {
+                            *j |= n;
+                        }
+                    }
+                    for (m, j) in meet.iter_mut().zip(&join) {
+                        *m &= j;
+                    }
+                }
+                meet[c / 64] |= 1 << (c % 64);
+                if meet != necessary[c] {
+                    necessary[c] = meet;
+                    changed = true;
+                }
+            }
+        }
+        necessary
+    }
+
+    // The least the required classes still to decide add to the cost.
+    fn bound(&self, todo: &[usize]) -> f64 {
+        let open = todo.iter().copied().filter(|c| self.pending[*c]);
+        let Some(necessary) = &self.necessary else {
+            return open.map(|c| self.min_weight[c]).sum();
+        };
+        let mut union = vec![0u64; necessary.first().map_or(0, |n| n.len())];
+        for c in open {
+            for (u, n) in union.iter_mut().zip(&necessary[c]) {
+                *u |= n;
+            }
+        }
+        let mut bound = 0.0;
+        for (w, mut word) in union.into_iter().enumerate() {
+            while word != 0 {
+                let c = w * 64 + word.trailing_zeros() as usize;
+                word &= word - 1;
+                if self.chosen[c].is_none() {
+                    bound += self.min_weight[c];
+                }
+            }
+        }
+        bound
+    }
+
+    fn seed(&mut self, roots: &[Id]) {
+        let dag = DagExtractor::new(self.egraph, roots, 10);
+        let mut stack = roots.to_vec();
+        let mut cost = 0.0;
+        while let Some(class) = stack.pop() {
+            if self.best.contains_key(&class) {
+                continue;
+            }
+            let node = dag.node(class);
+            let index = self.egraph[class].iter().position(|n| n == node).unwrap();
+            cost += self.weights[self.index[&class]][index];
+            self.best.insert(class, index);
+            stack.extend(node.children().iter().map(|c| self.egraph.find(*c)));
+        }
+        self.best_cost = cost;
+        self.initial_cost = cost;
+    }
+
+    fn require(&mut self, class: usize, todo: &mut Vec<usize>) -> bool {
+        if self.chosen[class].is_some() || self.pending[class] {
+            return false;
+        }
+        self.pending[class] = true;
+        todo.push(class);
+        true
+    }
+
+    // Would selecting a node with `children` for `class` close a cycle through the choices made so far?
+    fn closes_cycle(&self, class: usize, children: &[usize]) -> bool {
+        let mut seen = vec![false; self.ids.len()];
+        let mut stack = children.to_vec();
+        while let Some(c) = stack.pop() {
+            if c == class {
+                return true;
+            }
+            if std::mem::replace(&mut seen[c], true) {
+                continue;
+            }
+            if let Some(k) = self.chosen[c] {
+                stack.extend(&self.candidates[c][k].1);
+            }
+        }
+        false
+    }
+
+    // Returns false if the time limit cut the search short. Circuits make
+    // deep e-graphs, so the branches are kept on a stack of frames instead of
+    // recursing.
+    fn search(&mut self, todo: &mut Vec<usize>) -> bool {
+        let mut frames: Vec<Frame> = Vec::new();
+        let mut cost = 0.0;
+        loop {
+            // a new branch, at `cost`
+            self.visited += 1;
+            if self.visited.is_multiple_of(1024) && Instant::now() > self.deadline {
+                return false;
+            }
+            let mut skipped = Vec::new();
+            let class = loop {
+                match todo.pop() {
+                    Some(c) if self.chosen[c].is_some() => skipped.push(c),
+                    other => break other,
+                }
+            };
+            match class {
+                Some(class) => {
+                    self.pending[class] = false;
+                    frames.push(Frame { class, skipped, next: 0, cost, branch: None });
+                }
+                None => {
+                    if cost < self.best_cost {
+                        self.best_cost = cost;
+                        self.best = (0..self.ids.len())
+                            .filter_map(|c| self.chosen[c].map(|k| (self.ids[c], self.candidates[c][k].0)))
+                            .collect();
+                    }
+                    todo.extend(skipped.into_iter().rev());
+                }
+            }
+
+            // the next candidate of the innermost class that has one left
+            'next: loop {
+                let Some(frame) = frames.last_mut() else {
+                    return true;
+                };
+                let class = frame.class;
+                if let Some((mark, added)) = frame.branch.take() {
+                    todo.truncate(mark);
+                    for child in added {
+                        self.pending[child] = false;
+                    }
+                    self.chosen[class] = None;
+                }
+                while frame.next < self.candidates[class].len() {
+                    let k = frame.next;
+                    frame.next += 1;
+                    let (index, children) = &self.candidates[class][k];
+                    let weight = self.weights[class][*index];
+                    if frame.cost + weight >= self.best_cost || self.closes_cycle(class, children) {
+                        continue;
+                    }
+                    self.chosen[class] = Some(k);
+                    let mark = todo.len();
+                    let mut added = Vec::new();
+                    for child in children.clone() {
+                        if self.require(child, todo) {
+                            added.push(child);
+                        }
+                    }
+                    let frame = frames.last_mut().unwrap();
+                    frame.branch = Some((mark, added));
+                    if frame.cost + weight + self.bound(todo) < self.best_cost {
+                        cost = frame.cost + weight;
+                        break 'next;
+                    }
+                    // pruned, undo it on the next pass
+                    continue 'next;
+                }
+                let frame = frames.pop().unwrap();
+                self.pending[class] = true;
+                todo.push(class);
+                todo.extend(frame.skipped.into_iter().rev());
+            }
+        }
+    }
+
+    /// Total cost of the best selection found.
+    pub fn cost(&self) -> f64 {
+        self.best_cost
+    }
+
+    pub fn find_best(&self, root: Id) -> RecExpr<L> {
+        let get = |id: Id| {
+            let id = self.egraph.find(id);
+            self.egraph[id].nodes[self.best[&id]].clone()
+        };
+        get(root).build_recexpr(get)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prop::Prop;
+
+    fn gates(node: &Prop) -> f64 {
+        if node.is_leaf() {
+            0.0
+        } else {
+            node.gates() as f64
+        }
+    }
+
+    #[test]
+    fn finds_the_shared_selection() {
+        // r1 is either `c + d` or `a * b`, r2 reads `a * b`: sharing it costs 2 gates
+        let mut egraph: EGraph<Prop, ()> = EGraph::default();
+        let r1 = egraph.add_expr(&"(+ c d)".parse().unwrap());
+        let ab = egraph.add_expr(&"(* a b)".parse().unwrap());
+        let r2 = egraph.add(Prop::Not(ab));
+        egraph.union(r1, ab);
+        egraph.rebuild();
+        let extractor = IlpExtractor::new(&egraph, &[r1, r2], gates, Duration::from_secs(10));
+        assert!(extractor.optimal);
+        assert_eq!((extractor.cost(), extractor.lower_bound), (2.0, 2.0));
+        assert_eq!(extractor.find_best(r1).to_string(), "(* a b)");
+        assert_eq!(extractor.find_best(r2).to_string(), "(! (* a b))");
+    }
+
+    #[test]
+    fn cuts_cycles() {
+        // x = !!x, the class reads itself through its `!` node
+        let mut egraph: EGraph<Prop, ()> = EGraph::default();
+        let x = egraph.add_expr(&"x".parse().unwrap());
+        let nn = egraph.add_expr(&"(! (! x))".parse().unwrap());
+        let y = egraph.add_expr(&"(* (! (! x)) y)".parse().unwrap());
+        egraph.union(x, nn);
+        egraph.rebuild();
+        let extractor = IlpExtractor::new(&egraph, &[y], gates, Duration::from_secs(10));
+        assert_eq!(extractor.find_best(y).to_string(), "(* x y)");
+        assert_eq!(extractor.cost(), 1.0);
+    }
+
+    #[test]
+    fn keeps_the_dag_selection_past_the_deadline() {
+        let mut egraph: EGraph<Prop, ()> = EGraph::default();
+        let root = egraph.add_expr(&"(+ (* a b) (* a c))".parse().unwrap());
+        let factored = egraph.add_expr(&"(* a (+ b c))".parse().unwrap());
+        egraph.union(root, factored);
+        egraph.rebuild();
+        let extractor = IlpExtractor::new(&egraph, &[root], gates, Duration::ZERO);
+        assert_eq!((extractor.initial_cost, extractor.cost()), (2.0, 2.0));
+        assert!(extractor.lower_bound <= extractor.cost());
+        assert_eq!(extractor.find_best(root).to_string(), "(* a (+ b c))");
+    }
+}
@@ -0,0 +1,45 @@
+//! Parts of e-rewriter shared with the other tools in this repository.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+// 0 with --quiet, 1 by default, 2 and up with -v
+static VERBOSITY: AtomicU8 = AtomicU8::new(1);
+
+pub fn verbosity() -> u8 {
+    VERBOSITY.load(Ordering::Relaxed)
+}
+
+pub fn set_verbosity(level: u8) {
+    VERBOSITY.store(level, Ordering::Relaxed);
+}
+
+/// `println!` that is skipped below the given verbosity.
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($arg:tt)*) => {
+        if $crate::verbosity() >= $level {
+            println!($($arg)*);
+        }
+    };
+}
+
+pub mod aiger;
+pub mod bench;
+pub mod blif;
+pub mod cec;
+pub mod cli;
+pub mod eqn;
+pub mod explain;
+pub mod extract;
+pub mod ilp;
+pub mod liberty;
+pub mod netlist;
+pub mod optimize;
+pub mod partition;
+pub mod proof;
+pub mod prop;
+pub mod rules;
+pub mod schedule;
+pub mod stats;
+pub mod sweep;
+pub mod verilog;
@@ -0,0 +1,594 @@
+//! Cell costs from a Liberty (`.lib`) library.
+//!
+//! Only what extraction needs is read: the area of every combinational
+//! single-output cell and its worst-case delay (the largest entry of its
+//! `cell_rise`/`cell_fall` tables, or `intrinsic_rise`/`intrinsic_fall` for
+//! older libraries). Cells are matched to operators by the truth table of
+//! their output `function`, so `AND2x2_ASAP7_75t_R` and `and2_1` both serve
+//! `*` without relying on naming conventions.
+
+use egg::*;
+
+use std::collections::HashMap;
+use std::fmt::Display;
+
+#[derive(Debug, Default)]
+struct Group {
+    kind: String,
+    args: Vec<String>,
+    attrs: Vec<(String, Vec<String>)>,
+    groups: Vec<Group>,
+}
+
+impl Group {
+    fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs.iter().find(|(n, _)| n == name).and_then(|(_, v)| v.first()).map(String::as_str)
+    }
+
+    fn children<'a>(&'a self, kind: &'a str) -> impl Iterator<Item = &'a Group> + 'a {
+        self.groups.iter().filter(move |g| g.kind == kind)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Str(String),
+    Punct(char),
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut last = ' ';
+                loop {
+                    match chars.next() {
+                        Some('/') if last == '*' => break,
+                        Some(c) => last = c,
+                        None => return Err("unterminated comment".to_string()),
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '"' => {
+                let mut s = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        // line continuation inside a string
+                        Some('\\') if matches!(chars.peek(), Some('\n') | Some('\r')) => {}
+                        Some(c) => s.push(c),
+                        None => return Err("unterminated string".to_string()),
+                    }
+                }
+                tokens.push(Token::Str(s));
+            }
+            '(' | ')' | '{' | '}' | ':' | ';' | ',' => tokens.push(Token::Punct(c)),
+            c if c.is_whitespace() || c == '\\' => {}
+            c => {
+                let mut word = c.to_string();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || "(){}:;,\"\\".contains(c) {
+                        break;
+                    }
+                    word.push(c);
+                    chars.next();
+                }
+                tokens.push(Token::Word(word));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Result<Token, String> {
+        let token = self.tokens.get(self.pos).cloned().ok_or("unexpected end of library")?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(&Token::Punct(c)) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), String> {
+        match self.next()? {
+            Token::Punct(p) if p == c => Ok(()),
+            t => Err(format!("expected `{}`, found {:?}", c, t)),
+        }
+    }
+
+    fn value(&mut self) -> Result<String, String> {
+        match self.next()? {
+            Token::Word(w) | Token::Str(w) => Ok(w),
+            t => Err(format!("expected a value, found {:?}", t)),
+        }
+    }
+
+    // `name : value ;`, `name (args) ;` or `name (args) { ... }`
+    fn statement(&mut self, parent: &mut Group) -> Result<(), String> {
+        let name = self.value()?;
+        if self.eat(':') {
+            let value = self.value()?;
+            self.eat(';');
+            parent.attrs.push((name, vec![value]));
+            return Ok(());
+        }
+        self.expect('(')?;
+        let mut args = Vec::new();
+        while !self.eat(')') {
+            if !self.eat(',') {
+                args.push(self.value()?);
+            }
+        }
+        if self.eat('{') {
+            let mut group = Group { kind: name, args, ..Default::default() };
+            while !self.eat('}') {
+                self.statement(&mut group)?;
+            }
+            parent.groups.push(group);
+        } else {
+            self.eat(';');
+            parent.attrs.push((name, args));
+        }
+        Ok(())
+    }
+}
+
+// Truth table of a Liberty `function` over `pins`, row `r` assigns bit `i` of `r` to `pins[i]`.
+// The table is one word, so at most 6 pins.
+fn truth_table(function: &str, pins: &[String]) -> Result<u64, String> {
+    if pins.len() > 6 {
+        return Err(format!("function `{}` has more than 6 pins", function));
+    }
+    let chars: Vec<char> = function.chars().collect();
+    let mut pos = 0;
+    let rows = 1u32 << pins.len();
+    let mask = if rows == 64 { !0 } else { (1u64 << rows) - 1 };
+    let table = parse_or(&chars, &mut pos, pins)?;
+    skip_space(&chars, &mut pos);
+    if pos != chars.len() {
+        return Err(format!("cannot parse function `{}`", function));
+    }
+    Ok(table & mask)
+}
+
+fn skip_space(chars: &[char], pos: &mut usize) {
+    while *pos < chars.len() && chars[*pos].is_whitespace() {
+        *pos += 1;
+    }
+}
+
+// precedence: `!`/`'` > `^` > `*`/`&`/juxtaposition > `+`/`|`
+fn parse_or(chars: &[char], pos: &mut usize, pins: &[String]) -> Result<u64, String> {
+    let mut value = parse_and(chars, pos, pins)?;
+    loop {
+        skip_space(chars, pos);
+        match chars.get(*pos) {
+            Some('+') | Some('|') => {
+                *pos += 1;
+                value |= parse_and(chars, pos, pins)?;
+            }
+            _ => return Ok(value),
+        }
+    }
+}
+
+fn parse_and(chars: &[char], pos: &mut usize, pins: &[String]) -> Result<u64, String> {
+    let mut value = parse_xor(chars, pos, pins)?;
+    loop {
+        skip_space(chars, pos);
+        match chars.get(*pos) {
+            Some('*') | Some('&') => {
+                *pos += 1;
+                value &= parse_xor(chars, pos, pins)?;
+            }
+            Some(c) if c.is_alphanumeric() || *c == '(' || *c == '!' || *c == '_' => {
+                value &= parse_xor(chars, pos, pins)?;
+            }
+            _ => return Ok(value),
+        }
+    }
+}
+
+fn parse_xor(chars: &[char], pos: &mut usize, pins: &[String]) -> Result<u64, String> {
+    let mut value = parse_not(chars, pos, pins)?;
+    loop {
+        skip_space(chars, pos);
+        if chars.get(*pos) == Some(&'^') {
+            *pos += 1;
+            value ^= parse_not(chars, pos, pins)?;
+        } else {
+            return Ok(value);
+        }
+    }
+}
+
+fn parse_not(chars: &[char], pos: &mut usize, pins: &[String]) -> Result<u64, String> {
+    skip_space(chars, pos);
+    let mut value = match chars.get(*pos) {
+        Some('!') => {
+            *pos += 1;
+            !parse_not(chars, pos, pins)?
+        }
+        Some('(') => {
+            *pos += 1;
+            let value = parse_or(chars, pos, pins)?;
+            skip_space(chars, pos);
+            if chars.get(*pos) != Some(&')') {
+                return Err("expected `)` in function".to_string());
+            }
+            *pos += 1;
+            value
+        }
+        Some(_) => {
+            let start = *pos;
+            while *pos < chars.len() && (chars[*pos].is_alphanumeric() || chars[*pos] == '_') {
+                *pos += 1;
+            }
+            let name: String = chars[start..*pos].iter().collect();
+            match name.as_str() {
+                "" => return Err(format!("unexpected `{}` in function", chars[start])),
+                "0" => 0,
+                "1" => !0,
+                name => {
+                    let i = pins.iter().position(|p| p == name).ok_or_else(|| format!("unknown pin `{}`", name))?;
+                    (0..64u64).filter(|r| r >> i & 1 == 1).fold(0u64, |t, r| t | 1 << r)
+                }
+            }
+        }
+        None => return Err("unexpected end of function".to_string()),
+    };
+    while chars.get(*pos) == Some(&'\'') {
+        *pos += 1;
+        value = !value;
+    }
+    Ok(value)
+}
+
+/// The operators a cell can implement, with their arity and truth table.
+type Truth = fn(&[bool]) -> bool;
+
+const OPERATORS: &[(&str, usize, Truth)] = &[
+    ("!", 1, |x| !x[0]),
+    ("*", 2, |x| x[0] && x[1]),
+    ("+", 2, |x| x[0] || x[1]),
+    ("^", 2, |x| x[0] ^ x[1]),
+    ("xnor", 2, |x| x[0] == x[1]),
+    ("mux", 3, |x| if x[0] { x[1] } else { x[2] }),
+    ("maj", 3, |x| (x[0] && x[1]) || (x[2] && (x[0] || x[1]))),
+];
+
+fn operator_table(arity: usize, f: Truth, perm: &[usize]) -> u64 {
+    let mut table = 0;
+    for row in 0..1u64 << arity {
+        let x: Vec<bool> = perm.iter().map(|&p| row >> p & 1 == 1).collect();
+        if f(&x) {
+            table |= 1 << row;
+        }
+    }
+    table
+}
+
+fn permutations(n: usize) -> Vec<Vec<usize>> {
+    if n == 0 {
+        return vec![vec![]];
+    }
+    let mut result = Vec::new();
+    for p in permutations(n - 1) {
+        for i in 0..n {
+            let mut q = p.clone();
+            q.insert(i, n - 1);
+            result.push(q);
+        }
+    }
+    result
+}
+
+/// Cost of one operator: the cheapest cell that implements it, or a
+/// decomposition into cheaper operators when no cell does.
+#[derive(Debug, Clone)]
+pub struct OpCost {
+    pub area: f64,
+    pub area_cell: String,
+    pub delay: f64,
+    pub delay_cell: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Library {
+    pub name: String,
+    ops: HashMap<String, OpCost>,
+}
+
+impl Library {
+    pub fn parse(text: &str) -> Result<Library, String> {
+        let mut parser = Parser { tokens: tokenize(text)?, pos: 0 };
+        let mut root = Group::default();
+        parser.statement(&mut root)?;
+        let library = root.groups.pop().filter(|g| g.kind == "library").ok_or("expected a `library` group")?;
+
+        let mut ops: HashMap<String, OpCost> = HashMap::new();
+        for cell in library.children("cell") {
+            let sequential = ["ff", "latch", "statetable"].iter().any(|k| cell.children(k).next().is_some());
+            let outputs: Vec<&Group> = cell
+                .children("pin")
+                .filter(|p| p.attr("direction") == Some("output") && p.attr("function").is_some())
+                .collect();
+            if sequential || outputs.len() != 1 {
+                continue;
+            }
+            let output = outputs[0];
+            let inputs: Vec<String> = cell
+                .children("pin")
+                .filter(|p| p.attr("direction") == Some("input"))
+                .filter_map(|p| p.args.first().cloned())
+                .collect();
+            // no operator has more than 3 operands
+            if inputs.len() > 3 {
+                continue;
+            }
+            let name = cell.args.first().cloned().unwrap_or_default();
+            let area: f64 = match cell.attr("area") {
+                Some(a) => a.parse().map_err(|_| format!("cell `{}`: bad area `{}`", name, a))?,
+                None => continue,
+            };
+            let Ok(table) = truth_table(output.attr("function").unwrap(), &inputs) else {
+                continue;
+            };
+            let delay = worst_delay(output);
+
+            for (op, arity, f) in OPERATORS {
+                if *arity != inputs.len() || !permutations(*arity).iter().any(|p| operator_table(*arity, *f, p) == table) {
+                    continue;
+                }
+                let entry = ops.entry(op.to_string()).or_insert_with(|| OpCost {
+                    area: f64::INFINITY,
+                    area_cell: String::new(),
+                    delay: f64::INFINITY,
+                    delay_cell: String::new(),
+                });
+                if area < entry.area {
+                    entry.area = area;
+                    entry.area_cell = name.clone();
+                }
+                if delay < entry.delay {
+                    entry.delay = delay;
+                    entry.delay_cell = name.clone();
+                }
+            }
+        }
+
+        for op in ["!", "*", "+"] {
+            if !ops.contains_key(op) {
+                return Err(format!("no cell implements `{}`", op));
+            }
+        }
+        // (area, delay) of a sequence of gates, the last three on the critical path
+        let composite = |ops: &HashMap<String, OpCost>, gates: &[&str], path: &[&str], name: &str| {
+            let area = gates.iter().map(|g| ops[*g].area).sum();
+            let delay = path.iter().map(|g| ops[*g].delay).sum();
+            OpCost { area, area_cell: name.to_string(), delay, delay_cell: name.to_string() }
+        };
+        let derived: [(&str, &[&str], &[&str], &str); 7] = [
+            ("^", &["*", "*", "+", "!", "!"], &["!", "*", "+"], "AND-OR"),
+            ("xnor", &["^", "!"], &["^", "!"], "XOR-INV"),
+            ("mux", &["*", "*", "+", "!"], &["!", "*", "+"], "AND-OR"),
+            ("maj", &["*", "*", "+", "+"], &["+", "*", "+"], "AND-OR"),
+            ("->", &["!", "+"], &["!", "+"], "INV-OR"),
+            ("let", &["xnor"], &["xnor"], "XNOR"),
+            ("&", &["!", "*"], &["!", "*"], "INV-AND"),
+        ];
+        for (op, gates, path, name) in derived {
+            if !ops.contains_key(op) {
+                let cost = composite(&ops, gates, path, name);
+                ops.insert(op.to_string(), cost);
+            }
+        }
+
+        Ok(Library { name: library.args.first().cloned().unwrap_or_default(), ops })
+    }
+
+    /// Area of the cell for `op`, 0 for inputs and constants.
+    pub fn area(&self, op: &str) -> f64 {
+        self.ops.get(op).map_or(0.0, |c| c.area)
+    }
+
+    /// Worst-case delay of the cell for `op`, 0 for inputs and constants.
+    pub fn delay(&self, op: &str) -> f64 {
+        self.ops.get(op).map_or(0.0, |c| c.delay)
+    }
+
+    /// Operators with their costs, sorted by operator.
+    pub fn ops(&self) -> Vec<(&str, &OpCost)> {
+        let mut ops: Vec<(&str, &OpCost)> = self.ops.iter().map(|(op, c)| (op.as_str(), c)).collect();
+        ops.sort_by(|a, b| a.0.cmp(b.0));
+        ops
+    }
+}
+
+fn worst_delay(pin: &Group) -> f64 {
+    let mut worst: f64 = 0.0;
+    for timing in pin.children("timing") {
+        for (name, values) in &timing.attrs {
+            if name == "intrinsic_rise" || name == "intrinsic_fall" {
+                worst = values.iter().filter_map(|v| v.parse().ok()).fold(worst, f64::max);
+            }
+        }
+        for table in timing.groups.iter().filter(|g| g.kind == "cell_rise" || g.kind == "cell_fall") {
+            for (name, values) in &table.attrs {
+                if name == "values" {
+                    worst = values
+                        .iter()
+                        .flat_map(|v| v.split(|c: char| c == ',' || c.is_whitespace()))
+                        .filter_map(|v| v.parse().ok())
+                        .fold(worst, f64::max);
+                }
+            }
+        }
+    }
+    worst
+}
+
+/// Total cell area of a tree of operators.
+pub struct AreaCost<'a> {
+    pub library: &'a Library,
+}
+
+impl<L: Language + Display> CostFunction<L> for AreaCost<'_> {
+    type Cost = f64;
+    fn cost<C>(&mut self, enode: &L, mut costs: C) -> Self::Cost
+    where
+        C: FnMut(Id) -> Self::Cost,
+    {
+        enode.fold(self.library.area(&enode.to_string()), |sum, id| sum + costs(id))
+    }
+}
+
+/// Critical-path delay through a tree of operators.
+pub struct DelayCost<'a> {
+    pub library: &'a Library,
+}
+
+impl<L: Language + Display> CostFunction<L> for DelayCost<'_> {
+    type Cost = f64;
+    fn cost<C>(&mut self, enode: &L, mut costs: C) -> Self::Cost
+    where
+        C: FnMut(Id) -> Self::Cost,
+    {
+        self.library.delay(&enode.to_string()) + enode.fold(0.0, |max: f64, id| max.max(costs(id)))
+    }
+}
+
+/// Cell area of a multi-output circuit, a sub-circuit shared between
+/// outputs is counted once.
+pub fn circuit_area<L: Language + Display>(library: &Library, exprs: &[RecExpr<L>]) -> f64 {
+    let mut memo: HashMap<L, Id> = HashMap::new();
+    let mut area = 0.0;
+    for expr in exprs {
+        let mut ids: Vec<Id> = Vec::with_capacity(expr.as_ref().len());
+        for node in expr.as_ref() {
+            let node = node.clone().map_children(|c| ids[usize::from(c)]);
+            let next = Id::from(memo.len());
+            let id = *memo.entry(node.clone()).or_insert_with(|| {
+                area += library.area(&node.to_string());
+                next
+            });
+            ids.push(id);
+        }
+    }
+    area
+}
+
+/// Delay of the slowest output of a circuit.
+pub fn circuit_delay<L: Language + Display>(library: &Library, exprs: &[RecExpr<L>]) -> f64 {
+    exprs.iter().map(|e| DelayCost { library }.cost_rec(e)).fold(0.0, f64::max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIBRARY: &str = r#"
+library (test) {
+  cell (INV) {
+    area : 1;
+    pin (A) { direction : input; }
+    pin (Y) { direction : output; function : "!A"; timing () { cell_rise (t) { values ("5, 7"); } } }
+  }
+  cell (AND2) {
+    area : 2;
+    pin (A) { direction : input; }
+    pin (B) { direction : input; }
+    pin (Y) { direction : output; function : "(A B)"; timing () { cell_rise (t) { values ("10"); } } }
+  }
+  cell (OR2) {
+    area : 3;
+    pin (A) { direction : input; }
+    pin (B) { direction : input; }
+    pin (Y) { direction : output; function : "A | B"; timing () { intrinsic_rise : 12; } }
+  }
+  cell (MUX2) {
+    area : 4;
+    pin (S) { direction : input; }
+    pin (A) { direction : input; }
+    pin (B) { direction : input; }
+    pin (Y) { direction : output; function : "(S * B) + (!S * A)"; }
+  }
+  cell (AND9) {
+    area : 9;
+    pin (A) { direction : input; } pin (B) { direction : input; } pin (C) { direction : input; }
+    pin (D) { direction : input; } pin (E) { direction : input; } pin (F) { direction : input; }
+    pin (G) { direction : input; } pin (H) { direction : input; } pin (I) { direction : input; }
+    pin (Y) { direction : output; function : "A * B * C * D * E * F * G * H * I"; }
+  }
+}
+"#;
+
+    #[test]
+    fn matches_cells_to_operators() {
+        let library = Library::parse(LIBRARY).unwrap();
+        assert_eq!(library.name, "test");
+        assert_eq!(library.area("!"), 1.0);
+        assert_eq!(library.area("*"), 2.0);
+        assert_eq!(library.area("+"), 3.0);
+        // the select pin is matched whatever its position
+        assert_eq!(library.area("mux"), 4.0);
+        // no XOR cell, priced as AND-OR
+        assert_eq!(library.area("^"), 2.0 * 2.0 + 3.0 + 2.0 * 1.0);
+        assert_eq!(library.delay("!"), 7.0);
+        assert_eq!(library.delay("+"), 12.0);
+    }
+
+    #[test]
+    fn skips_wide_cells() {
+        // a 9-input cell used to overflow the truth table
+        let library = Library::parse(LIBRARY).unwrap();
+        assert!(library.ops().iter().all(|(_, cost)| cost.area_cell != "AND9"));
+        let pins: Vec<String> = (0..7).map(|i| format!("p{}", i)).collect();
+        assert!(truth_table("p0 * p6", &pins).is_err());
+    }
+
+    #[test]
+    fn truth_tables() {
+        let pins = vec!["A".to_string(), "B".to_string()];
+        assert_eq!(truth_table("A * B", &pins), Ok(0b1000));
+        assert_eq!(truth_table("A ^ B", &pins), Ok(0b0110));
+        assert_eq!(truth_table("(A + B)'", &pins), Ok(0b0001));
+        assert!(truth_table("A * C", &pins).is_err());
+    }
+
+    #[test]
+    fn circuit_area_counts_shared_logic_once() {
+        let library = Library::parse(LIBRARY).unwrap();
+        let a: RecExpr<crate::prop::Prop> = "(* (! x) y)".parse().unwrap();
+        let b: RecExpr<crate::prop::Prop> = "(+ (! x) z)".parse().unwrap();
+        assert_eq!(circuit_area(&library, &[a.clone(), b]), 1.0 + 2.0 + 3.0);
+        assert_eq!(circuit_delay(&library, &[a]), 7.0 + 10.0);
+    }
+}
@@ -0,0 +1,239 @@
+//! What the netlist readers and writers have in common.
+
+use egg::*;
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+use std::path::Path;
+
+use crate::{aiger, bench, blif, eqn, verilog};
+
+/// A combinational multi-output circuit read from a netlist file.
+///
+/// `exprs[i]` is the cone of `outputs[i]`, kept in DAG form so that logic
+/// feeding several gates or outputs is shared instead of unfolded.
+pub struct Netlist<L> {
+    pub inputs: Vec<String>,
+    pub outputs: Vec<String>,
+    pub exprs: Vec<RecExpr<L>>,
+}
+
+/// Extensions `write_netlist` writes in a format of their own.
+pub const OUTPUT_EXTENSIONS: &[&str] = &["aig", "aag", "blif", "eqn", "v"];
+
+/// Read a netlist, telling the format from the contents: AIGER, bench,
+/// BLIF, eqn, or else a single s-expression with one unnamed output.
+pub fn read_netlist<L: FromOp>(bytes: &[u8]) -> Result<Netlist<L>, String> {
+    if aiger::is_aiger(bytes) {
+        return aiger::parse_aiger(bytes);
+    }
+    let contents = std::str::from_utf8(bytes).map_err(|e| format!("neither AIGER nor text: {}", e))?;
+    if bench::is_bench(contents) {
+        bench::parse_bench(contents)
+    } else if blif::is_blif(contents) {
+        blif::parse_blif(contents)
+    } else if eqn::is_eqn(contents) {
+        eqn::parse_eqn(contents)
+    } else {
+        // a parenthesised term or a single symbol, anything else would read as its first word
+        let text = contents.trim();
+        if !text.starts_with('(') && text.contains(char::is_whitespace) {
+            return Err("not an AIGER, bench, BLIF or eqn netlist, nor an s-expression".to_string());
+        }
+        let expr: RecExpr<L> = text.parse().map_err(|e| format!("not a netlist or an s-expression: {:?}", e))?;
+        Ok(Netlist { inputs: vec![], outputs: vec![], exprs: vec![expr] })
+    }
+}
+
+/// Encode a circuit in the format given by the extension of `path`, see
+/// `OUTPUT_EXTENSIONS`; bench cannot be written. Anything else is written
+/// as text, one `name = expr;` per output, or the bare s-expression if the
+/// outputs have no names. The model is named after the file.
+pub fn write_netlist<L: Language + Display>(path: &Path, inputs: &[String], outputs: &[String], exprs: &[RecExpr<L>]) -> Result<Vec<u8>, String> {
+    let model = path.file_stem().and_then(|s| s.to_str()).unwrap_or("top");
+    Ok(match path.extension().and_then(|e| e.to_str()) {
+        Some(ext @ ("aig" | "aag")) => aiger::write_aiger(inputs, outputs, exprs, ext == "aig")?,
+        Some("blif") => blif::write_blif(model, inputs, outputs, exprs)?.into_bytes(),
+        Some("eqn") => eqn::write_eqn(model, inputs, outputs, exprs)?.into_bytes(),
+        Some("v") => verilog::write_verilog(model, inputs, outputs, exprs)?.into_bytes(),
+        Some("bench") => return Err(format!("cannot write bench, use one of .{}", OUTPUT_EXTENSIONS.join(", ."))),
+        _ if outputs.is_empty() => exprs.first().map(|expr| expr.to_string()).unwrap_or_default().into_bytes(),
+        _ => outputs.iter().zip(exprs).map(|(name, expr)| format!("{} = {};\n", name, expr)).collect::<String>().into_bytes(),
+    })
+}
+
+// ABC's `Abc_NtkAddDummy*Names`: prefix plus the index, padded to the digits of the count
+pub(crate) fn default_names(prefix: &str, count: usize) -> Vec<String> {
+    let mut width = 0;
+    while 10usize.pow(width) < count {
+        width += 1;
+    }
+    (0..count).map(|i| format!("{}{:0w$}", prefix, i, w = width as usize)).collect()
+}
+
+pub(crate) fn is_constant(name: &str) -> bool {
+    matches!(name, "0" | "1" | "true" | "false")
+}
+
+/// `inputs` followed by the other free symbols of `exprs`, sorted.
+pub(crate) fn all_inputs<L: Language + Display>(inputs: &[String], exprs: &[RecExpr<L>]) -> Vec<String> {
+    let mut inputs = inputs.to_vec();
+    let mut seen: HashSet<String> = inputs.iter().cloned().collect();
+    let mut extra = Vec::new();
+    for expr in exprs {
+        for node in expr.as_ref().iter().filter(|n| n.is_leaf()) {
+            let name = node.to_string();
+            if !is_constant(&name) && seen.insert(name.clone()) {
+                extra.push(name);
+            }
+        }
+    }
+    extra.sort();
+    inputs.extend(extra);
+    inputs
+}
+
+/// Build every signal the `outputs` depend on after its fanins.
+///
+/// `defs` maps a signal to its definition, `fanins` lists the signals a
+/// definition reads and `build` adds it to the node table once they are all
+/// in `signals`. Netlists list their gates in any order and the logic of some
+/// benchmarks is thousands of levels deep, so this does not recurse.
+pub(crate) fn build_signals<'a, D>(
+    outputs: &'a [String],
+    defs: &'a HashMap<String, D>,
+    signals: &mut HashMap<String, Id>,
+    fanins: impl Fn(&'a D) -> &'a [String],
+    mut build: impl FnMut(&'a D, &HashMap<String, Id>) -> Result<Id, String>,
+) -> Result<(), String> {
+    let mut in_progress = HashSet::new();
+    for output in outputs {
+        let mut stack = vec![(output.as_str(), false)];
+        while let Some((name, expanded)) = stack.pop() {
+            if signals.contains_key(name) {
+                continue;
+            }
+            let def = defs.get(name).ok_or_else(|| format!("signal `{}` is never defined", name))?;
+            if expanded {
+                let id = build(def, signals)?;
+                signals.insert(name.to_string(), id);
+                in_progress.remove(name);
+                continue;
+            }
+            if !in_progress.insert(name) {
+                return Err(format!("combinational loop through `{}`", name));
+            }
+            stack.push((name, true));
+            for fanin in fanins(def) {
+                if in_progress.contains(fanin.as_str()) {
+                    return Err(format!("combinational loop through `{}`", fanin));
+                }
+                if !signals.contains_key(fanin) {
+                    stack.push((fanin, false));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Hash-consed node table shared by all outputs.
+pub(crate) struct NodeTable<L> {
+    pub nodes: Vec<L>,
+    memo: HashMap<L, Id>,
+}
+
+impl<L: Language> NodeTable<L> {
+    pub fn new() -> Self {
+        NodeTable { nodes: Vec::new(), memo: HashMap::new() }
+    }
+
+    pub fn add_node(&mut self, node: L) -> Id {
+        if let Some(id) = self.memo.get(&node) {
+            return *id;
+        }
+        let id = Id::from(self.nodes.len());
+        self.nodes.push(node.clone());
+        self.memo.insert(node, id);
+        id
+    }
+
+    /// Copy `expr` into the table, returning the id of its root.
+    pub fn add_expr(&mut self, expr: &RecExpr<L>) -> Id {
+        let mut ids: Vec<Id> = Vec::with_capacity(expr.as_ref().len());
+        for node in expr.as_ref() {
+            let id = self.add_node(node.clone().map_children(|c| ids[usize::from(c)]));
+            ids.push(id);
+        }
+        *ids.last().unwrap()
+    }
+
+    // Copy the cone of `root` into its own RecExpr, root last.
+    pub fn cone(&self, root: Id) -> RecExpr<L> {
+        let mut reachable = HashSet::new();
+        let mut stack = vec![root];
+        while let Some(id) = stack.pop() {
+            if reachable.insert(id) {
+                stack.extend(self.nodes[usize::from(id)].children().iter().copied());
+            }
+        }
+        let mut order: Vec<Id> = reachable.into_iter().collect();
+        order.sort();
+        let mut new_ids = HashMap::new();
+        let mut expr = RecExpr::default();
+        for id in order {
+            let node = self.nodes[usize::from(id)].clone().map_children(|c| new_ids[&c]);
+            new_ids.insert(id, expr.add(node));
+        }
+        expr
+    }
+}
+
+impl<L: FromOp> NodeTable<L> {
+    pub fn add(&mut self, op: &str, children: Vec<Id>) -> Result<Id, String> {
+        let node = L::from_op(op, children).map_err(|e| format!("cannot build `{}`: {:?}", op, e))?;
+        Ok(self.add_node(node))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cec;
+    use crate::prop::Prop;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn reads_back_every_format_it_writes() {
+        let c17: Netlist<Prop> = read_netlist(include_bytes!("../../benchmark/converted_circuit/ISCAS85/c17.eqn")).unwrap();
+        for ext in OUTPUT_EXTENSIONS {
+            let path = format!("c17.{}", ext);
+            let bytes = write_netlist(Path::new(&path), &c17.inputs, &c17.outputs, &c17.exprs).unwrap();
+            // Verilog is written only
+            if *ext == "v" {
+                continue;
+            }
+            let read: Netlist<Prop> = read_netlist(&bytes).unwrap_or_else(|e| panic!("{}: {}", path, e));
+            assert_eq!(read.inputs, c17.inputs, "{}", path);
+            assert_eq!(read.outputs, c17.outputs, "{}", path);
+            assert!(cec::check_equivalence(&c17.exprs, &read.exprs, &c17.inputs, &mut StdRng::seed_from_u64(1)).unwrap().is_none(), "{}", path);
+        }
+        let bench = "INPUT(a)\nINPUT(b)\nOUTPUT(y)\ny = NAND(a, b)\n";
+        let read: Netlist<Prop> = read_netlist(bench.as_bytes()).unwrap();
+        assert_eq!(read.exprs[0].to_string(), "(! (* a b))");
+        let read: Netlist<Prop> = read_netlist(b" (+ a b)\n").unwrap();
+        assert!(read.outputs.is_empty());
+        assert_eq!(read.exprs[0].to_string(), "(+ a b)");
+        assert!(read_netlist::<Prop>(b"a b c").is_err());
+    }
+
+    #[test]
+    fn writes_circuits_without_outputs() {
+        for path in ["out.aag", "out.blif", "out.eqn", "out.v", "out.txt"] {
+            assert!(write_netlist::<Prop>(Path::new(path), &[], &[], &[]).is_ok(), "{}", path);
+        }
+        assert!(write_netlist::<Prop>(Path::new("out"), &[], &[], &[]).unwrap().is_empty());
+        let err = write_netlist::<Prop>(Path::new("out.bench"), &[], &[], &[]).unwrap_err();
+        assert!(err.starts_with("cannot write bench"), "{}", err);
+    }
+}
@@ -0,0 +1,895 @@
+//! The optimisation pipeline: equality saturation over a circuit, then
+//! extraction, ranking and an equivalence check of the best results.
+
+use egg::*;
+
+use clap::ValueEnum;
+use num::pow;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rayon::prelude::*;
+
+use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::f64::consts::PI;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+use std::time::{Duration, Instant};
+
+use crate::cec;
+use crate::explain;
+use crate::cli::{BanditArms, CostKind, Extraction, OptimizeArgs, PartitionKind, Scheduler};
+use crate::extract::{self, DagExtractor, GateCost, Policy, RandomExtractor};
+use crate::ilp::IlpExtractor;
+use crate::liberty::{circuit_area, circuit_delay, AreaCost, DelayCost, Library};
+use crate::partition::Partition;
+use crate::proof;
+use crate::prop::{Circuit, ConstantFold, Prop};
+use crate::rules;
+use crate::schedule::{BanditPolicy, BanditScheduler, CostFn, YieldScheduler};
+use crate::stats::Stats;
+use crate::sweep;
+
+pub struct AstSize;
+impl<L: Language + GateCost> CostFunction<L> for AstSize {
+    type Cost = usize;
+    fn cost<C>(&mut self, enode: &L, mut costs: C) -> Self::Cost
+    where
+        C: FnMut(Id) -> Self::Cost,
+    {
+        enode.fold(enode.gates(), |sum, id: Id| sum.saturating_add(costs(id)))
+    }
+}
+
+pub struct AstDepth;
+impl<L: Language + GateCost> CostFunction<L> for AstDepth {
+    type Cost = usize;
+    fn cost<C>(&mut self, enode: &L, mut costs: C) -> Self::Cost
+    where
+        C: FnMut(Id) -> Self::Cost,
+    {
+        enode.levels() + enode.fold(0, |max, id| max.max(costs(id)))
+    }
+}
+
+#[allow(clippy::excessive_precision)]
+pub fn calculate_cost(x1: f64, x2: f64, _x3:f64, x4: f64, _x5: f64,_x6:f64) -> f64 {
+    //let cost =(((((1.4074399975676353 / (x2 + 0.2620202058844679)) * x1) + 147.29219656957378) + x1) - (E.powf(x2 - cube(x1 - 1.4614726807034428)) - (((x1 + 2.8289664189149817) * ((37.56786118979008 + (x5 - (x5 * x2))) + x5)) + (square(x5) + x5)))) + -1.597589119894574;
+    let cost =36.109265171004246*x1 + 1.488470710765137*x1/(x2 + 0.2620202058844679) - (x2 - 3.1031664518592159*((0.68559079218687315*x4 - 1.0).powi(3)).exp()) + 249.43155897921006;
+    //let cost =(7.544539131409524*((3.051915820861467*(0.83030548759182525*x2 + 1.0).powi(6)).powi(2)).cos() + 22.532787907770317)*(x1 - 1.335070352638029*(20.603356550299853*(-x1 - 0.7295426217671495*x2).powi(3)).exp() + 10.175872397596697);
+    cost
+}
+#[allow(dead_code)]
+fn cube(n: f64) -> f64 {
+    pow(n, 3)
+}
+#[allow(dead_code)]
+fn square(n: f64) -> f64 {
+    pow(n, 2)
+}
+
+#[allow(dead_code)]
+fn cos2(n: f64) -> f64 {
+    (2.0 * PI * n).cos().powi(2)
+}
+
+pub fn generate_random_float<R: Rng>(rng: &mut R) -> f64 {
+    let random_float: f64 = rng.gen_range(0.0..0.5);
+    random_float
+}
+// All random decisions take the run's RNG, so a run is reproducible from `--seed`.
+pub trait OrdRandom {
+    fn max_random<R: Rng>(self, other: i32, rng: &mut R) -> i32;
+    fn min_random<R: Rng>(self, other: i32, rng: &mut R) -> i32;
+}
+
+impl OrdRandom for i32 {
+    fn max_random<R: Rng>(self, other: i32, rng: &mut R) -> i32 {
+        match self.cmp(&other) {
+            Ordering::Equal => {
+                if rng.gen::<bool>() {
+                    self
+                } else {
+                    other
+                }
+            }
+            Ordering::Less | Ordering::Greater => {
+                if self < other {
+                    other
+                } else {
+                    self
+                }
+            }
+        }
+    }
+    fn min_random<R: Rng>(self, other: i32, rng: &mut R) -> i32 {
+        match self.cmp(&other) {
+            Ordering::Equal => {
+                if rng.gen::<bool>() {
+                    self
+                } else {
+                    other
+                }
+            }
+            Ordering::Less | Ordering::Greater => {
+                if self < other {
+                    self
+                } else {
+                    other
+                }
+            }
+        }
+    }
+}
+
+pub fn min_random_cmp<T, F, R>(v1: T, v2: T, compare: F, rng: &mut R) -> T
+where
+    F: FnOnce(&T, &T) -> Ordering,
+    R: Rng,
+{
+    match compare(&v1, &v2) {
+        Ordering::Less | Ordering::Equal => {
+            if rng.gen::<bool>() {
+                v1
+            } else {
+                v2
+            }
+        }
+        Ordering::Greater => v2,
+    }
+}
+
+pub trait MyIteratorExt: Iterator {
+    fn min_by_random<F, R>(self, compare: F, rng: &mut R) -> Option<Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item, &Self::Item) -> std::cmp::Ordering,
+        R: Rng;
+}
+
+
+impl<I> MyIteratorExt for I
+where
+    I: Iterator,
+{
+    fn min_by_random<F, R>(self, mut compare: F, rng: &mut R) -> Option<Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item, &Self::Item) -> Ordering,
+        R: Rng,
+    {
+        self.reduce(|x, y| min_random_cmp(x, y, &mut compare, rng))
+    }
+}
+
+#[allow(dead_code)]
+fn cmp<T: PartialOrd>(a: &Option<T>, b: &Option<T>) -> Ordering {
+    // None is high
+    match (a, b) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(a), Some(b)) => a.partial_cmp(b).unwrap(),
+    }
+}
+
+pub struct Mixcost<'a, R: Rng> {
+    pub rng: &'a mut R,
+}
+impl<R: Rng> CostFunction<Prop> for Mixcost<'_, R> {
+    type Cost = i32;
+    fn cost<C>(&mut self, enode: &Prop, mut costs: C) -> Self::Cost
+    where
+        C: FnMut(Id) -> Self::Cost,
+    {
+        let cost_size = enode.fold(enode.gates() as i32, |sum:i32, id: Id| sum.saturating_add(costs(id)));
+        let cost_depth = enode.levels() as i32 + enode.fold(0, |max, id| max.max_random(costs(id), self.rng));
+
+        // You can adjust the weights for size and depth here
+        let weight_size = 0.5;
+        let weight_depth = 0.5;
+
+        let result = (weight_size * cost_size as f64) + (weight_depth * cost_depth as f64);
+        result as i32
+    }
+}
+
+/// How often each operator character occurs in `expr` written out as a
+/// tree, which is what the learned cost model was fitted on. The tree is
+/// counted without unfolding it, shared terms just count once per reader,
+/// and operators the writers expand count as the gates they expand into.
+pub fn count_operators(expr: &RecExpr<Prop>) -> HashMap<String, f64> {
+    let nodes = expr.as_ref();
+    let mut occurrences = vec![0.0; nodes.len()];
+    if let Some(root) = occurrences.last_mut() {
+        *root = 1.0;
+    }
+    let mut operator_counts = HashMap::new();
+    for (i, node) in nodes.iter().enumerate().rev() {
+        if occurrences[i] == 0.0 {
+            continue;
+        }
+        for c in node.children() {
+            occurrences[usize::from(*c)] += occurrences[i];
+        }
+        if let Some((gates, _)) = node.expansion() {
+            for gate in gates {
+                *operator_counts.entry(gate.to_string()).or_insert(0.0) += occurrences[i];
+            }
+            continue;
+        }
+        for c in node.to_string().chars() {
+            if matches!(c, '*' | '!' | '+' | '-' | '>' | '&' | '^') {
+                *operator_counts.entry(c.to_string()).or_insert(0.0) += occurrences[i];
+            }
+        }
+    }
+    operator_counts
+}
+
+pub fn count_ast_size_and_depth(expr: &RecExpr<Prop>) -> (f64, f64) {
+    let size = AstSize.cost_rec(expr) as f64;
+    let depth = AstDepth.cost_rec(expr) as f64;
+    (size, depth)
+}
+
+// Sample a candidate with the randomised extractor: the terms of the roots.
+fn sample_candidate<CF, R>(egraph: &EGraph<Prop, ConstantFold>, roots: &[Id], cost_function: CF, policy: Policy, rng: &mut R) -> Vec<RecExpr<Prop>>
+where
+    CF: CostFunction<Prop>,
+    CF::Cost: extract::CostValue,
+    R: Rng,
+{
+    let extractor = RandomExtractor::new(egraph, cost_function, policy, rng);
+    roots.iter().map(|root| extractor.find_best(*root)).collect()
+}
+
+/// The tree extraction cost of `cost` over the outputs, the sum of theirs or
+/// for depth and delay the largest, which rewards the bandit scheduler.
+fn extraction_cost(cost: CostKind, library: Option<&Library>, seed: u64) -> CostFn {
+    fn total<CF: CostFunction<Prop>>(egraph: &EGraph<Prop, ConstantFold>, roots: &[Id], cost_function: CF, value: fn(CF::Cost) -> f64, max: bool) -> f64 {
+        let extractor = Extractor::new(egraph, cost_function);
+        let costs = roots.iter().map(|root| value(extractor.find_best_cost(*root)));
+        if max { costs.fold(0.0, f64::max) } else { costs.sum() }
+    }
+    match cost {
+        CostKind::AstDepth => Box::new(|egraph, roots| total(egraph, roots, AstDepth, |c| c as f64, true)),
+        CostKind::AstSize => Box::new(|egraph, roots| total(egraph, roots, AstSize, |c| c as f64, false)),
+        CostKind::Mixcost => {
+            let mut rng = StdRng::seed_from_u64(seed);
+            Box::new(move |egraph, roots| total(egraph, roots, Mixcost { rng: &mut rng }, |c| c as f64, false))
+        }
+        CostKind::Area => {
+            let library = library.unwrap().clone();
+            Box::new(move |egraph, roots| total(egraph, roots, AreaCost { library: &library }, |c| c, false))
+        }
+        CostKind::Delay => {
+            let library = library.unwrap().clone();
+            Box::new(move |egraph, roots| total(egraph, roots, DelayCost { library: &library }, |c| c, true))
+        }
+    }
+}
+
+/// The cost `run` ranks its results by: the learned `calculate_cost` of the
+/// operator counts, size and depth, or with a library the cell area or
+/// delay for those costs.
+pub fn final_cost(bests: &[RecExpr<Prop>], library: Option<&Library>, cost: CostKind) -> f64 {
+    let mut operator_counts: HashMap<String, f64> = HashMap::new();
+    let mut size = 0.0;
+    let mut depth: f64 = 0.0;
+    for best in bests {
+        let (root_size, root_depth) = count_ast_size_and_depth(best);
+        size += root_size;
+        depth = depth.max(root_depth);
+        for (op, count) in count_operators(best) {
+            *operator_counts.entry(op).or_insert(0.0) += count;
+        }
+    }
+    let x1 = operator_counts.get("+").copied().unwrap_or(0.0);
+    let x2 = operator_counts.get("!").copied().unwrap_or(0.0);
+    let x3 = operator_counts.get("*").copied().unwrap_or(0.0);
+    let x4 = operator_counts.get("&").copied().unwrap_or(0.0);
+       // println!("+:{},!:{},*:{},&:{},astsize:{},astdepth:{}",x1,x2,x3,x4,size,depth);
+
+    fn mean(data: &[f64]) -> f64 {
+        data.iter().sum::<f64>() / data.len() as f64
+    }
+
+    fn std_dev(data: &[f64], mean: f64) -> f64 {
+        let variance = data.iter().map(|&x| (x - mean).powi(2)).sum::<f64>() / data.len() as f64;
+        variance.sqrt()
+    }
+
+    // all features equal, e.g. without outputs, are all average
+    fn standardize(data: &[f64], mean: f64, std_dev: f64) -> Vec<f64> {
+        if std_dev == 0.0 {
+            return vec![0.0; data.len()];
+        }
+        data.iter().map(|&x| (x - mean) / std_dev).collect()
+    }
+    let x = vec![x1, x2, x3, x4, size, depth];
+
+    let mean = mean(&x);
+    let std_dev = std_dev(&x, mean);
+    let scaled_data_vec = standardize(&x, mean, std_dev);
+
+
+    let x1_new =scaled_data_vec[0];
+    let x2_new =scaled_data_vec[1];
+    let x3_new =scaled_data_vec[2];
+    let x4_new =scaled_data_vec[3];
+    let size_new =scaled_data_vec[4];
+    let depth_new =scaled_data_vec[5];
+    //println!("+:{},!:{},*:{},&:{},astsize:{},astdepth:{}",x1_new,x2_new,x3_new,x4_new,size_new,depth_new);
+
+    let sym_cost = calculate_cost(x1_new,x2_new,x3_new,x4_new,size_new,depth_new);
+
+
+
+    // with a library, rank by what the library says instead of the learned model
+    match (library, cost) {
+        (Some(library), CostKind::Area) => circuit_area(library, bests),
+        (Some(library), CostKind::Delay) => circuit_delay(library, bests),
+        _ => sym_cost,
+    }
+}
+
+/// Saturate the outputs `exprs` in one e-graph with the rules, within the
+/// runner limits of `args` and the `deadline`, and log how it went.
+fn saturate(args: &OptimizeArgs, rules: &[Rewrite<Prop, ConstantFold>], library: Option<&Library>, deadline: Option<Instant>, rng: &mut StdRng, exprs: &[RecExpr<Prop>]) -> Result<Runner<Prop, ConstantFold>, String> {
+    let start = Instant::now();
+    let time_limit = Duration::from_secs(args.time_limit);
+    let time_limit = deadline.map_or(time_limit, |d| time_limit.min(d.saturating_duration_since(start)));
+    // one root per output, so the outputs share e-classes
+    let analysis = ConstantFold { sim_seed: rng.gen() };
+    let mut runner = exprs
+        .iter()
+        .fold(Runner::<Prop, ConstantFold>::new(analysis).with_explanations_enabled(), |runner, expr| runner.with_expr(expr))
+        .with_time_limit(time_limit)
+        .with_iter_limit(args.iter_limit)
+        .with_node_limit(args.node_limit);
+    let (mut schedule, mut bandit) = (None, None);
+    match args.scheduler {
+        Scheduler::Yield => {
+            let scheduler = YieldScheduler::new(&runner.roots);
+            schedule = Some(scheduler.stats());
+            runner = runner.with_scheduler(scheduler);
+        }
+        Scheduler::Backoff => {}
+        Scheduler::Bandit => {
+            let policy = match &args.bandit_policy {
+                Some(path) if Path::new(path).exists() => {
+                    let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+                    BanditPolicy::load(&text).map_err(|e| format!("{}: {}", path, e))?
+                }
+                _ => BanditPolicy::default(),
+            };
+            let cost = extraction_cost(args.cost, library, rng.gen());
+            let scheduler = BanditScheduler::new(&runner.roots, rules, args.bandit_arms == BanditArms::Group, cost, policy);
+            bandit = Some(scheduler.policy());
+            runner = runner.with_scheduler(scheduler);
+        }
+    }
+    if !args.no_sweep {
+        // merge simulation-equivalent classes before every iteration
+        let mut sweeper = sweep::Sweeper::default();
+        let sweep_deadline = start + time_limit;
+        runner = runner.with_hook(move |runner| {
+            let merged = sweeper.sweep(&mut runner.egraph, sweep_deadline)?;
+            if merged > 0 {
+                log!(2, "sweep: {} merges (total {})", merged, sweeper.merged);
+            }
+            Ok(())
+        });
+    }
+    let runner = runner.run(rules.iter());
+    let duration = start.elapsed();
+    if crate::verbosity() >= 2 {
+        runner.print_report();
+    }
+    log!(1, "Runner stopped: {:?}. Time take for runner: {:?}, Classes: {}, Nodes: {}, Size: {}\n\n",
+            runner.stop_reason, duration, runner.egraph.number_of_classes(),
+            runner.egraph.total_number_of_nodes(), runner.egraph.total_size());
+    if let Some(stats) = &schedule {
+        let stats = stats.borrow();
+        log!(1, "{}", stats.summary());
+        log!(2, "{}", stats);
+    }
+    if let Some(policy) = &bandit {
+        let policy = policy.borrow();
+        log!(1, "{}", policy.summary());
+        log!(2, "{}", policy);
+        if let Some(path) = &args.bandit_policy {
+            std::fs::write(path, policy.save()).map_err(|e| format!("{}: {}", path, e))?;
+        }
+    }
+    Ok(runner)
+}
+
+// Cost of a round of `--reseed`: the cell area or delay for those costs, the
+// number of distinct nodes otherwise.
+fn round_cost(cost: CostKind, library: Option<&Library>, exprs: &[RecExpr<Prop>]) -> f64 {
+    match (library, cost) {
+        (Some(library), CostKind::Area) => circuit_area(library, exprs),
+        (Some(library), CostKind::Delay) => circuit_delay(library, exprs),
+        _ => Stats::new(&[], exprs, None).nodes as f64,
+    }
+}
+
+/// Saturate, extract the smallest DAG and start again from it alone, until
+/// `--reseed` rounds in a row bring no improvement, `--max-rounds` is
+/// reached or the `deadline` has passed. Returns the best DAG.
+fn reseed(args: &OptimizeArgs, rules: &[Rewrite<Prop, ConstantFold>], library: Option<&Library>, deadline: Option<Instant>, rng: &mut StdRng, exprs: Vec<RecExpr<Prop>>) -> Result<Vec<RecExpr<Prop>>, String> {
+    let mut best_cost = round_cost(args.cost, library, &exprs);
+    let mut best = exprs;
+    let mut stale = 0;
+    log!(1, "round 0: cost {}", best_cost);
+    for round in 1..=args.max_rounds {
+        let start = Instant::now();
+        let runner = saturate(args, rules, library, deadline, rng, &best)?;
+        let extractor = DagExtractor::new(&runner.egraph, &runner.roots, 10);
+        let bests: Vec<RecExpr<Prop>> = runner.roots.iter().map(|root| extractor.find_best(*root)).collect();
+        let cost = round_cost(args.cost, library, &bests);
+        if cost < best_cost {
+            (best, best_cost, stale) = (bests, cost, 0);
+        } else {
+            stale += 1;
+        }
+        log!(1, "round {}: cost {}, best {}, time: {:?}", round, cost, best_cost, start.elapsed());
+        if stale >= args.reseed || deadline.is_some_and(|d| Instant::now() >= d) {
+            break;
+        }
+    }
+    Ok(best)
+}
+
+/// A rule set, cell library and random generator, set up once and used for
+/// any number of circuits.
+pub struct Optimizer {
+    args: OptimizeArgs,
+    rules: Vec<Rewrite<Prop, ConstantFold>>,
+    library: Option<Library>,
+    // the only source of randomness for the whole run
+    rng: StdRng,
+    // when the runners and the sampling stop at the latest, shared by the configurations of a portfolio
+    deadline: Option<Instant>,
+    // false when the caller checks the results it keeps itself
+    check: bool,
+}
+
+impl Optimizer {
+    pub fn new(args: &OptimizeArgs) -> Result<Self, String> {
+        if args.extraction == Extraction::Ilp && !matches!(args.cost, CostKind::AstSize | CostKind::Area) {
+            return Err(format!("the ILP extractor minimises a sum of node costs, use --cost ast-size or area rather than {}", cost_name(args.cost)));
+        }
+        let seed: u64 = args.seed.unwrap_or_else(rand::random);
+        let specs = args.rules.load()?;
+        let enabled_tags: HashSet<String> = args.rules.enable.iter().cloned().collect();
+        rules::reject_unsound(&specs, &enabled_tags)?;
+        let rules = rules::make_rewrites(&specs, &enabled_tags)?;
+        log!(1, "rules: {} ({} rewrites)", args.rules.rules.as_deref().unwrap_or(&args.rules.ruleset), rules.len());
+        let rng = StdRng::seed_from_u64(seed);
+        log!(1, "seed: {}", seed);
+        let library = match &args.liberty {
+            Some(path) => {
+                let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+                let library = Library::parse(&text)?;
+                log!(1, "liberty: {}", library.name);
+                for (op, cost) in library.ops() {
+                    log!(2, "  {:>4}  area {} ({}), delay {} ({})", op, cost.area, cost.area_cell, cost.delay, cost.delay_cell);
+                }
+                Some(library)
+            }
+            None => None,
+        };
+        Ok(Optimizer { args: args.clone(), rules, library, rng, deadline: None, check: true })
+    }
+
+    pub fn library(&self) -> Option<&Library> {
+        self.library.as_ref()
+    }
+
+    /// The `--top-k` best results for `circuit`, best first, each one
+    /// checked to be equivalent to it.
+    pub fn run(&mut self, circuit: &Circuit) -> Result<Vec<Vec<RecExpr<Prop>>>, String> {
+        if !self.args.portfolio_rules.is_empty() || !self.args.portfolio_costs.is_empty() {
+            return self.run_portfolio(circuit);
+        }
+        if let Some(kind) = self.args.partition {
+            return self.run_partitioned(circuit, kind);
+        }
+        let Optimizer { args, rules, library, rng, deadline, check } = self;
+        let deadline = *deadline;
+        let mut egraphin = EGraph::new(ConstantFold::default());
+        for expr in &circuit.exprs {
+            egraphin.add_expr(expr);
+        }
+        //egraphin.dot().to_png("./image/fooin.png").unwrap();
+        log!(1, "input node: {}", egraphin.total_size());
+        log!(1, "input class: {}", egraphin.number_of_classes());
+        if !circuit.outputs.is_empty() {
+            log!(1, "inputs: {}, outputs: {}", circuit.inputs.len(), circuit.outputs.len());
+        }
+
+        let mut seed = circuit.exprs.clone();
+        if args.reseed > 0 {
+            seed = reseed(args, rules, library.as_ref(), deadline, rng, seed)?;
+        }
+        let mut runner = saturate(args, rules, library.as_ref(), deadline, rng, &seed)?;
+
+        let extractor = Extractor::new(&runner.egraph, AstDepth);
+        let mut egraphout = EGraph::new(ConstantFold::default());
+        for root in &runner.roots {
+            let (_, best) = extractor.find_best(*root);
+            egraphout.add_expr(&best);
+        }
+        log!(1, "output node:{}", egraphout.total_size());
+        log!(1, "output class:{}", egraphout.number_of_classes());
+        //egraphout.dot().to_png("./image/fooout.png").unwrap();
+        //let result = best.to_string();
+
+
+        //let mut unique_solutions = HashSet::new();
+        let mut results: HashMap<i32, Vec<RecExpr<Prop>>> = HashMap::new();
+
+
+        match args.extraction {
+            Extraction::Dag => {
+                let start = Instant::now();
+                let extractor = DagExtractor::new(&runner.egraph, &runner.roots, 10);
+                let bests = runner.roots.iter().map(|root| extractor.find_best(*root)).collect();
+                log!(1, "DAG extraction: {} nodes, time: {:?}", extractor.dag_size(), start.elapsed());
+                results.insert(0, bests);
+            }
+            Extraction::Ilp => {
+                let start = Instant::now();
+                let node_cost = |node: &Prop| match args.cost {
+                    CostKind::Area => library.as_ref().unwrap().area(&node.to_string()),
+                    _ => AstSize.cost(node, |_| 0) as f64,
+                };
+                let ilp_time_limit = Duration::from_secs(args.ilp_time_limit);
+                let ilp_time_limit = deadline.map_or(ilp_time_limit, |d| ilp_time_limit.min(d.saturating_duration_since(Instant::now())));
+                let extractor = IlpExtractor::new(&runner.egraph, &runner.roots, node_cost, ilp_time_limit);
+                let bests = runner.roots.iter().map(|root| extractor.find_best(*root)).collect();
+                let gap = if extractor.cost() > 0.0 { 100.0 * (extractor.cost() - extractor.lower_bound) / extractor.cost() } else { 0.0 };
+                log!(1, "ILP extraction: greedy cost {}, ILP cost {} ({}), lower bound {}, gap {:.2}%, time: {:?}",
+                        extractor.initial_cost, extractor.cost(),
+                        if extractor.optimal { "optimal" } else { "time limit" },
+                        extractor.lower_bound, gap, start.elapsed());
+                results.insert(0, bests);
+            }
+            Extraction::Tree => {
+                // Mixcost draws its own tie-breaks, from a generator seeded by the run's
+                let mut cost_rng = StdRng::seed_from_u64(rng.gen());
+                // distinct candidates from the randomised extractor, keyed by structural hash
+                let mut seen = HashSet::new();
+                for i in 0..args.candidates as i32 {
+                    if i > 0 && deadline.is_some_and(|d| Instant::now() >= d) {
+                        break;
+                    }
+                    let (egraph, roots, policy) = (&runner.egraph, &runner.roots, args.policy);
+                    let bests = match args.cost {
+                        CostKind::AstDepth => sample_candidate(egraph, roots, AstDepth, policy, &mut *rng),
+                        CostKind::AstSize => sample_candidate(egraph, roots, AstSize, policy, &mut *rng),
+                        CostKind::Mixcost => sample_candidate(egraph, roots, Mixcost { rng: &mut cost_rng }, policy, &mut *rng),
+                        CostKind::Area => {
+                            let library = library.as_ref().unwrap();
+                            sample_candidate(egraph, roots, AreaCost { library }, policy, &mut *rng)
+                        }
+                        CostKind::Delay => {
+                            let library = library.as_ref().unwrap();
+                            sample_candidate(egraph, roots, DelayCost { library }, policy, &mut *rng)
+                        }
+                    };
+                    let mut hasher = DefaultHasher::new();
+                    bests.hash(&mut hasher);
+                    if !seen.insert(hasher.finish()) {
+                        continue;
+                    }
+                    results.insert(i, bests);
+                }
+                log!(1, "distinct candidates: {} of {}", results.len(), args.candidates);
+            }
+        }
+        // extraction from the last e-graph can come out worse than the DAG it was seeded with
+        if args.reseed > 0 {
+            results.insert(-1, seed);
+        }
+
+        // with --reseed the results compete with its best DAG on the cost the rounds were judged by
+        let mut sym_cost_dict: HashMap<i32, f64> = HashMap::new();
+        for (key, bests) in &results {
+            let cost = if args.reseed > 0 { round_cost(args.cost, library.as_ref(), bests) } else { final_cost(bests, library.as_ref(), args.cost) };
+            sym_cost_dict.insert(*key, cost);
+        }
+        // for(key,value)in &sym_cost_dict{
+        //     println!("Inserted key: {}, value: {}", key, value);
+        // }
+        //let mut min_value = f64::INFINITY;
+        //let mut min_key = 0; 
+
+        let mut key_value_pairs: Vec<(&i32, &f64)> = sym_cost_dict.iter().collect();
+        key_value_pairs.sort_by(|&(key1, value1), &(key2, value2)| value1.total_cmp(value2).then(key1.cmp(key2)));
+        //let Some((min_key, min_value)) = key_value_pairs.first() else { todo!() };
+        let min_keys: Vec<i32> = key_value_pairs.iter().take(args.top_k).map(|&(key, _)| *key).collect();
+
+        // nothing reaches the output files without passing CEC against the input
+        if *check {
+            for min_key in &min_keys {
+                cec::check_result(circuit, &results[min_key], &mut *rng)?;
+            }
+        }
+        let best = min_keys.first().map(|key| &results[key]);
+        if let (true, Some(best)) = (args.explain.is_some() || args.proof.is_some(), best) {
+            let start = Instant::now();
+            let names: Vec<String> = (0..best.len()).map(|i| circuit.outputs.get(i).cloned().unwrap_or_else(|| format!("output {}", i))).collect();
+            let explanations: Vec<_> = circuit.exprs.iter().zip(best).map(|(input, output)| runner.explain_equivalence(input, output)).collect();
+            if let Some(path) = &args.explain {
+                let flat: Vec<_> = names.iter().zip(&explanations).map(|(name, e)| explain::flatten(name, e)).collect();
+                std::fs::write(path, explain::write_explanations(Path::new(path), &flat)).map_err(|e| format!("{}: {}", path, e))?;
+                let steps: usize = flat.iter().map(|e| e.steps.len()).sum();
+                log!(1, "explanation: {} rewrites, written to {}", steps, path);
+            }
+            if let Some(path) = &args.proof {
+                std::fs::write(path, proof::write_proof(&names, &explanations)).map_err(|e| format!("{}: {}", path, e))?;
+                log!(1, "proof written to {}", path);
+            }
+            log!(1, "explaining took {:?}", start.elapsed());
+        }
+        Ok(min_keys.iter().map(|key| results.remove(key).unwrap()).collect())
+    }
+
+    // `run` on each window of `--partition` in parallel, keeping the best
+    // result of each unless it is larger than the window was, stitched back
+    // together and checked as a whole. The windows share `--time-limit`,
+    // each runner getting its share of what is left when it starts.
+    fn run_partitioned(&mut self, circuit: &Circuit, kind: PartitionKind) -> Result<Vec<Vec<RecExpr<Prop>>>, String> {
+        let start = Instant::now();
+        let deadline = start + Duration::from_secs(self.args.time_limit);
+        let deadline = self.deadline.map_or(deadline, |d| d.min(deadline));
+        let partition = Partition::new(circuit, kind, self.args.window_inputs, self.args.window_nodes);
+        let largest = partition.windows.iter().map(|w| w.nodes).max().unwrap_or(0);
+        log!(1, "partition: {} windows, largest {} gates, time: {:?}", partition.windows.len(), largest, start.elapsed());
+
+        // the windows would all write the bandit policy at once; a tree
+        // extracted per window would duplicate what the window's outputs share
+        let extraction = if self.args.extraction == Extraction::Tree { Extraction::Dag } else { self.args.extraction };
+        let args = OptimizeArgs { partition: None, top_k: 1, bandit_policy: None, extraction, ..self.args.clone() };
+        // drawn up front, so that the results do not depend on the thread schedule
+        let seeds: Vec<u64> = partition.windows.iter().map(|_| self.rng.gen()).collect();
+        let specs = args.rules.load()?;
+        let enabled_tags: HashSet<String> = args.rules.enable.iter().cloned().collect();
+        let library = &self.library;
+        let waiting = AtomicUsize::new(partition.windows.iter().filter(|w| w.nodes >= 2).count());
+        let threads = rayon::current_num_threads() as u32;
+        // the windows' own reports interleave, so only show them when asked for more
+        let level = crate::verbosity();
+        crate::set_verbosity(level.saturating_sub(1));
+        let results: Result<Vec<_>, String> = partition
+            .windows
+            .par_iter()
+            .zip(seeds)
+            .enumerate()
+            .map(|(i, (window, seed))| {
+                let start = Instant::now();
+                // a single gate has nothing to rewrite
+                if window.nodes < 2 {
+                    return Ok((window.circuit.exprs.clone(), start.elapsed()));
+                }
+                let waiting = waiting.fetch_sub(1, AtomicOrdering::Relaxed) as u32;
+                let left = deadline.saturating_duration_since(start);
+                if left.is_zero() {
+                    return Ok((window.circuit.exprs.clone(), start.elapsed()));
+                }
+                let share = start + (left * threads / waiting).min(left);
+                // egg's rewrites do not clone, each window builds its own
+                let rules = rules::make_rewrites(&specs, &enabled_tags)?;
+                let mut optimizer = Optimizer { args: args.clone(), rules, library: library.clone(), rng: StdRng::seed_from_u64(seed), deadline: Some(share), check: true };
+                let best = optimizer.run(&window.circuit).map_err(|e| format!("window {}: {}", i, e))?.swap_remove(0);
+                let before = round_cost(args.cost, library.as_ref(), &window.circuit.exprs);
+                if round_cost(args.cost, library.as_ref(), &best) > before {
+                    return Ok((window.circuit.exprs.clone(), start.elapsed()));
+                }
+                Ok((best, start.elapsed()))
+            })
+            .collect();
+        crate::set_verbosity(level);
+        let results = results?;
+        for (i, (window, (best, time))) in partition.windows.iter().zip(&results).enumerate() {
+            let after = Stats::new(&[], best, None).nodes;
+            log!(2, "window {}: {} inputs, {} outputs, {} -> {} gates, time: {:?}", i, window.circuit.inputs.len(), window.circuit.outputs.len(), window.nodes, after, time);
+        }
+        let results: Vec<Vec<RecExpr<Prop>>> = results.into_iter().map(|(best, _)| best).collect();
+
+        let exprs = partition.stitch(&results);
+        log!(1, "stitched: {} -> {} nodes, time: {:?}", Stats::new(&[], &circuit.exprs, None).nodes, Stats::new(&[], &exprs, None).nodes, start.elapsed());
+        cec::check_result(circuit, &exprs, &mut self.rng)?;
+        Ok(vec![exprs])
+    }
+
+    // `run` for each pair of `--portfolio-rules` and `--portfolio-costs`, each
+    // on a thread and an e-graph of its own, all stopping their runners and
+    // sampling at `--time-limit` from now. The results of all configurations
+    // are ranked together by the gates of their DAG, or with a library by
+    // their delay for `--cost delay` and their area otherwise. Only the
+    // results kept are checked, which is all that runs past the deadline.
+    fn run_portfolio(&mut self, circuit: &Circuit) -> Result<Vec<Vec<RecExpr<Prop>>>, String> {
+        let start = Instant::now();
+        let deadline = start + Duration::from_secs(self.args.time_limit);
+        let base = &self.args;
+        let rule_sets: Vec<Option<&String>> = if base.portfolio_rules.is_empty() { vec![None] } else { base.portfolio_rules.iter().map(Some).collect() };
+        let costs = if base.portfolio_costs.is_empty() { vec![base.cost] } else { base.portfolio_costs.clone() };
+        let mut configs = Vec::new();
+        for rule_set in &rule_sets {
+            for &cost in &costs {
+                if matches!(cost, CostKind::Area | CostKind::Delay) && self.library.is_none() {
+                    return Err(format!("the {} cost of the portfolio needs --liberty", cost_name(cost)));
+                }
+                let mut args = OptimizeArgs {
+                    portfolio_rules: Vec::new(),
+                    portfolio_costs: Vec::new(),
+                    cost,
+                    seed: Some(self.rng.gen()),
+                    // the configurations would all write the bandit policy at once
+                    bandit_policy: None,
+                    ..base.clone()
+                };
+                let rules = match rule_set {
+                    Some(name) if rules::BUILTIN.iter().any(|(builtin, _)| builtin == name) => {
+                        (args.rules.ruleset, args.rules.rules) = (name.to_string(), None);
+                        name.as_str()
+                    }
+                    Some(path) => {
+                        args.rules.rules = Some(path.to_string());
+                        path.as_str()
+                    }
+                    None => base.rules.rules.as_deref().unwrap_or(&base.rules.ruleset),
+                };
+                configs.push((format!("{} {}", rules, cost_name(cost)), args));
+            }
+        }
+        log!(1, "portfolio: {} configurations, time limit: {}s", configs.len(), base.time_limit);
+
+        // the configurations' own reports interleave, so only show them when asked for more
+        let level = crate::verbosity();
+        crate::set_verbosity(level.saturating_sub(1));
+        let outcomes: Vec<_> = std::thread::scope(|scope| {
+            let threads: Vec<_> = configs
+                .iter()
+                .map(|(_, args)| {
+                    scope.spawn(move || -> Result<_, String> {
+                        let start = Instant::now();
+                        let mut optimizer = Optimizer::new(args)?;
+                        optimizer.deadline = Some(deadline);
+                        optimizer.check = false;
+                        Ok((optimizer.run(circuit)?, start.elapsed()))
+                    })
+                })
+                .collect();
+            threads.into_iter().map(|thread| thread.join().unwrap_or_else(|_| Err("panicked".to_string()))).collect()
+        });
+        crate::set_verbosity(level);
+
+        let metric = |result: &[RecExpr<Prop>]| match (self.library.as_ref(), base.cost) {
+            (Some(library), CostKind::Delay) => circuit_delay(library, result),
+            (Some(library), _) => circuit_area(library, result),
+            (None, _) => Stats::new(&[], result, None).nodes as f64,
+        };
+        // (cost, configuration, result) over the results of every configuration
+        let mut ranked = Vec::new();
+        let mut rows = Vec::new();
+        for (i, ((name, _), outcome)) in configs.iter().zip(outcomes).enumerate() {
+            match outcome {
+                Ok((results, time)) => {
+                    let costs: Vec<f64> = results.iter().map(|r| metric(r)).collect();
+                    let best = costs.iter().copied().fold(f64::INFINITY, f64::min);
+                    let stats = results.iter().zip(&costs).find(|(_, c)| **c == best).map(|(r, _)| Stats::new(&[], r, None));
+                    rows.push((name, stats.map(|s| (s.nodes, s.depth, best)), format!("{:.2?}", time)));
+                    ranked.extend(costs.into_iter().zip(results).map(|(cost, result)| (cost, i, result)));
+                }
+                Err(e) => rows.push((name, None, e)),
+            }
+        }
+        ranked.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+        let winner = ranked.first().map(|(_, i, _)| *i);
+        log!(1, "  {:<28}  {:>7}  {:>6}  {:>12}  time", "configuration", "nodes", "depth", "cost");
+        for (i, (name, stats, time)) in rows.iter().enumerate() {
+            let mark = if winner == Some(i) { "*" } else { " " };
+            match stats {
+                Some((nodes, depth, cost)) => log!(1, "{} {:<28}  {:>7}  {:>6}  {:>12.3}  {}", mark, name, nodes, depth, cost, time),
+                None => log!(1, "{} {:<28}  failed: {}", mark, name, time),
+            }
+        }
+        if ranked.is_empty() {
+            return Err("every configuration of the portfolio failed".to_string());
+        }
+        let kept: Vec<Vec<RecExpr<Prop>>> = ranked.into_iter().take(base.top_k).map(|(_, _, result)| result).collect();
+        for result in &kept {
+            cec::check_result(circuit, result, &mut self.rng)?;
+        }
+        log!(1, "portfolio took {:?}", start.elapsed());
+        Ok(kept)
+    }
+}
+
+fn cost_name(cost: CostKind) -> String {
+    cost.to_possible_value().map_or_else(|| format!("{:?}", cost), |v| v.get_name().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eqn::parse_eqn;
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct Cli {
+        #[command(flatten)]
+        optimize: OptimizeArgs,
+    }
+
+    fn args(flags: &[&str]) -> OptimizeArgs {
+        let base = ["esyn", "--seed", "1", "--iter-limit", "3", "--time-limit", "20", "--candidates", "8"];
+        Cli::parse_from(base.iter().chain(flags)).optimize
+    }
+
+    #[test]
+    fn final_cost_is_a_number_when_the_features_are_equal() {
+        assert!(final_cost(&[], None, CostKind::AstSize).is_finite());
+        let bests: Vec<RecExpr<Prop>> = vec!["(+ (* a (! b)) (& a b))".parse().unwrap()];
+        assert!(final_cost(&bests, None, CostKind::AstSize).is_finite());
+    }
+
+    #[test]
+    fn operators_cost_their_expansion() {
+        let cost = |e: &str| {
+            let expr: RecExpr<Prop> = e.parse().unwrap();
+            (AstSize.cost_rec(&expr), AstDepth.cost_rec(&expr))
+        };
+        // leaves count 1 each
+        assert_eq!(cost("(^ a b)"), (7, 4));
+        assert_eq!(cost("(mux s a b)"), (7, 4));
+        assert_eq!(cost("(maj a b c)"), (7, 4));
+    }
+
+    #[test]
+    fn partitioned_windows_never_grow() {
+        let c17 = parse_eqn(include_str!("../../benchmark/converted_circuit/ISCAS85/c17.eqn")).unwrap();
+        let before = Stats::new(&[], &c17.exprs, None).nodes;
+        for kind in ["cone", "mffc"] {
+            // tree extraction per window would duplicate the logic its outputs share
+            let args = args(&["--partition", kind, "--window-nodes", "4", "--extraction", "tree"]);
+            let results = Optimizer::new(&args).unwrap().run(&c17).unwrap();
+            assert_eq!(results.len(), 1);
+            assert!(Stats::new(&[], &results[0], None).nodes <= before);
+        }
+    }
+
+    #[test]
+    fn reseeded_results_are_never_larger_than_the_input() {
+        let c17 = parse_eqn(include_str!("../../benchmark/converted_circuit/ISCAS85/c17.eqn")).unwrap();
+        let before = round_cost(CostKind::AstSize, None, &c17.exprs);
+        // ranked on the round cost, not the depth the runs extract by
+        let args = args(&["--reseed", "1", "--max-rounds", "2", "--cost", "ast-depth", "--top-k", "3"]);
+        let results = Optimizer::new(&args).unwrap().run(&c17).unwrap();
+        let costs: Vec<f64> = results.iter().map(|r| round_cost(args.cost, None, r)).collect();
+        assert!(costs.windows(2).all(|w| w[0] <= w[1]), "{:?}", costs);
+        assert!(Stats::new(&[], &results[0], None).nodes as f64 <= before);
+    }
+
+    #[test]
+    fn portfolio_ranks_every_result_by_its_gates() {
+        let c17 = parse_eqn(include_str!("../../benchmark/converted_circuit/ISCAS85/c17.eqn")).unwrap();
+        let args = args(&["--portfolio-costs", "ast-size,ast-depth", "--top-k", "4"]);
+        let results = Optimizer::new(&args).unwrap().run(&c17).unwrap();
+        assert!(!results.is_empty() && results.len() <= 4);
+        let gates: Vec<usize> = results.iter().map(|r| Stats::new(&[], r, None).nodes).collect();
+        assert!(gates.windows(2).all(|w| w[0] <= w[1]), "{:?}", gates);
+        let mut rng = StdRng::seed_from_u64(1);
+        for result in &results {
+            assert!(cec::check_equivalence(&c17.exprs, result, &c17.inputs, &mut rng).unwrap().is_none());
+        }
+    }
+}
@@ -0,0 +1,299 @@
+//! Windows of a large circuit that are rewritten on their own.
+//!
+//! Cone windows cut the gates, in the order the output cones were read, into
+//! runs that stay within the node and input caps, so that each window only
+//! reads the windows before it. MFFC windows grow from a root gate towards
+//! the inputs, taking in a fanin only once all its readers are in the
+//! window, so that the root is their only output; the fanins a window leaves
+//! out root windows of their own, and a window only reads those after it.
+//! Either way the windows do not read each other in a cycle, and the
+//! optimised windows are stitched back in the order they read each other.
+
+use egg::*;
+
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use crate::cli::PartitionKind;
+use crate::netlist::{all_inputs, NodeTable};
+use crate::prop::{Circuit, Prop};
+
+/// A window as a circuit of its own: its inputs are circuit inputs and
+/// the signals of other windows, its outputs the gates read outside it.
+pub struct Window {
+    pub circuit: Circuit,
+    /// Gates in the window.
+    pub nodes: usize,
+}
+
+pub struct Partition {
+    pub windows: Vec<Window>,
+    // the signal, or the leaf, that drives each output of the circuit
+    outputs: Vec<Result<String, Prop>>,
+}
+
+impl Partition {
+    pub fn new(circuit: &Circuit, kind: PartitionKind, max_inputs: usize, max_nodes: usize) -> Self {
+        let mut table = NodeTable::new();
+        let roots: Vec<Id> = circuit.exprs.iter().map(|expr| table.add_expr(expr)).collect();
+        let nodes = &table.nodes;
+        let fanins = |id: usize| -> Vec<usize> {
+            let mut fanins: Vec<usize> = nodes[id].children().iter().map(|c| usize::from(*c)).collect();
+            fanins.sort();
+            fanins.dedup();
+            fanins
+        };
+        let mut fanouts: Vec<Vec<usize>> = vec![Vec::new(); nodes.len()];
+        for id in 0..nodes.len() {
+            for fanin in fanins(id) {
+                fanouts[fanin].push(id);
+            }
+        }
+
+        let is_gate = |id: usize| !nodes[id].is_leaf();
+        let is_root: HashSet<usize> = roots.iter().map(|r| usize::from(*r)).collect();
+        let mut window_of: Vec<Option<usize>> = vec![None; nodes.len()];
+        let mut members: Vec<Vec<usize>> = Vec::new();
+        match kind {
+            PartitionKind::Cone => {
+                let mut gates = Vec::new();
+                let mut inputs: HashSet<usize> = HashSet::new();
+                for id in (0..nodes.len()).filter(|id| is_gate(*id)) {
+                    let w = members.len();
+                    let new_inputs = fanins(id).into_iter().filter(|f| window_of[*f] != Some(w) && !inputs.contains(f)).count();
+                    if !gates.is_empty() && (gates.len() >= max_nodes || inputs.len() + new_inputs > max_inputs) {
+                        members.push(std::mem::take(&mut gates));
+                        inputs.clear();
+                    }
+                    window_of[id] = Some(members.len());
+                    inputs.remove(&id);
+                    inputs.extend(fanins(id));
+                    gates.push(id);
+                }
+                if !gates.is_empty() {
+                    members.push(gates);
+                }
+            }
+            PartitionKind::Mffc => {
+                // largest id first, so that every reader of a root has a window already
+                let mut pending: BinaryHeap<usize> = roots.iter().map(|r| usize::from(*r)).filter(|r| is_gate(*r)).collect();
+                while let Some(root) = pending.pop() {
+                    if window_of[root].is_some() {
+                        continue;
+                    }
+                    let w = members.len();
+                    let mut gates = Vec::new();
+                    let mut inputs: HashSet<usize> = HashSet::new();
+                    let mut candidates = BinaryHeap::from([root]);
+                    let mut seen = HashSet::from([root]);
+                    while let Some(id) = candidates.pop() {
+                        let new_inputs = fanins(id).into_iter().filter(|f| window_of[*f] != Some(w) && !inputs.contains(f)).count();
+                        let fits = gates.len() < max_nodes && inputs.len() - inputs.contains(&id) as usize + new_inputs <= max_inputs;
+                        // an output of the circuit is read outside any window
+                        let free = !is_root.contains(&id) && fanouts[id].iter().all(|p| window_of[*p] == Some(w));
+                        if id != root && !(free && fits) {
+                            pending.push(id);
+                            continue;
+                        }
+                        window_of[id] = Some(w);
+                        inputs.remove(&id);
+                        gates.push(id);
+                        for fanin in fanins(id) {
+                            inputs.insert(fanin);
+                            if is_gate(fanin) && window_of[fanin].is_none() && seen.insert(fanin) {
+                                candidates.push(fanin);
+                            }
+                        }
+                    }
+                    members.push(gates);
+                }
+            }
+        }
+
+        // names for the signals between windows that no input of the circuit has
+        let mut prefix = "w".to_string();
+        while circuit.inputs.iter().any(|i| i.starts_with(&prefix)) {
+            prefix.push('_');
+        }
+        let signal = |id: usize| format!("{}{}", prefix, id);
+        let windows = members
+            .iter()
+            .enumerate()
+            .map(|(w, gates)| {
+                let mut gates = gates.clone();
+                gates.sort();
+                let mut local = NodeTable::new();
+                let mut ids: HashMap<usize, Id> = HashMap::new();
+                let mut outputs = Vec::new();
+                for &id in &gates {
+                    let node = nodes[id].clone().map_children(|c| {
+                        let c = usize::from(c);
+                        match (ids.get(&c), &nodes[c]) {
+                            (Some(id), _) => *id,
+                            (None, leaf) if leaf.is_leaf() => local.add_node(leaf.clone()),
+                            (None, _) => local.add_node(Prop::Symbol(signal(c).into())),
+                        }
+                    });
+                    ids.insert(id, local.add_node(node));
+                    if is_root.contains(&id) || fanouts[id].iter().any(|p| window_of[*p] != Some(w)) {
+                        outputs.push(id);
+                    }
+                }
+                let exprs: Vec<RecExpr<Prop>> = outputs.iter().map(|id| local.cone(ids[id])).collect();
+                let circuit = Circuit { inputs: all_inputs(&[], &exprs), outputs: outputs.iter().map(|id| signal(*id)).collect(), exprs };
+                Window { circuit, nodes: gates.len() }
+            })
+            .collect();
+        let outputs = roots
+            .iter()
+            .map(|r| {
+                let node = &nodes[usize::from(*r)];
+                if node.is_leaf() { Err(node.clone()) } else { Ok(signal(usize::from(*r))) }
+            })
+            .collect();
+        Partition { windows, outputs }
+    }
+
+    /// The circuit with each window replaced by `results[i]`, the outputs of
+    /// window `i` in the same order. Logic that windows share stays shared.
+    pub fn stitch(&self, results: &[Vec<RecExpr<Prop>>]) -> Vec<RecExpr<Prop>> {
+        let mut table = NodeTable::new();
+        let mut signals: HashMap<&str, Id> = HashMap::new();
+        for w in self.order() {
+            for (name, expr) in self.windows[w].circuit.outputs.iter().zip(&results[w]) {
+                let mut ids: Vec<Id> = Vec::with_capacity(expr.as_ref().len());
+                for node in expr.as_ref() {
+                    let id = match node {
+                        Prop::Symbol(s) if signals.contains_key(s.as_str()) => signals[s.as_str()],
+                        node => table.add_node(node.clone().map_children(|c| ids[usize::from(c)])),
+                    };
+                    ids.push(id);
+                }
+                signals.insert(name, *ids.last().unwrap());
+            }
+        }
+        self.outputs
+            .iter()
+            .map(|output| match output {
+                Ok(name) => table.cone(signals[name.as_str()]),
+                Err(leaf) => RecExpr::from(vec![leaf.clone()]),
+            })
+            .collect()
+    }
+
+    // the windows, each after those whose outputs it reads
+    fn order(&self) -> Vec<usize> {
+        let mut producer: HashMap<&str, usize> = HashMap::new();
+        for (w, window) in self.windows.iter().enumerate() {
+            producer.extend(window.circuit.outputs.iter().map(|name| (name.as_str(), w)));
+        }
+        let mut readers: Vec<Vec<usize>> = vec![Vec::new(); self.windows.len()];
+        let mut waiting: Vec<usize> = vec![0; self.windows.len()];
+        for (w, window) in self.windows.iter().enumerate() {
+            let mut sources: Vec<usize> = window.circuit.inputs.iter().filter_map(|i| producer.get(i.as_str()).copied()).collect();
+            sources.sort();
+            sources.dedup();
+            waiting[w] = sources.len();
+            for source in sources {
+                readers[source].push(w);
+            }
+        }
+        let mut order: Vec<usize> = (0..self.windows.len()).filter(|w| waiting[*w] == 0).collect();
+        let mut next = 0;
+        while next < order.len() {
+            for &reader in &readers[order[next]] {
+                waiting[reader] -= 1;
+                if waiting[reader] == 0 {
+                    order.push(reader);
+                }
+            }
+            next += 1;
+        }
+        order
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cec;
+    use crate::eqn::parse_eqn;
+    use crate::stats::Stats;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn circuits() -> Vec<Circuit> {
+        vec![
+            parse_eqn(include_str!("../../benchmark/converted_circuit/ISCAS85/c17.eqn")).unwrap(),
+            parse_eqn(include_str!("../../benchmark/converted_circuit/ISCAS85/c432.eqn")).unwrap(),
+        ]
+    }
+
+    fn same(circuit: &Circuit, exprs: &[RecExpr<Prop>]) -> bool {
+        cec::check_equivalence(&circuit.exprs, exprs, &circuit.inputs, &mut StdRng::seed_from_u64(1)).unwrap().is_none()
+    }
+
+    #[test]
+    fn windows_keep_to_the_caps() {
+        for circuit in circuits() {
+            for kind in [PartitionKind::Cone, PartitionKind::Mffc] {
+                let partition = Partition::new(&circuit, kind, 6, 8);
+                assert!(!partition.windows.is_empty());
+                let gates: usize = partition.windows.iter().map(|w| w.nodes).sum();
+                assert_eq!(gates, Stats::new(&[], &circuit.exprs, None).nodes);
+                for window in &partition.windows {
+                    assert!(window.nodes >= 1 && window.nodes <= 8);
+                    // the root of an MFFC window may read more than the cap on its own
+                    if window.nodes > 1 {
+                        assert!(window.circuit.inputs.len() <= 6, "{} inputs", window.circuit.inputs.len());
+                    }
+                    if kind == PartitionKind::Mffc {
+                        assert_eq!(window.circuit.outputs.len(), 1);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn stitches_the_windows_back() {
+        for circuit in circuits() {
+            for kind in [PartitionKind::Cone, PartitionKind::Mffc] {
+                let partition = Partition::new(&circuit, kind, 6, 8);
+                let results: Vec<Vec<RecExpr<Prop>>> = partition.windows.iter().map(|w| w.circuit.exprs.clone()).collect();
+                let exprs = partition.stitch(&results);
+                assert!(same(&circuit, &exprs));
+                // nothing that the windows share is duplicated
+                assert_eq!(Stats::new(&[], &exprs, None).nodes, Stats::new(&[], &circuit.exprs, None).nodes);
+            }
+        }
+    }
+
+    #[test]
+    fn stitches_rewritten_windows() {
+        for circuit in circuits() {
+            for kind in [PartitionKind::Cone, PartitionKind::Mffc] {
+                let partition = Partition::new(&circuit, kind, 6, 8);
+                let (mut results, mut outputs) = (Vec::new(), 0);
+                for window in &partition.windows {
+                    // an equivalent window with a gate more per output
+                    let doubled: Vec<RecExpr<Prop>> = window.circuit.exprs.iter().map(|e| format!("(* {} {})", e, e).parse().unwrap()).collect();
+                    outputs += doubled.len();
+                    results.push(doubled);
+                }
+                let exprs = partition.stitch(&results);
+                assert!(same(&circuit, &exprs));
+                assert_eq!(Stats::new(&[], &exprs, None).nodes, Stats::new(&[], &circuit.exprs, None).nodes + outputs);
+            }
+        }
+    }
+
+    #[test]
+    fn names_signals_apart_from_the_inputs() {
+        let circuit = Circuit { inputs: vec!["w1".into(), "b".into()], outputs: vec!["y".into(), "z".into()], exprs: vec!["(* (+ w1 b) (! w1))".parse().unwrap(), "(+ w1 b)".parse().unwrap()] };
+        let partition = Partition::new(&circuit, PartitionKind::Mffc, 4, 4);
+        for window in &partition.windows {
+            assert!(window.circuit.outputs.iter().all(|o| o.starts_with("w_")));
+        }
+        let results: Vec<Vec<RecExpr<Prop>>> = partition.windows.iter().map(|w| w.circuit.exprs.clone()).collect();
+        assert!(same(&circuit, &partition.stitch(&results)));
+    }
+}
@@ -0,0 +1,395 @@
+//! Rewrite proofs that can be checked without egg.
+//!
+//! `write_proof` stores egg's explanation of each output as a DAG of proof
+//! steps, one line each, and `check_proof` replays it: every term is rebuilt
+//! from the steps, every rule application must be an instance of a rule that
+//! holds on its truth table. The first term of each output must be the input
+//! and the last one the result.
+//!
+//! Constant folding (`analysis`) merges an operator over constants with its
+//! value, which is checked by evaluating it. No rule derives the other merges,
+//! those of the sweep (`fraig`) and folds of terms that are only constant
+//! under the e-graph's analysis, nor a result file that spells out operators
+//! its format lacks. With `cec` they are proven with `cec::prove_equal`
+//! instead, and counted apart from the rewrites; without it they fail the
+//! check.
+//!
+//! The format, after `#` comments:
+//!
+//! ```text
+//! step <id> <op> <seq>...  [: <rule> => | : <rule> <=]
+//! seq <id> <step>...
+//! output <name> <seq>
+//! ```
+//!
+//! A step is an operator over the proofs of its children; its first term is
+//! the operator over their first terms, its last over their last ones. A
+//! `seq` chains steps: each one starts where the previous ended, or where a
+//! rule rewrites it to, left to right with `=>` and right to left with `<=`.
+//! Steps and seqs are defined before they are used.
+
+use egg::*;
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Write};
+use std::rc::Rc;
+
+use crate::cec;
+use crate::netlist::{is_constant, NodeTable};
+use crate::prop::{Circuit, Prop};
+use crate::rules::{RuleSpec, Verdict};
+
+/// The proof of `explanations[i]`, the explanation of output `names[i]`.
+pub fn write_proof<L: Language + Display>(names: &[String], explanations: &[Explanation<L>]) -> String {
+    let mut text = String::from("# e-rewriter proof, check with `esyn check-proof`\n");
+    let mut steps: HashMap<*const TreeTerm<L>, usize> = HashMap::new();
+    let mut seqs = 0;
+    let mut write_seq = |text: &mut String, proof: &[Rc<TreeTerm<L>>], steps: &HashMap<*const TreeTerm<L>, usize>| {
+        let ids: String = proof.iter().map(|term| format!(" {}", steps[&Rc::as_ptr(term)])).collect();
+        writeln!(text, "seq {}{}", seqs, ids).unwrap();
+        seqs += 1;
+        seqs - 1
+    };
+    for (name, explanation) in names.iter().zip(explanations) {
+        // children before parents, without recursion since circuits get deep
+        let mut stack: Vec<(&Rc<TreeTerm<L>>, bool)> = explanation.explanation_trees.iter().rev().map(|t| (t, false)).collect();
+        while let Some((term, expanded)) = stack.pop() {
+            if steps.contains_key(&Rc::as_ptr(term)) {
+                continue;
+            }
+            if !expanded {
+                stack.push((term, true));
+                stack.extend(term.child_proofs.iter().flatten().rev().map(|t| (t, false)));
+                continue;
+            }
+            let children: Vec<usize> = term.child_proofs.iter().map(|proof| write_seq(&mut text, proof, &steps)).collect();
+            let id = steps.len();
+            write!(text, "step {} {}", id, term.node).unwrap();
+            for child in children {
+                write!(text, " {}", child).unwrap();
+            }
+            match (term.forward_rule, term.backward_rule) {
+                (Some(rule), _) => writeln!(text, " : {} =>", rule).unwrap(),
+                (None, Some(rule)) => writeln!(text, " : {} <=", rule).unwrap(),
+                (None, None) => writeln!(text).unwrap(),
+            }
+            steps.insert(Rc::as_ptr(term), id);
+        }
+        let seq = write_seq(&mut text, &explanation.explanation_trees, &steps);
+        writeln!(text, "output {} {}", name, seq).unwrap();
+    }
+    text
+}
+
+/// What `check_proof` went through.
+#[derive(Debug, Default)]
+pub struct ProofReport {
+    pub outputs: usize,
+    pub steps: usize,
+    /// Rule applications, each matched against its rule.
+    pub rewrites: usize,
+    /// `analysis` merges of an operator over constants, each evaluated.
+    pub folds: usize,
+    /// The other `fraig` and `analysis` merges, each proven by simulation or
+    /// SAT.
+    pub cec_merges: usize,
+    /// The rules the proof uses, each checked on its truth table.
+    pub rules: usize,
+    /// Outputs whose last term is not the result as written, but proven
+    /// equivalent to it by simulation or SAT.
+    pub cec_outputs: usize,
+}
+
+// subterms below this depth are printed as `...` in errors
+const TERM_DEPTH: usize = 4;
+
+// The first and last term of a step, and the rule that leads to it.
+struct Step {
+    first: Id,
+    last: Id,
+    rule: Option<(String, bool)>,
+}
+
+struct Checker<'a> {
+    terms: NodeTable<Prop>,
+    specs: &'a [RuleSpec],
+    // name to (spec, lhs, rhs), `-rev` rules the other way round
+    rules: HashMap<String, (usize, PatternAst<Prop>, PatternAst<Prop>)>,
+    sound: HashSet<usize>,
+    proven: HashSet<(Id, Id)>,
+    cec: bool,
+    report: ProofReport,
+}
+
+impl Checker<'_> {
+    // `to` follows `from` in a seq, rewritten by `rule` if there is one.
+    fn link(&mut self, from: Id, to: Id, rule: &Option<(String, bool)>) -> Result<(), String> {
+        let Some((rule, forward)) = rule else {
+            if from != to {
+                return Err(format!("{} does not continue from {}", self.show(to), self.show(from)));
+            }
+            return Ok(());
+        };
+        if rule == "fraig" || rule == "analysis" {
+            if rule == "analysis" && self.is_constant(from) && self.is_constant(to) {
+                self.report.folds += 1;
+            } else if self.cec {
+                self.report.cec_merges += 1;
+            } else {
+                return Err(format!("`{}` merges {} and {}, which no rule derives (check with --cec)", rule, self.show(from), self.show(to)));
+            }
+            if from != to && !self.proven.contains(&(from, to)) {
+                if !cec::prove_equal(&self.terms.cone(from), &self.terms.cone(to))? {
+                    return Err(format!("`{}` merges {} and {}, which differ", rule, self.show(from), self.show(to)));
+                }
+                self.proven.insert((from, to));
+            }
+            return Ok(());
+        }
+        let (spec, lhs, rhs) = self.rules[rule].clone();
+        self.rule_is_sound(spec)?;
+        self.report.rewrites += 1;
+        // `<=`: the rule rewrites `to` into `from`
+        let (from, to) = if *forward { (from, to) } else { (to, from) };
+        let mut subst = HashMap::new();
+        if !self.matches(&lhs, lhs.as_ref().len() - 1, from, &mut subst) {
+            return Err(format!("`{}` does not match {}", rule, self.show(from)));
+        }
+        let rewritten = self.instantiate(&rhs, &subst);
+        if rewritten != to {
+            return Err(format!("`{}` rewrites {} into {}, not {}", rule, self.show(from), self.show(rewritten), self.show(to)));
+        }
+        Ok(())
+    }
+
+    // Does `term` read no inputs?
+    fn is_constant(&self, term: Id) -> bool {
+        self.terms.cone(term).as_ref().iter().filter(|n| n.is_leaf()).all(|n| is_constant(&n.to_string()))
+    }
+
+    fn rule_is_sound(&mut self, spec: usize) -> Result<(), String> {
+        if self.sound.insert(spec) {
+            if let Verdict::Unsound(_) = self.specs[spec].check()? {
+                return Err(format!("rule `{}` does not hold on its truth table", self.specs[spec].name));
+            }
+            self.report.rules += 1;
+        }
+        Ok(())
+    }
+
+    fn matches(&self, pattern: &PatternAst<Prop>, at: usize, term: Id, subst: &mut HashMap<Var, Id>) -> bool {
+        match &pattern.as_ref()[at] {
+            ENodeOrVar::Var(v) => *subst.entry(*v).or_insert(term) == term,
+            ENodeOrVar::ENode(node) => {
+                let term = &self.terms.nodes[usize::from(term)];
+                node.matches(term)
+                    && node.children().iter().zip(term.children()).all(|(p, t)| self.matches(pattern, usize::from(*p), *t, subst))
+            }
+        }
+    }
+
+    fn instantiate(&mut self, pattern: &PatternAst<Prop>, subst: &HashMap<Var, Id>) -> Id {
+        let mut ids: Vec<Id> = Vec::with_capacity(pattern.as_ref().len());
+        for node in pattern.as_ref() {
+            let id = match node {
+                ENodeOrVar::Var(v) => subst[v],
+                ENodeOrVar::ENode(node) => self.terms.add_node(node.clone().map_children(|c| ids[usize::from(c)])),
+            };
+            ids.push(id);
+        }
+        *ids.last().unwrap()
+    }
+
+    fn show(&self, term: Id) -> String {
+        self.show_to(term, TERM_DEPTH)
+    }
+
+    fn show_to(&self, term: Id, depth: usize) -> String {
+        let node = &self.terms.nodes[usize::from(term)];
+        if node.is_leaf() {
+            return node.to_string();
+        }
+        if depth == 0 {
+            return "...".to_string();
+        }
+        let children: Vec<String> = node.children().iter().map(|c| self.show_to(*c, depth - 1)).collect();
+        format!("({} {})", node, children.join(" "))
+    }
+}
+
+/// Replay `proof` and check that it turns the outputs of `input` into those
+/// of `result` with the enabled rules of `specs`, and with simulation or SAT
+/// where no rule applies if `cec` is set.
+pub fn check_proof(proof: &str, specs: &[RuleSpec], enabled: &HashSet<String>, input: &Circuit, result: &Circuit, cec: bool) -> Result<ProofReport, String> {
+    let mut rules = HashMap::new();
+    for (i, spec) in specs.iter().enumerate().filter(|(_, s)| s.is_enabled(enabled)) {
+        let lhs: Pattern<Prop> = spec.lhs.parse().map_err(|e| format!("rule `{}`: {}", spec.name, e))?;
+        let rhs: Pattern<Prop> = spec.rhs.parse().map_err(|e| format!("rule `{}`: {}", spec.name, e))?;
+        if spec.bidirectional {
+            rules.insert(format!("{}-rev", spec.name), (i, rhs.ast.clone(), lhs.ast.clone()));
+        }
+        rules.insert(spec.name.clone(), (i, lhs.ast, rhs.ast));
+    }
+    if input.exprs.len() != result.exprs.len() {
+        return Err(format!("the input has {} outputs, the result {}", input.exprs.len(), result.exprs.len()));
+    }
+    let mut checker = Checker { terms: NodeTable::new(), specs, rules, sound: HashSet::new(), proven: HashSet::new(), cec, report: ProofReport::default() };
+    let mut steps: Vec<Step> = Vec::new();
+    // first and last term of each seq
+    let mut seqs: Vec<(Id, Id)> = Vec::new();
+    for (n, line) in proof.lines().enumerate() {
+        let at = |e: String| format!("line {}: {}", n + 1, e);
+        let line = line.split('#').next().unwrap();
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let id = |word: &str, defined: usize| -> Result<usize, String> {
+            match word.parse::<usize>() {
+                Ok(id) if id < defined => Ok(id),
+                _ => Err(at(format!("`{}` is not defined yet", word))),
+            }
+        };
+        match words.as_slice() {
+            [] => {}
+            ["step", number, op, rest @ ..] => {
+                if number.parse() != Ok(steps.len()) {
+                    return Err(at(format!("expected step {}", steps.len())));
+                }
+                let (children, rule) = match rest.iter().position(|w| *w == ":") {
+                    Some(i) => match &rest[i + 1..] {
+                        [rule, "=>"] => (&rest[..i], Some((rule.to_string(), true))),
+                        [rule, "<="] => (&rest[..i], Some((rule.to_string(), false))),
+                        _ => return Err(at("expected `: <rule> =>` or `: <rule> <=`".to_string())),
+                    },
+                    None => (rest, None),
+                };
+                if let Some((rule, _)) = &rule {
+                    if !checker.rules.contains_key(rule) && rule != "fraig" && rule != "analysis" {
+                        return Err(at(format!("`{}` is not in the rule set", rule)));
+                    }
+                }
+                let children = children.iter().map(|w| id(w, seqs.len()).map(|i| seqs[i])).collect::<Result<Vec<_>, _>>()?;
+                let first = checker.terms.add(op, children.iter().map(|c| c.0).collect()).map_err(at)?;
+                let last = checker.terms.add(op, children.iter().map(|c| c.1).collect()).map_err(at)?;
+                steps.push(Step { first, last, rule });
+                checker.report.steps += 1;
+            }
+            ["seq", number, rest @ ..] => {
+                if number.parse() != Ok(seqs.len()) {
+                    return Err(at(format!("expected seq {}", seqs.len())));
+                }
+                let ids = rest.iter().map(|w| id(w, steps.len())).collect::<Result<Vec<_>, _>>()?;
+                let (Some(head), Some(tail)) = (ids.first(), ids.last()) else {
+                    return Err(at("empty seq".to_string()));
+                };
+                for pair in ids.windows(2) {
+                    let (from, to) = (steps[pair[0]].last, &steps[pair[1]]);
+                    checker.link(from, to.first, &to.rule).map_err(at)?;
+                }
+                seqs.push((steps[*head].first, steps[*tail].last));
+            }
+            ["output", name, seq] => {
+                let (first, last) = seqs[id(seq, seqs.len())?];
+                let i = checker.report.outputs;
+                if i >= input.exprs.len() {
+                    return Err(at(format!("the input has only {} outputs", input.exprs.len())));
+                }
+                let expected = input.outputs.get(i).cloned().unwrap_or_else(|| format!("output {}", i));
+                if *name != expected {
+                    return Err(at(format!("expected output `{}`, not `{}`", expected, name)));
+                }
+                if result.outputs.get(i).is_some_and(|o| o != name) {
+                    return Err(at(format!("output {} of the result is `{}`, not `{}`", i, result.outputs[i], name)));
+                }
+                if checker.terms.add_expr(&input.exprs[i]) != first {
+                    return Err(at(format!("the proof of `{}` does not start from the input", name)));
+                }
+                // eqn, BLIF and AIGER spell out the operators they lack, the result is then only equivalent
+                if checker.terms.add_expr(&result.exprs[i]) != last {
+                    if !cec || !cec::prove_equal(&checker.terms.cone(last), &result.exprs[i])? {
+                        return Err(at(format!("the proof of `{}` does not end at the result", name)));
+                    }
+                    checker.report.cec_outputs += 1;
+                }
+                checker.report.outputs += 1;
+            }
+            _ => return Err(at(format!("cannot read `{}`", line.trim()))),
+        }
+    }
+    if checker.report.outputs != input.exprs.len() {
+        return Err(format!("the proof covers {} of {} outputs", checker.report.outputs, input.exprs.len()));
+    }
+    Ok(checker.report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prop::ConstantFold;
+    use crate::rules::parse_rules;
+
+    const RULES: &str = "and-comm: (* ?a ?b) => (* ?b ?a)\nor-comm: (+ ?a ?b) => (+ ?b ?a)\n";
+
+    fn circuit(expr: &str) -> Circuit {
+        Circuit { inputs: vec!["a".to_string(), "b".to_string()], outputs: vec!["y".to_string()], exprs: vec![expr.parse().unwrap()] }
+    }
+
+    fn check(proof: &str, input: &str, result: &str, cec: bool) -> Result<ProofReport, String> {
+        check_proof(proof, &parse_rules(RULES).unwrap(), &HashSet::new(), &circuit(input), &circuit(result), cec)
+    }
+
+    // The proof egg explains for `input` and `result`, equal under RULES.
+    fn explain(input: &str, result: &str) -> String {
+        let rewrites = crate::rules::make_rewrites(&parse_rules(RULES).unwrap(), &HashSet::new()).unwrap();
+        let (input, result): (RecExpr<Prop>, RecExpr<Prop>) = (input.parse().unwrap(), result.parse().unwrap());
+        let mut runner = Runner::<Prop, ConstantFold>::new(ConstantFold::default()).with_explanations_enabled().with_expr(&input).run(&rewrites);
+        write_proof(&["y".to_string()], &[runner.explain_equivalence(&input, &result)])
+    }
+
+    #[test]
+    fn written_proofs_check() {
+        let proof = explain("(+ (* a b) b)", "(+ b (* b a))");
+        let report = check(&proof, "(+ (* a b) b)", "(+ b (* b a))", false).unwrap();
+        assert_eq!((report.outputs, report.rewrites, report.rules), (1, 2, 2));
+        assert_eq!((report.cec_merges, report.cec_outputs), (0, 0));
+    }
+
+    #[test]
+    fn rejects_a_step_its_rule_does_not_make() {
+        let proof = explain("(* a b)", "(* b a)").replace("and-comm", "or-comm");
+        let e = check(&proof, "(* a b)", "(* b a)", false).unwrap_err();
+        assert!(e.contains("`or-comm` does not match"), "{}", e);
+    }
+
+    #[test]
+    fn the_last_term_must_be_the_result() {
+        let proof = explain("(* a b)", "(* b a)");
+        // equivalent, but not what the proof ends at
+        let e = check(&proof, "(* a b)", "(! (+ (! a) (! b)))", false).unwrap_err();
+        assert!(e.contains("does not end at the result"), "{}", e);
+        let report = check(&proof, "(* a b)", "(! (+ (! a) (! b)))", true).unwrap();
+        assert_eq!(report.cec_outputs, 1);
+        // with cec, a different function is still rejected
+        assert!(check(&proof, "(* a b)", "(+ b a)", true).is_err());
+    }
+
+    #[test]
+    fn merges_no_rule_derives_need_cec() {
+        // `(* a b)`, then `(! (+ (! a) (! b)))` merged into it by the sweep
+        let proof = "step 0 a\nseq 0 0\nstep 1 b\nseq 1 1\nstep 2 * 0 1\nstep 3 ! 0\nstep 4 ! 1\nseq 2 3\nseq 3 4\nstep 5 + 2 3\nseq 4 5\nstep 6 ! 4 : fraig =>\nseq 5 2 6\noutput y 5\n";
+        let e = check(proof, "(* a b)", "(! (+ (! a) (! b)))", false).unwrap_err();
+        assert!(e.contains("no rule derives"), "{}", e);
+        let report = check(proof, "(* a b)", "(! (+ (! a) (! b)))", true).unwrap();
+        assert_eq!((report.cec_merges, report.cec_outputs), (1, 0));
+        let proof = proof.replace("step 5 +", "step 5 *");
+        let e = check(&proof, "(* a b)", "(! (* (! a) (! b)))", true).unwrap_err();
+        assert!(e.contains("which differ"), "{}", e);
+    }
+
+    #[test]
+    fn folds_constants_without_cec() {
+        let proof = "step 0 true\nseq 0 0\nstep 1 ! 0\nstep 2 false : analysis =>\nseq 1 1 2\noutput y 1\n";
+        let report = check(proof, "(! true)", "false", false).unwrap();
+        assert_eq!((report.folds, report.cec_merges), (1, 0));
+        let proof = proof.replace("step 2 false", "step 2 true");
+        assert!(check(&proof, "(! true)", "true", true).is_err());
+    }
+}
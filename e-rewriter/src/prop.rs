@@ -0,0 +1,160 @@
+//! The circuit language and its e-class analysis.
+
+use egg::*;
+
+use crate::extract::GateCost;
+use crate::netlist::Netlist;
+use crate::sweep;
+
+define_language! {
+    pub enum Prop {
+        Bool(bool),
+        "*" = And([Id; 2]),
+        "!" = Not(Id),
+        "+" = Or([Id; 2]),
+        "->" = Implies([Id; 2]),
+        "let" = Let([Id; 2]),
+        "&" = Concat([Id; 2]),
+        "^" = Xor([Id; 2]),
+        "xnor" = Xnor([Id; 2]),
+        // (mux s a b) is a when s holds, b otherwise
+        "mux" = Mux([Id; 3]),
+        "maj" = Maj([Id; 3]),
+        Symbol(Symbol),
+    }
+}
+
+impl Prop {
+    /// The AND/OR/NOT gates the writers expand an operator that has no gate
+    /// of its own into, and how many of them are on its longest path.
+    pub fn expansion(&self) -> Option<(&'static [&'static str], usize)> {
+        match self {
+            Prop::Xor(_) | Prop::Xnor(_) => Some((&["*", "*", "+", "!", "!"], 3)),
+            Prop::Mux(_) => Some((&["*", "*", "+", "!"], 3)),
+            Prop::Maj(_) => Some((&["*", "*", "+", "+"], 3)),
+            _ => None,
+        }
+    }
+}
+
+impl GateCost for Prop {
+    fn gates(&self) -> usize {
+        self.expansion().map_or(1, |(gates, _)| gates.len())
+    }
+
+    fn levels(&self) -> usize {
+        self.expansion().map_or(1, |(_, levels)| levels)
+    }
+}
+
+/// The circuit being optimised, with one expression per output.
+pub type Circuit = Netlist<Prop>;
+
+// Constant folding, plus a random-simulation signature and the smallest term
+// per e-class for sweeping.
+#[derive(Default)]
+pub struct ConstantFold {
+    /// Seeds the input patterns of the signatures.
+    pub sim_seed: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct FoldData {
+    pub constant: Option<(bool, PatternAst<Prop>)>,
+    pub sig: sweep::Signature,
+    /// The e-node of the smallest term of the class and that term's tree size,
+    /// which is larger than the sizes of its children's terms.
+    pub term: Prop,
+    pub size: usize,
+}
+
+fn fold_constant(egraph: &egg::EGraph<Prop, ConstantFold>, enode: &Prop) -> Option<(bool, PatternAst<Prop>)> {
+    let x = |i: &Id| egraph[*i].data.constant.as_ref().map(|c| c.0);
+    let constant = match enode {
+        Prop::Let([a, b]) => Some((
+            x(a) == x(b),
+            format!("(let {} {})", x(a)?, x(b)?).parse().unwrap(),
+        )),
+        Prop::Bool(c) => Some((*c, c.to_string().parse().unwrap())),
+        Prop::And([a, b]) => Some((
+            x(a)? && x(b)?,
+            format!("(* {} {})", x(a)?, x(b)?).parse().unwrap(),
+        )),
+        Prop::Not(a) => Some((!x(a)?, format!("(! {})", x(a)?).parse().unwrap())),
+        Prop::Or([a, b]) => Some((
+            x(a)? || x(b)?,
+            format!("(+ {} {})", x(a)?, x(b)?).parse().unwrap(),
+        )),
+        Prop::Implies([a, b]) => Some((
+            !x(a)? || x(b)?,
+            format!("(-> {} {})", x(a)?, x(b)?).parse().unwrap(),
+        )),
+        Prop::Concat([a, b]) => Some((
+            x(a)? & !x(b)?,
+            format!("(& {} {})", x(a)?, x(b)?).parse().unwrap(),
+        )),
+        Prop::Xor([a, b]) => Some((
+            x(a)? ^ x(b)?,
+            format!("(^ {} {})", x(a)?, x(b)?).parse().unwrap(),
+        )),
+        Prop::Xnor([a, b]) => Some((
+            x(a)? == x(b)?,
+            format!("(xnor {} {})", x(a)?, x(b)?).parse().unwrap(),
+        )),
+        Prop::Mux([s, a, b]) => Some((
+            if x(s)? { x(a)? } else { x(b)? },
+            format!("(mux {} {} {})", x(s)?, x(a)?, x(b)?).parse().unwrap(),
+        )),
+        Prop::Maj([a, b, c]) => Some((
+            (x(a)? && x(b)?) || (x(c)? && (x(a)? || x(b)?)),
+            format!("(maj {} {} {})", x(a)?, x(b)?, x(c)?).parse().unwrap(),
+        )),
+        Prop::Symbol(_) => None,
+    };
+    //println!("Make: {:?} -> {:?}", enode, constant);
+    constant
+}
+
+impl Analysis<Prop> for ConstantFold {
+    type Data = FoldData;
+    fn merge(&mut self, to: &mut Self::Data, from: Self::Data) -> DidMerge {
+        let smaller = DidMerge(from.size < to.size, to.size < from.size);
+        if from.size < to.size {
+            (to.term, to.size) = (from.term, from.size);
+        }
+        smaller | merge_option(&mut to.constant, from.constant, |a, b| {
+            assert_eq!(a.0, b.0, "Merged non-equal constants");
+            DidMerge(false, false)
+        })
+    }
+    fn make(egraph: &egg::EGraph<Prop, ConstantFold>, enode: &Prop) -> Self::Data {
+        let sig = sweep::signature(enode, egraph.analysis.sim_seed, |id| egraph[id].data.sig);
+        let size = enode.fold(1usize, |sum, id| sum.saturating_add(egraph[id].data.size));
+        FoldData { constant: fold_constant(egraph, enode), sig, term: enode.clone(), size }
+    }
+    fn modify(egraph: &mut egg::EGraph<Prop, ConstantFold>, id: Id) {
+        if let Some(c) = egraph[id].data.constant.clone() {
+            egraph.union_instantiations(
+                &c.1,
+                &c.0.to_string().parse().unwrap(),
+                &Default::default(),
+                "analysis".to_string(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn folds_the_new_operators() {
+        let mut egraph = egg::EGraph::new(ConstantFold::default());
+        for (expr, value) in [("(^ true true)", false), ("(xnor false true)", false), ("(mux false false true)", true), ("(maj true false true)", true)] {
+            let id = egraph.add_expr(&expr.parse().unwrap());
+            egraph.rebuild();
+            assert_eq!(egraph[id].data.constant.as_ref().map(|c| c.0), Some(value), "{}", expr);
+        }
+    }
+}
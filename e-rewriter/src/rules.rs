@@ -0,0 +1,250 @@
+use egg::*;
+
+use std::collections::HashSet;
+
+use crate::prop::{ConstantFold, Prop};
+
+/// Rule sets compiled into the binary, selected with `--ruleset <name>`.
+pub const BUILTIN: &[(&str, &str)] = &[
+    ("enhance", include_str!("../rules/enhance.rules")),
+    ("basic", include_str!("../rules/basic.rules")),
+];
+
+pub const DEFAULT_RULESET: &str = "enhance";
+
+/// One line of a rule file, before it is turned into egg rewrites.
+#[derive(Clone, Debug)]
+pub struct RuleSpec {
+    pub name: String,
+    pub lhs: String,
+    pub rhs: String,
+    pub bidirectional: bool,
+    pub tags: Vec<String>,
+}
+
+impl RuleSpec {
+    /// Untagged rules are always on, tagged ones need one of their tags enabled.
+    pub fn is_enabled(&self, enabled: &HashSet<String>) -> bool {
+        self.tags.is_empty() || self.tags.iter().any(|t| enabled.contains(t))
+    }
+
+    /// Compare both sides on every assignment of the pattern variables.
+    ///
+    /// Symbols other than `0` and `1` only match themselves, so they are
+    /// treated as variables too.
+    pub fn check(&self) -> Result<Verdict, String> {
+        let lhs: Pattern<Prop> = self.lhs.parse().map_err(|e| format!("rule `{}`: {}", self.name, e))?;
+        let rhs: Pattern<Prop> = self.rhs.parse().map_err(|e| format!("rule `{}`: {}", self.name, e))?;
+        let mut vars = Vec::new();
+        for ast in [&lhs.ast, &rhs.ast] {
+            for node in ast.as_ref() {
+                if let Some(v) = leaf_name(node) {
+                    if !vars.contains(&v) {
+                        vars.push(v);
+                    }
+                }
+            }
+        }
+        if vars.len() > MAX_CHECK_VARS {
+            return Err(format!("rule `{}` has {} variables, at most {} can be checked", self.name, vars.len(), MAX_CHECK_VARS));
+        }
+        for row in 0..1u32 << vars.len() {
+            let value = |v: &str| row >> vars.iter().position(|w| w == v).unwrap() & 1 == 1;
+            if eval(&lhs.ast, value) != eval(&rhs.ast, value) {
+                let assignment = vars.iter().map(|v| (v.clone(), value(v))).collect();
+                return Ok(Verdict::Unsound(assignment));
+            }
+        }
+        Ok(Verdict::Sound)
+    }
+
+    pub fn to_rewrites(&self) -> Result<Vec<Rewrite<Prop, ConstantFold>>, String> {
+        let lhs: Pattern<Prop> = self.lhs.parse().map_err(|e| format!("rule `{}`: {}", self.name, e))?;
+        let rhs: Pattern<Prop> = self.rhs.parse().map_err(|e| format!("rule `{}`: {}", self.name, e))?;
+        let mut rws = vec![Rewrite::new(self.name.as_str(), lhs.clone(), rhs.clone())?];
+        if self.bidirectional {
+            rws.push(Rewrite::new(format!("{}-rev", self.name), rhs, lhs)?);
+        }
+        Ok(rws)
+    }
+}
+
+const MAX_CHECK_VARS: usize = 20;
+
+#[derive(Debug)]
+pub enum Verdict {
+    Sound,
+    /// An assignment of the pattern variables on which the two sides differ.
+    Unsound(Vec<(String, bool)>),
+}
+
+// Name of a pattern leaf that is free in the truth table, None for constants
+// and operators.
+fn leaf_name(node: &ENodeOrVar<Prop>) -> Option<String> {
+    match node {
+        ENodeOrVar::Var(v) => Some(v.to_string()),
+        ENodeOrVar::ENode(Prop::Symbol(s)) if s.as_str() != "0" && s.as_str() != "1" => Some(s.to_string()),
+        ENodeOrVar::ENode(_) => None,
+    }
+}
+
+fn eval(ast: &PatternAst<Prop>, value: impl Fn(&str) -> bool) -> bool {
+    let mut values: Vec<bool> = Vec::with_capacity(ast.as_ref().len());
+    for node in ast.as_ref() {
+        let x = |i: &Id| values[usize::from(*i)];
+        let v = match node {
+            ENodeOrVar::Var(v) => value(&v.to_string()),
+            ENodeOrVar::ENode(node) => match node {
+                Prop::Bool(c) => *c,
+                Prop::Symbol(s) => match s.as_str() {
+                    "0" => false,
+                    "1" => true,
+                    name => value(name),
+                },
+                Prop::And([a, b]) => x(a) && x(b),
+                Prop::Or([a, b]) => x(a) || x(b),
+                Prop::Not(a) => !x(a),
+                Prop::Implies([a, b]) => !x(a) || x(b),
+                Prop::Let([a, b]) => x(a) == x(b),
+                Prop::Concat([a, b]) => x(a) & !x(b),
+                Prop::Xor([a, b]) => x(a) ^ x(b),
+                Prop::Xnor([a, b]) => x(a) == x(b),
+                Prop::Mux([s, a, b]) => if x(s) { x(a) } else { x(b) },
+                Prop::Maj([a, b, c]) => (x(a) && x(b)) || (x(c) && (x(a) || x(b))),
+            },
+        };
+        values.push(v);
+    }
+    *values.last().unwrap()
+}
+
+/// Fail on any enabled rule that does not hold on its truth table.
+pub fn reject_unsound(specs: &[RuleSpec], enabled: &HashSet<String>) -> Result<(), String> {
+    let mut unsound = Vec::new();
+    for spec in specs.iter().filter(|s| s.is_enabled(enabled)) {
+        if let Verdict::Unsound(_) = spec.check()? {
+            unsound.push(spec.name.as_str());
+        }
+    }
+    if unsound.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("unsound rules: {} (run `e-rewriter verify-rules` for counterexamples)", unsound.join(", ")))
+    }
+}
+
+/// Parse a rule file.
+///
+/// One rule per line, `name: lhs => rhs` or `name: lhs <=> rhs`, followed
+/// by optional `@tag` words. `#` starts a comment.
+pub fn parse_rules(contents: &str) -> Result<Vec<RuleSpec>, String> {
+    let mut specs = Vec::new();
+    let mut names = HashSet::new();
+    for (number, line) in contents.lines().enumerate() {
+        let line = line.split('#').next().unwrap().trim();
+        if line.is_empty() {
+            continue;
+        }
+        let err = |msg: &str| format!("line {}: {}: `{}`", number + 1, msg, line);
+        let (name, rule) = line.split_once(':').ok_or_else(|| err("expected `name: lhs => rhs`"))?;
+        let (rule, tags) = match rule.split_once('@') {
+            Some((rule, tags)) => (rule, tags.split('@').map(str::trim).filter(|t| !t.is_empty()).map(String::from).collect()),
+            None => (rule, Vec::new()),
+        };
+        let (lhs, rhs, bidirectional) = if let Some((lhs, rhs)) = rule.split_once("<=>") {
+            (lhs, rhs, true)
+        } else if let Some((lhs, rhs)) = rule.split_once("=>") {
+            (lhs, rhs, false)
+        } else {
+            return Err(err("expected `=>` or `<=>`"));
+        };
+        let name = name.trim();
+        if !names.insert(name.to_string()) {
+            return Err(err("duplicate rule name"));
+        }
+        specs.push(RuleSpec {
+            name: name.to_string(),
+            lhs: lhs.trim().to_string(),
+            rhs: rhs.trim().to_string(),
+            bidirectional,
+            tags,
+        });
+    }
+    Ok(specs)
+}
+
+/// The text of a built-in rule set.
+pub fn builtin(name: &str) -> Result<&'static str, String> {
+    BUILTIN
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, text)| *text)
+        .ok_or_else(|| {
+            let known: Vec<&str> = BUILTIN.iter().map(|(n, _)| *n).collect();
+            format!("unknown rule set `{}`, built-in sets are: {}", name, known.join(", "))
+        })
+}
+
+/// Turn the enabled rules into egg rewrites.
+pub fn make_rewrites(specs: &[RuleSpec], enabled: &HashSet<String>) -> Result<Vec<Rewrite<Prop, ConstantFold>>, String> {
+    let mut rws = Vec::new();
+    for spec in specs.iter().filter(|s| s.is_enabled(enabled)) {
+        rws.extend(spec.to_rewrites()?);
+    }
+    Ok(rws)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enabled(tags: &[&str]) -> HashSet<String> {
+        tags.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_rule_files() {
+        let specs = parse_rules("# header\n\ncomm: (* ?a ?b) => (* ?b ?a)\ndm: (! (* ?a ?b)) <=> (+ (! ?a) (! ?b)) # De Morgan\nx: (^ ?a ?a) => 0 @xor @extra\n").unwrap();
+        let names: Vec<&str> = specs.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["comm", "dm", "x"]);
+        assert_eq!((specs[1].lhs.as_str(), specs[1].rhs.as_str(), specs[1].bidirectional), ("(! (* ?a ?b))", "(+ (! ?a) (! ?b))", true));
+        assert_eq!(specs[2].tags, ["xor", "extra"]);
+        assert!(specs[0].is_enabled(&enabled(&[])) && !specs[2].is_enabled(&enabled(&[])) && specs[2].is_enabled(&enabled(&["extra"])));
+        let rewrites: Vec<String> = make_rewrites(&specs, &enabled(&[])).unwrap().iter().map(|r| r.name.to_string()).collect();
+        assert_eq!(rewrites, ["comm", "dm", "dm-rev"]);
+    }
+
+    #[test]
+    fn rejects_broken_rule_files() {
+        assert!(parse_rules("comm (* ?a ?b) => (* ?b ?a)\n").unwrap_err().contains("line 1: expected `name: lhs => rhs`"));
+        assert!(parse_rules("a: x => y\n\nb: x = y\n").unwrap_err().contains("line 3: expected `=>` or `<=>`"));
+        assert!(parse_rules("a: x => y\na: y => x\n").unwrap_err().contains("duplicate rule name"));
+        let specs = parse_rules("bad: (* ?a => ?a\n").unwrap();
+        assert!(make_rewrites(&specs, &enabled(&[])).unwrap_err().contains("rule `bad`"));
+        assert!(builtin("nope").unwrap_err().contains("built-in sets are: enhance, basic"));
+    }
+
+    #[test]
+    fn builtin_rules_hold() {
+        for (name, text) in BUILTIN {
+            let specs = parse_rules(text).unwrap();
+            reject_unsound(&specs, &HashSet::new()).unwrap_or_else(|e| panic!("{}: {}", name, e));
+        }
+        // Theorem 25 of the basic set is misstated, which is why it is tagged
+        let basic = parse_rules(builtin("basic").unwrap()).unwrap();
+        assert!(reject_unsound(&basic, &enabled(&["unverified"])).unwrap_err().contains("unsound rules: th25 "));
+    }
+
+    #[test]
+    fn finds_unsound_rules() {
+        let specs = parse_rules("ok: (-> ?a ?b) <=> (+ (! ?a) ?b)
+wrong: (+ ?a (* ?a b)) => (* ?a b) @x
+").unwrap();
+        assert!(matches!(specs[0].check().unwrap(), Verdict::Sound));
+        // `b` is a symbol, free like the variables
+        let Verdict::Unsound(assignment) = specs[1].check().unwrap() else { panic!("`wrong` holds") };
+        assert_eq!(assignment, [("?a".to_string(), true), ("b".to_string(), false)]);
+        assert!(reject_unsound(&specs, &HashSet::new()).is_ok());
+        assert!(reject_unsound(&specs, &enabled(&["x"])).unwrap_err().contains("unsound rules: wrong"));
+    }
+}
@@ -0,0 +1,472 @@
+//! Rule scheduling: by yield, or as a multi-armed bandit.
+//!
+//! egg's `BackoffScheduler` bans any rule whose matches exceed a limit, so
+//! associativity and commutativity are treated like every other rule while
+//! they fill the node budget with permutations of terms already there. This
+//! scheduler keeps the same exponential backoff but also tracks, per rule,
+//! how many of its unions merged classes that already existed rather than
+//! classes it had just created, and in which iterations the extracted size
+//! went down. Rules that only permute their operands get smaller limits and
+//! longer bans; rules that were applying when the size went down get larger
+//! limits and shorter bans.
+//!
+//! The bandit scheduler instead decides each iteration which rules run at
+//! all. Every rule, or every group of rules, is an arm; an iteration pulls the
+//! better half of the arms by UCB1 and rewards each by its share of the drop in
+//! extraction cost that followed. What it learnt can be saved and loaded again
+//! for the next run.
+
+use egg::*;
+
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Display};
+use std::rc::Rc;
+
+use crate::extract::GateCost;
+use crate::prop::{ConstantFold, Prop};
+
+const MATCH_LIMIT: usize = 1_000;
+const BAN_LENGTH: usize = 5;
+// boosts stop growing after this many improvements
+const MAX_BOOST: usize = 4;
+
+#[derive(Default)]
+pub struct RuleStats {
+    pub name: String,
+    /// Both sides have the same operators and variables: commutativity,
+    /// associativity and the like.
+    pub permutation: bool,
+    pub searches: usize,
+    pub matches: usize,
+    pub unions: usize,
+    /// E-nodes added by the rule's right-hand sides.
+    pub nodes: usize,
+    /// Unions beyond the nodes the rule added, merges of existing classes.
+    pub merges: usize,
+    /// Iterations in which the rule made unions and the extracted size went down.
+    pub improvements: usize,
+    pub bans: usize,
+    banned_until: usize,
+    // unions in the current iteration
+    recent_unions: usize,
+}
+
+impl RuleStats {
+    fn throttled(&self) -> bool {
+        self.permutation && self.merges == 0
+    }
+}
+
+/// What the scheduler saw, shared with whoever reports it after the run.
+#[derive(Default)]
+pub struct ScheduleStats {
+    pub rules: Vec<RuleStats>,
+    index: HashMap<Symbol, usize>,
+    /// Tree size of the outputs extracted before each iteration.
+    pub costs: Vec<f64>,
+}
+
+impl ScheduleStats {
+    /// One line for the log: throttled and boosted rules, and the size trend.
+    pub fn summary(&self) -> String {
+        let throttled = self.rules.iter().filter(|r| r.throttled()).count();
+        let boosted = self.rules.iter().filter(|r| r.improvements > 0).count();
+        let (first, last) = (self.costs.first().copied().unwrap_or(0.0), self.costs.last().copied().unwrap_or(0.0));
+        format!(
+            "scheduler: {} rules, {} throttled as permutations, {} boosted; extracted size {} -> {} over {} iterations",
+            self.rules.len(), throttled, boosted, first, last, self.costs.len()
+        )
+    }
+}
+
+impl Display for ScheduleStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let costs: Vec<String> = self.costs.iter().map(|c| c.to_string()).collect();
+        writeln!(f, "extracted size per iteration: {}", costs.join(" "))?;
+        writeln!(f, "{:<28} {:>8} {:>10} {:>10} {:>10} {:>10} {:>6} {:>5}", "rule", "searches", "matches", "unions", "nodes", "merges", "better", "bans")?;
+        for rule in &self.rules {
+            let name = if rule.throttled() { format!("{} (perm)", rule.name) } else { rule.name.clone() };
+            writeln!(
+                f,
+                "{:<28} {:>8} {:>10} {:>10} {:>10} {:>10} {:>6} {:>5}",
+                name, rule.searches, rule.matches, rule.unions, rule.nodes, rule.merges, rule.improvements, rule.bans
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// Tree size as a float: deep circuits overflow any integer, and saturated sizes compare equal.
+struct TreeSize;
+
+impl<L: Language + GateCost> CostFunction<L> for TreeSize {
+    type Cost = f64;
+    fn cost<C>(&mut self, enode: &L, mut costs: C) -> Self::Cost
+    where
+        C: FnMut(Id) -> Self::Cost,
+    {
+        enode.fold(enode.gates() as f64, |sum, id| sum + costs(id))
+    }
+}
+
+pub struct YieldScheduler {
+    roots: Vec<Id>,
+    iteration: Option<usize>,
+    stats: Rc<RefCell<ScheduleStats>>,
+}
+
+impl YieldScheduler {
+    /// `roots` are the outputs whose extracted size rewards the rules.
+    pub fn new(roots: &[Id]) -> Self {
+        YieldScheduler { roots: roots.to_vec(), iteration: None, stats: Default::default() }
+    }
+
+    /// A handle on the statistics, which stays valid after the runner took
+    /// the scheduler.
+    pub fn stats(&self) -> Rc<RefCell<ScheduleStats>> {
+        self.stats.clone()
+    }
+
+    // Extract once per iteration and credit the rules of the one before if the size went down.
+    fn start_iteration<L: Language + GateCost, N: Analysis<L>>(&mut self, iteration: usize, egraph: &EGraph<L, N>) {
+        if self.iteration == Some(iteration) {
+            return;
+        }
+        self.iteration = Some(iteration);
+        let extractor = Extractor::new(egraph, TreeSize);
+        let cost: f64 = self.roots.iter().map(|root| extractor.find_best_cost(*root)).sum();
+        let mut stats = self.stats.borrow_mut();
+        let improved = stats.costs.last().is_some_and(|last| cost < *last);
+        for rule in &mut stats.rules {
+            if improved && rule.recent_unions > 0 {
+                rule.improvements += 1;
+            }
+            rule.recent_unions = 0;
+        }
+        stats.costs.push(cost);
+    }
+}
+
+fn is_permutation<L: Language + Display>(lhs: &PatternAst<L>, rhs: &PatternAst<L>) -> bool {
+    let ops = |ast: &PatternAst<L>| {
+        let mut ops: Vec<String> = ast.as_ref().iter().map(|n| n.to_string()).collect();
+        ops.sort();
+        ops
+    };
+    ops(lhs) == ops(rhs)
+}
+
+impl<L: Language + Display + GateCost, N: Analysis<L>> RewriteScheduler<L, N> for YieldScheduler {
+    // like egg's backoff: fast-forward to the first unban instead of stopping
+    fn can_stop(&mut self, iteration: usize) -> bool {
+        let mut stats = self.stats.borrow_mut();
+        let Some(first) = stats.rules.iter().map(|r| r.banned_until).filter(|b| *b > iteration).min() else {
+            return true;
+        };
+        for rule in stats.rules.iter_mut().filter(|r| r.banned_until > iteration) {
+            rule.banned_until -= first - iteration;
+        }
+        false
+    }
+
+    fn search_rewrite<'a>(&mut self, iteration: usize, egraph: &EGraph<L, N>, rewrite: &'a Rewrite<L, N>) -> Vec<SearchMatches<'a, L>> {
+        self.start_iteration(iteration, egraph);
+        let mut stats = self.stats.borrow_mut();
+        let i = match stats.index.get(&rewrite.name) {
+            Some(i) => *i,
+            None => {
+                let permutation = match (rewrite.searcher.get_pattern_ast(), rewrite.applier.get_pattern_ast()) {
+                    (Some(lhs), Some(rhs)) => is_permutation(lhs, rhs),
+                    _ => false,
+                };
+                let i = stats.rules.len();
+                stats.rules.push(RuleStats { name: rewrite.name.to_string(), permutation, ..Default::default() });
+                stats.index.insert(rewrite.name, i);
+                i
+            }
+        };
+        let rule = &mut stats.rules[i];
+        if iteration < rule.banned_until {
+            return vec![];
+        }
+        let (mut limit, mut ban) = (MATCH_LIMIT << rule.bans.min(20), BAN_LENGTH << rule.bans.min(20));
+        if rule.throttled() {
+            limit /= 4;
+            ban *= 2;
+        }
+        let boost = rule.improvements.min(MAX_BOOST);
+        limit <<= boost;
+        ban = (ban >> boost.min(2)).max(1);
+
+        rule.searches += 1;
+        let matches = rewrite.search_with_limit(egraph, limit + 1);
+        let total: usize = matches.iter().map(|m| m.substs.len()).sum();
+        if total > limit {
+            rule.bans += 1;
+            rule.banned_until = iteration + ban;
+            return vec![];
+        }
+        rule.matches += total;
+        matches
+    }
+
+    fn apply_rewrite(&mut self, _iteration: usize, egraph: &mut EGraph<L, N>, rewrite: &Rewrite<L, N>, matches: Vec<SearchMatches<L>>) -> usize {
+        let nodes = egraph.total_size();
+        let unions = rewrite.apply(egraph, &matches).len();
+        let added = egraph.total_size() - nodes;
+        let mut stats = self.stats.borrow_mut();
+        let i = stats.index[&rewrite.name];
+        let rule = &mut stats.rules[i];
+        rule.unions += unions;
+        rule.recent_unions += unions;
+        rule.nodes += added;
+        rule.merges += unions.saturating_sub(added);
+        unions
+    }
+}
+
+/// The arm a rule belongs to: the rule itself, or its group, the name
+/// without `-rev`, primes and a trailing number (`de-morgan2-rev` is in
+/// `de-morgan`).
+pub fn arm_of(rule: &str, groups: bool) -> String {
+    if !groups {
+        return rule.to_string();
+    }
+    let name = rule.strip_suffix("-rev").unwrap_or(rule);
+    let name = name.trim_end_matches('\'').trim_end_matches(|c: char| c.is_ascii_digit());
+    if name.is_empty() { rule.to_string() } else { name.to_string() }
+}
+
+#[derive(Default, Clone)]
+pub struct Arm {
+    pub name: String,
+    /// Iterations in which the arm ran, over this and earlier runs.
+    pub pulls: f64,
+    pub reward: f64,
+    // unions of its rules in the current iteration
+    recent_unions: usize,
+}
+
+impl Arm {
+    fn mean(&self) -> f64 {
+        if self.pulls > 0.0 { self.reward / self.pulls } else { 0.0 }
+    }
+}
+
+/// The arms and what they earned, shared with whoever saves the policy.
+#[derive(Default)]
+pub struct BanditPolicy {
+    pub arms: Vec<Arm>,
+    index: HashMap<String, usize>,
+    /// Extraction cost before each iteration of this run.
+    pub costs: Vec<f64>,
+    /// Arms pulled in each iteration of this run.
+    pub pulled: Vec<usize>,
+}
+
+impl BanditPolicy {
+    /// Read a policy saved by `save`, one `arm <name> <pulls> <reward>` per line.
+    pub fn load(text: &str) -> Result<Self, String> {
+        let mut policy = BanditPolicy::default();
+        for (n, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap();
+            match line.split_whitespace().collect::<Vec<_>>().as_slice() {
+                [] => {}
+                ["arm", name, pulls, reward] => {
+                    let number = |w: &str| match w.parse::<f64>() {
+                        Ok(x) if x.is_finite() && x >= 0.0 => Ok(x),
+                        _ => Err(format!("line {}: `{}` is not a non-negative number", n + 1, w)),
+                    };
+                    let arm = Arm { name: name.to_string(), pulls: number(pulls)?, reward: number(reward)?, ..Default::default() };
+                    policy.index.insert(arm.name.clone(), policy.arms.len());
+                    policy.arms.push(arm);
+                }
+                _ => return Err(format!("line {}: expected `arm <name> <pulls> <reward>`", n + 1)),
+            }
+        }
+        Ok(policy)
+    }
+
+    pub fn save(&self) -> String {
+        let mut text = String::from("# e-rewriter bandit policy: arm <name> <pulls> <reward>\n");
+        for arm in &self.arms {
+            text.push_str(&format!("arm {} {} {}\n", arm.name, arm.pulls, arm.reward));
+        }
+        text
+    }
+
+    fn arm(&mut self, name: &str) -> usize {
+        if let Some(i) = self.index.get(name) {
+            return *i;
+        }
+        self.arms.push(Arm { name: name.to_string(), ..Default::default() });
+        self.index.insert(name.to_string(), self.arms.len() - 1);
+        self.arms.len() - 1
+    }
+
+    /// One line for the log: the arms pulled and the cost trend.
+    pub fn summary(&self) -> String {
+        let (first, last) = (self.costs.first().copied().unwrap_or(0.0), self.costs.last().copied().unwrap_or(0.0));
+        let pulled: Vec<String> = self.pulled.iter().map(|p| p.to_string()).collect();
+        format!("bandit: {} arms, pulled per iteration: {}; cost {} -> {}", self.arms.len(), pulled.join(" "), first, last)
+    }
+}
+
+impl Display for BanditPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut arms: Vec<&Arm> = self.arms.iter().collect();
+        arms.sort_by(|a, b| b.mean().total_cmp(&a.mean()).then(a.name.cmp(&b.name)));
+        writeln!(f, "{:<28} {:>8} {:>12} {:>12}", "arm", "pulls", "reward", "mean")?;
+        for arm in arms {
+            writeln!(f, "{:<28} {:>8} {:>12.6} {:>12.6}", arm.name, arm.pulls, arm.reward, arm.mean())?;
+        }
+        Ok(())
+    }
+}
+
+/// Extraction cost of the outputs in an e-graph, lower is better.
+pub type CostFn = Box<dyn FnMut(&EGraph<Prop, ConstantFold>, &[Id]) -> f64>;
+
+pub struct BanditScheduler {
+    roots: Vec<Id>,
+    groups: bool,
+    cost: CostFn,
+    // searches of the pulled arms go through egg's backoff, which keeps the explosive rules in check
+    backoff: BackoffScheduler,
+    iteration: Option<usize>,
+    // the arms of this run's rules; the policy may know others from earlier runs
+    arms: Vec<usize>,
+    active: HashSet<usize>,
+    // the runner saturated with arms left out: pull them all once before stopping
+    pull_all: bool,
+    policy: Rc<RefCell<BanditPolicy>>,
+}
+
+impl BanditScheduler {
+    /// `cost` scores the outputs `roots` before every iteration; arms are
+    /// the `rules`, or groups of them if `groups`.
+    pub fn new(roots: &[Id], rules: &[Rewrite<Prop, ConstantFold>], groups: bool, cost: CostFn, mut policy: BanditPolicy) -> Self {
+        let mut arms: Vec<usize> = rules.iter().map(|r| policy.arm(&arm_of(r.name.as_str(), groups))).collect();
+        arms.sort();
+        arms.dedup();
+        BanditScheduler {
+            roots: roots.to_vec(),
+            groups,
+            cost,
+            backoff: BackoffScheduler::default(),
+            iteration: None,
+            arms,
+            active: HashSet::new(),
+            pull_all: false,
+            policy: Rc::new(RefCell::new(policy)),
+        }
+    }
+
+    /// A handle on the policy, which stays valid after the runner took the
+    /// scheduler.
+    pub fn policy(&self) -> Rc<RefCell<BanditPolicy>> {
+        self.policy.clone()
+    }
+
+    // Reward the arms of the iteration before, then pick the arms of this one.
+    fn start_iteration(&mut self, iteration: usize, egraph: &EGraph<Prop, ConstantFold>) {
+        if self.iteration == Some(iteration) {
+            return;
+        }
+        self.iteration = Some(iteration);
+        let cost = (self.cost)(egraph, &self.roots);
+        let mut policy = self.policy.borrow_mut();
+        let policy = &mut *policy;
+        // the relative drop in cost, shared by the unions each pulled arm made
+        if let Some(last) = policy.costs.last().copied() {
+            let gain = if last > 0.0 { ((last - cost) / last).max(0.0) } else { 0.0 };
+            let unions: usize = self.active.iter().map(|i| policy.arms[*i].recent_unions).sum();
+            for i in &self.active {
+                let arm = &mut policy.arms[*i];
+                arm.pulls += 1.0;
+                if unions > 0 {
+                    arm.reward += gain * arm.recent_unions as f64 / unions as f64;
+                }
+                arm.recent_unions = 0;
+            }
+        }
+        policy.costs.push(cost);
+
+        // UCB1, exploring on the scale of the best mean so far
+        let arms = &policy.arms;
+        let rounds: f64 = self.arms.iter().map(|i| arms[*i].pulls).sum::<f64>().max(1.0);
+        let scale = self.arms.iter().map(|i| arms[*i].mean()).fold(0.0, f64::max).max(1e-6);
+        let score = |arm: &Arm| if arm.pulls == 0.0 { f64::INFINITY } else { arm.mean() + scale * (2.0 * rounds.ln() / arm.pulls).sqrt() };
+        let mut order = self.arms.clone();
+        order.sort_by(|a, b| {
+            let (a, b) = (&arms[*a], &arms[*b]);
+            score(b).total_cmp(&score(a)).then(a.pulls.total_cmp(&b.pulls))
+        });
+        let pulls = if self.pull_all { order.len() } else { order.len().div_ceil(2) };
+        self.pull_all = false;
+        self.active = order.into_iter().take(pulls).collect();
+        policy.pulled.push(self.active.len());
+    }
+}
+
+impl RewriteScheduler<Prop, ConstantFold> for BanditScheduler {
+    fn can_stop(&mut self, iteration: usize) -> bool {
+        if self.active.len() < self.arms.len() {
+            self.pull_all = true;
+            return false;
+        }
+        RewriteScheduler::<Prop, ConstantFold>::can_stop(&mut self.backoff, iteration)
+    }
+
+    fn search_rewrite<'a>(&mut self, iteration: usize, egraph: &EGraph<Prop, ConstantFold>, rewrite: &'a Rewrite<Prop, ConstantFold>) -> Vec<SearchMatches<'a, Prop>> {
+        self.start_iteration(iteration, egraph);
+        let arm = self.policy.borrow().index[&arm_of(rewrite.name.as_str(), self.groups)];
+        if !self.active.contains(&arm) {
+            return vec![];
+        }
+        self.backoff.search_rewrite(iteration, egraph, rewrite)
+    }
+
+    fn apply_rewrite(&mut self, iteration: usize, egraph: &mut EGraph<Prop, ConstantFold>, rewrite: &Rewrite<Prop, ConstantFold>, matches: Vec<SearchMatches<Prop>>) -> usize {
+        let unions = self.backoff.apply_rewrite(iteration, egraph, rewrite, matches);
+        let mut policy = self.policy.borrow_mut();
+        let arm = policy.index[&arm_of(rewrite.name.as_str(), self.groups)];
+        policy.arms[arm].recent_unions += unions;
+        unions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_rules_into_arms() {
+        assert_eq!(arm_of("de-morgan2-rev", true), "de-morgan");
+        assert_eq!(arm_of("th25'", true), "th");
+        assert_eq!(arm_of("42", true), "42");
+        assert_eq!(arm_of("de-morgan2-rev", false), "de-morgan2-rev");
+    }
+
+    #[test]
+    fn loads_what_it_saves() {
+        let policy = BanditPolicy::load("# comment\narm assoc 3 1.5\n\narm de-morgan 2 0 # trailing\n").unwrap();
+        assert_eq!(policy.arms.len(), 2);
+        assert_eq!((policy.arms[0].pulls, policy.arms[0].reward), (3.0, 1.5));
+        assert_eq!(policy.save(), "# e-rewriter bandit policy: arm <name> <pulls> <reward>\narm assoc 3 1.5\narm de-morgan 2 0\n");
+        // ranked by mean reward
+        let table = policy.to_string();
+        assert!(table.find("assoc").unwrap() < table.find("de-morgan").unwrap());
+    }
+
+    #[test]
+    fn rejects_values_that_are_not_counts() {
+        for value in ["NaN", "inf", "-1", "x"] {
+            let err = BanditPolicy::load(&format!("arm assoc 1 0\narm comm {} 1\n", value)).err().unwrap_or_else(|| panic!("loaded {}", value));
+            assert_eq!(err, format!("line 2: `{}` is not a non-negative number", value));
+            assert!(BanditPolicy::load(&format!("arm comm 1 {}\n", value)).is_err());
+        }
+        assert!(BanditPolicy::load("arm comm 1\n").is_err());
+    }
+}
@@ -0,0 +1,84 @@
+//! Size and depth of a circuit, as `esyn analyze` and `esyn bench` report them.
+
+use egg::*;
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+
+use crate::extract::GateCost;
+use crate::liberty::{circuit_area, circuit_delay, Library};
+use crate::netlist::{all_inputs, NodeTable};
+
+/// Counts over the circuit as a DAG, logic shared between outputs counts once.
+pub struct Stats {
+    pub inputs: usize,
+    pub outputs: usize,
+    /// Operator nodes, as the AND/OR/NOT gates they are written as.
+    pub nodes: usize,
+    /// Gates on the longest input-to-output path.
+    pub depth: usize,
+    pub ops: BTreeMap<String, usize>,
+    /// Cell area and delay, with a Liberty library.
+    pub area: Option<f64>,
+    pub delay: Option<f64>,
+}
+
+impl Stats {
+    pub fn new<L: Language + Display + GateCost>(inputs: &[String], exprs: &[RecExpr<L>], library: Option<&Library>) -> Self {
+        let mut table = NodeTable::new();
+        let roots: Vec<Id> = exprs.iter().map(|expr| table.add_expr(expr)).collect();
+        // children come before their parents in the table
+        let mut level = vec![0usize; table.nodes.len()];
+        let mut ops = BTreeMap::new();
+        let mut nodes = 0;
+        for (id, node) in table.nodes.iter().enumerate() {
+            if !node.is_leaf() {
+                level[id] = node.levels() + node.children().iter().map(|c| level[usize::from(*c)]).max().unwrap_or(0);
+                *ops.entry(node.to_string()).or_insert(0) += 1;
+                nodes += node.gates();
+            }
+        }
+        Stats {
+            inputs: all_inputs(inputs, exprs).len(),
+            outputs: exprs.len(),
+            nodes,
+            depth: roots.iter().map(|r| level[usize::from(*r)]).max().unwrap_or(0),
+            ops,
+            area: library.map(|library| circuit_area(library, exprs)),
+            delay: library.map(|library| circuit_delay(library, exprs)),
+        }
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "inputs: {}", self.inputs)?;
+        writeln!(f, "outputs: {}", self.outputs)?;
+        writeln!(f, "nodes: {}", self.nodes)?;
+        writeln!(f, "depth: {}", self.depth)?;
+        for (op, count) in &self.ops {
+            writeln!(f, "  {:>4}: {}", op, count)?;
+        }
+        if let (Some(area), Some(delay)) = (self.area, self.delay) {
+            writeln!(f, "area: {}", area)?;
+            writeln!(f, "delay: {}", delay)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prop::Prop;
+
+    #[test]
+    fn counts_the_gates_operators_are_written_as() {
+        let exprs: Vec<RecExpr<Prop>> = vec!["(^ a (* a b))".parse().unwrap(), "(maj a b c)".parse().unwrap()];
+        let stats = Stats::new(&[], &exprs, None);
+        assert_eq!((stats.inputs, stats.outputs), (3, 2));
+        assert_eq!(stats.nodes, 5 + 1 + 4);
+        assert_eq!(stats.depth, 1 + 3);
+        assert_eq!(stats.ops.values().sum::<usize>(), 3);
+    }
+}
@@ -0,0 +1,239 @@
+//! Simulation-guided merging of equivalent e-classes during saturation.
+//!
+//! Every e-class carries the values it takes on random input patterns; the
+//! sweep proves the classes whose values agree equivalent and unions them,
+//! so that rewrites found on one term apply to all its equivalents.
+
+use egg::*;
+
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
+
+use crate::cec;
+use crate::prop::{ConstantFold, Prop};
+
+pub const SIG_WORDS: usize = 4;
+
+// candidates proven per sweep, the rest wait for the next one
+const MAX_CANDIDATES: usize = 1000;
+
+/// Values of an e-class on 256 random input patterns, one bit per pattern.
+pub type Signature = [u64; SIG_WORDS];
+
+// splitmix64, so an input's patterns only depend on the seed and its name
+fn mix(mut z: u64) -> u64 {
+    z = z.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+fn input_signature(seed: u64, name: &str) -> Signature {
+    let mut h = seed;
+    for b in name.bytes() {
+        h = mix(h ^ b as u64);
+    }
+    let mut sig = [0; SIG_WORDS];
+    for word in sig.iter_mut() {
+        h = mix(h);
+        *word = h;
+    }
+    sig
+}
+
+fn lift(f: impl Fn(usize) -> u64) -> Signature {
+    let mut sig = [0; SIG_WORDS];
+    for (i, word) in sig.iter_mut().enumerate() {
+        *word = f(i);
+    }
+    sig
+}
+
+/// Signature of `enode` from the signatures of its children.
+pub fn signature(enode: &Prop, seed: u64, child: impl Fn(Id) -> Signature) -> Signature {
+    let x = |id: &Id| child(*id);
+    match enode {
+        Prop::Bool(c) => lift(|_| if *c { !0 } else { 0 }),
+        Prop::Symbol(s) => match s.as_str() {
+            "0" => [0; SIG_WORDS],
+            "1" => [!0; SIG_WORDS],
+            name => input_signature(seed, name),
+        },
+        Prop::And([a, b]) => {
+            let (a, b) = (x(a), x(b));
+            lift(|i| a[i] & b[i])
+        }
+        Prop::Or([a, b]) => {
+            let (a, b) = (x(a), x(b));
+            lift(|i| a[i] | b[i])
+        }
+        Prop::Not(a) => {
+            let a = x(a);
+            lift(|i| !a[i])
+        }
+        Prop::Implies([a, b]) => {
+            let (a, b) = (x(a), x(b));
+            lift(|i| !a[i] | b[i])
+        }
+        Prop::Let([a, b]) | Prop::Xnor([a, b]) => {
+            let (a, b) = (x(a), x(b));
+            lift(|i| !(a[i] ^ b[i]))
+        }
+        Prop::Concat([a, b]) => {
+            let (a, b) = (x(a), x(b));
+            lift(|i| a[i] & !b[i])
+        }
+        Prop::Xor([a, b]) => {
+            let (a, b) = (x(a), x(b));
+            lift(|i| a[i] ^ b[i])
+        }
+        Prop::Mux([s, a, b]) => {
+            let (s, a, b) = (x(s), x(a), x(b));
+            lift(|i| (s[i] & a[i]) | (!s[i] & b[i]))
+        }
+        Prop::Maj([a, b, c]) => {
+            let (a, b, c) = (x(a), x(b), x(c));
+            lift(|i| (a[i] & b[i]) | (c[i] & (a[i] | b[i])))
+        }
+    }
+}
+
+// Is `!b` already in class `a`?
+fn is_not_of(egraph: &EGraph<Prop, ConstantFold>, a: Id, b: Id) -> bool {
+    egraph.lookup(Prop::Not(b)).is_some_and(|n| egraph.find(n) == a)
+}
+
+/// FRAIG-style sweeping: e-classes whose signatures collide, directly or
+/// complemented, are candidate equivalences. A candidate is proven on the
+/// cheapest term of each class (exhaustively for small supports, by SAT
+/// otherwise) before the classes are unioned, a complemented match through
+/// a new `!` node.
+#[derive(Default)]
+pub struct Sweeper {
+    refuted: HashSet<(Id, Id, bool)>,
+    pub merged: usize,
+}
+
+impl Sweeper {
+    /// Returns the number of unions made, the e-graph is rebuilt afterwards.
+    /// Proving stops at the `deadline`, unproven candidates are kept for the
+    /// next sweep.
+    pub fn sweep(&mut self, egraph: &mut EGraph<Prop, ConstantFold>, deadline: Instant) -> Result<usize, String> {
+        if Instant::now() > deadline {
+            return Ok(0);
+        }
+        let mut buckets: HashMap<Signature, Vec<(Id, bool)>> = HashMap::new();
+        let mut ids: Vec<Id> = egraph.classes().map(|c| c.id).collect();
+        ids.sort();
+        for id in ids {
+            let sig = egraph[id].data.sig;
+            // normalise so that the first pattern is 0, a class and its complement share a bucket
+            let complemented = sig[0] & 1 == 1;
+            let key = if complemented { lift(|i| !sig[i]) } else { sig };
+            buckets.entry(key).or_default().push((id, complemented));
+        }
+
+        let mut candidates = Vec::new();
+        for members in buckets.values().filter(|m| m.len() > 1) {
+            let (rep, rep_complemented) = members[0];
+            for &(id, complemented) in &members[1..] {
+                let key = (rep, id, rep_complemented != complemented);
+                if key.2 && (is_not_of(egraph, id, rep) || is_not_of(egraph, rep, id)) {
+                    continue;
+                }
+                if !self.refuted.contains(&key) {
+                    candidates.push(key);
+                }
+            }
+        }
+        if candidates.is_empty() {
+            return Ok(0);
+        }
+        candidates.sort();
+
+        // the smallest term of each class, as the analysis keeps it
+        let term = |id: Id| egraph[id].data.term.build_recexpr(|c| egraph[c].data.term.clone());
+        let mut unions = Vec::new();
+        for (rep, id, complemented) in candidates.into_iter().take(MAX_CANDIDATES) {
+            if Instant::now() > deadline {
+                break;
+            }
+            let a = term(rep);
+            let mut b = term(id);
+            if complemented {
+                let root = Id::from(b.as_ref().len() - 1);
+                b.add(Prop::Not(root));
+            }
+            if cec::prove_equal(&a, &b)? {
+                unions.push((rep, id, complemented));
+            } else {
+                self.refuted.insert((rep, id, complemented));
+            }
+        }
+
+        let mut merged = 0;
+        for (rep, id, complemented) in unions {
+            let rep = if complemented { egraph.add(Prop::Not(rep)) } else { rep };
+            if egraph.union_trusted(id, rep, "fraig") {
+                merged += 1;
+            }
+        }
+        egraph.rebuild();
+        self.merged += merged;
+        Ok(merged)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn egraph(exprs: &[&str]) -> (EGraph<Prop, ConstantFold>, Vec<Id>) {
+        let mut egraph = EGraph::new(ConstantFold { sim_seed: 7 });
+        let ids = exprs.iter().map(|e| egraph.add_expr(&e.parse().unwrap())).collect();
+        egraph.rebuild();
+        (egraph, ids)
+    }
+
+    #[test]
+    fn signatures_follow_the_operators() {
+        let (egraph, ids) = egraph(&["(^ a b)", "(+ (* a (! b)) (* (! a) b))", "(mux s a b)", "(maj a b c)"]);
+        assert_eq!(egraph[ids[0]].data.sig, egraph[ids[1]].data.sig);
+        let (a, b) = (input_signature(7, "a"), input_signature(7, "b"));
+        let s = input_signature(7, "s");
+        assert_eq!(egraph[ids[2]].data.sig, lift(|i| (s[i] & a[i]) | (!s[i] & b[i])));
+    }
+
+    #[test]
+    fn merges_proven_equivalences() {
+        let (mut egraph, ids) = egraph(&["(* a b)", "(* b a)", "(! (+ (! a) (! b)))", "(+ (! a) (! b))", "(+ a b)"]);
+        let mut sweeper = Sweeper::default();
+        let merged = sweeper.sweep(&mut egraph, Instant::now() + Duration::from_secs(60)).unwrap();
+        assert!(merged >= 2);
+        assert_eq!(egraph.find(ids[0]), egraph.find(ids[1]));
+        assert_eq!(egraph.find(ids[0]), egraph.find(ids[2]));
+        // complemented match, through a `!` of the representative
+        let (ab, nab) = (egraph.find(ids[0]), egraph.find(ids[3]));
+        assert!(is_not_of(&egraph, nab, ab) || is_not_of(&egraph, ab, nab));
+        assert_ne!(egraph.find(ids[0]), egraph.find(ids[4]));
+    }
+
+    #[test]
+    fn stops_at_the_deadline() {
+        let (mut egraph, ids) = egraph(&["(* a b)", "(* b a)"]);
+        let mut sweeper = Sweeper::default();
+        assert_eq!(sweeper.sweep(&mut egraph, Instant::now() - Duration::from_secs(1)).unwrap(), 0);
+        assert_ne!(egraph.find(ids[0]), egraph.find(ids[1]));
+    }
+
+    #[test]
+    fn analysis_keeps_the_smallest_term() {
+        let (mut egraph, ids) = egraph(&["(! (! (* a b)))", "(* a b)"]);
+        egraph.union(ids[0], ids[1]);
+        egraph.rebuild();
+        let data = &egraph[ids[0]].data;
+        assert_eq!(data.size, 3);
+        assert_eq!(data.term.build_recexpr(|c| egraph[c].data.term.clone()).to_string(), "(* a b)");
+    }
+}
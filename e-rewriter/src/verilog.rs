@@ -0,0 +1,255 @@
+//! Structural Verilog output.
+//!
+//! A circuit is written as one module of continuous assignments. Logic read
+//! more than once becomes a named wire (`new_n*_`, as in ABC's netlists),
+//! everything else is written inline in the assignment that reads it. Port
+//! names of the form `a[77]` are regrouped into bus ports, and names that
+//! are not Verilog identifiers are written as escaped identifiers.
+
+use egg::*;
+
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use crate::netlist::{all_inputs, default_names, NodeTable};
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+fn escape(name: &str) -> String {
+    if is_identifier(name) {
+        name.to_string()
+    } else {
+        format!("\\{} ", name)
+    }
+}
+
+// `a[77]` as ("a", 77)
+fn bus_bit(name: &str) -> Option<(&str, u32)> {
+    let (base, index) = name.strip_suffix(']')?.rsplit_once('[')?;
+    Some((base, index.parse().ok()?)).filter(|(base, _)| is_identifier(base))
+}
+
+struct PortDecl {
+    direction: &'static str,
+    base: String,
+    // msb and lsb of a bus
+    range: Option<(u32, u32)>,
+}
+
+/// Port declarations, and how each port name is referenced in the body.
+struct Ports {
+    decls: Vec<PortDecl>,
+    refs: HashMap<String, String>,
+}
+
+fn group_ports(inputs: &[String], outputs: &[String]) -> Ports {
+    let ports: Vec<(&'static str, &String)> =
+        inputs.iter().map(|n| ("input", n)).chain(outputs.iter().map(|n| ("output", n))).collect();
+    // a base can only become a bus if every name using it is a bit of the same direction
+    let mut bases: HashMap<&str, Option<&str>> = HashMap::new();
+    for (direction, name) in &ports {
+        match bus_bit(name) {
+            Some((base, _)) => {
+                let entry = bases.entry(base).or_insert(Some(direction));
+                if *entry != Some(*direction) {
+                    *entry = None;
+                }
+            }
+            None => {
+                bases.insert(name.as_str(), None);
+            }
+        }
+    }
+
+    let mut decls: Vec<PortDecl> = Vec::new();
+    let mut bus_index: HashMap<&str, usize> = HashMap::new();
+    let mut refs = HashMap::new();
+    for (direction, name) in ports {
+        match bus_bit(name) {
+            Some((base, bit)) if bases[base].is_some() => {
+                let i = *bus_index.entry(base).or_insert_with(|| {
+                    decls.push(PortDecl { direction, base: base.to_string(), range: Some((bit, bit)) });
+                    decls.len() - 1
+                });
+                if let Some((msb, lsb)) = &mut decls[i].range {
+                    *msb = (*msb).max(bit);
+                    *lsb = (*lsb).min(bit);
+                }
+                refs.insert(name.clone(), format!("{}[{}]", base, bit));
+            }
+            _ => {
+                decls.push(PortDecl { direction, base: name.clone(), range: None });
+                refs.insert(name.clone(), escape(name));
+            }
+        }
+    }
+    Ports { decls, refs }
+}
+
+// Verilog for an operator over the text of its children.
+fn operator_text(op: &str, x: &[String]) -> Option<String> {
+    let text = match (op, x) {
+        ("!", [a]) => format!("~{}", a),
+        ("*", [a, b]) => format!("({} & {})", a, b),
+        ("+", [a, b]) => format!("({} | {})", a, b),
+        ("->", [a, b]) => format!("(~{} | {})", a, b),
+        ("let" | "xnor", [a, b]) => format!("~({} ^ {})", a, b),
+        ("&", [a, b]) => format!("({} & ~{})", a, b),
+        ("^", [a, b]) => format!("({} ^ {})", a, b),
+        ("mux", [s, a, b]) => format!("({} ? {} : {})", s, a, b),
+        ("maj", [a, b, c]) => format!("(({} & {}) | ({} & {}) | ({} & {}))", a, b, a, c, b, c),
+        _ => return None,
+    };
+    Some(text)
+}
+
+/// Write a multi-output circuit as a Verilog module.
+///
+/// `inputs` fixes the order of the input ports, symbols of the expressions
+/// that are not in it follow sorted by name. Without `outputs` names, the
+/// outputs are called `po0`, `po1`, ...
+pub fn write_verilog<L: Language + Display>(module: &str, inputs: &[String], outputs: &[String], exprs: &[RecExpr<L>]) -> Result<String, String> {
+    let inputs = all_inputs(inputs, exprs);
+    let outputs = if outputs.is_empty() { default_names("po", exprs.len()) } else { outputs.to_vec() };
+    let ports = group_ports(&inputs, &outputs);
+
+    let mut table = NodeTable::new();
+    let roots: Vec<Id> = exprs.iter().map(|expr| table.add_expr(expr)).collect();
+
+    // how often the text of each node is read; `maj` reads its operands twice
+    let mut reads = vec![0usize; table.nodes.len()];
+    for node in &table.nodes {
+        let weight = if node.to_string() == "maj" { 2 } else { 1 };
+        for c in node.children() {
+            reads[usize::from(*c)] += weight;
+        }
+    }
+
+    // nodes that get a name: outputs, and operators read more than once
+    let mut names: HashMap<Id, String> = HashMap::new();
+    for (root, name) in roots.iter().zip(&outputs) {
+        if !table.nodes[usize::from(*root)].is_leaf() {
+            names.entry(*root).or_insert_with(|| ports.refs[name].clone());
+        }
+    }
+    let mut wires = Vec::new();
+    for (id, node) in table.nodes.iter().enumerate() {
+        if reads[id] > 1 && !node.is_leaf() {
+            names.entry(Id::from(id)).or_insert_with(|| {
+                wires.push(format!("new_n{}_", id));
+                format!("new_n{}_", id)
+            });
+        }
+    }
+
+    let mut text: Vec<String> = Vec::with_capacity(table.nodes.len());
+    let mut assigns = Vec::new();
+    for (id, node) in table.nodes.iter().enumerate() {
+        let op = node.to_string();
+        let expr = if node.is_leaf() {
+            match op.as_str() {
+                "0" | "false" => "1'b0".to_string(),
+                "1" | "true" => "1'b1".to_string(),
+                name => ports.refs.get(name).cloned().unwrap_or_else(|| escape(name)),
+            }
+        } else {
+            // inline operators are read exactly once, so their text can be moved
+            let x: Vec<String> = node
+                .children()
+                .iter()
+                .map(|c| match names.get(c) {
+                    Some(name) => name.clone(),
+                    None if table.nodes[usize::from(*c)].is_leaf() => text[usize::from(*c)].clone(),
+                    None => std::mem::take(&mut text[usize::from(*c)]),
+                })
+                .collect();
+            operator_text(&op, &x).ok_or_else(|| format!("cannot write `{}` to Verilog", op))?
+        };
+        match names.get(&Id::from(id)) {
+            Some(name) => {
+                assigns.push(format!("  assign {} = {};\n", name, expr));
+                text.push(name.clone());
+            }
+            None => text.push(expr),
+        }
+    }
+    // outputs driven by an input, a constant or the same logic as an earlier output
+    for (root, name) in roots.iter().zip(&outputs) {
+        let port = &ports.refs[name];
+        if names.get(root) != Some(port) {
+            let driver = names.get(root).cloned().unwrap_or_else(|| text[usize::from(*root)].clone());
+            assigns.push(format!("  assign {} = {};\n", port, driver));
+        }
+    }
+
+    let port_list: Vec<String> = ports.decls.iter().map(|decl| escape(&decl.base)).collect();
+    let mut out = format!("module {}({});\n", escape(module), port_list.join(", "));
+    for decl in &ports.decls {
+        match decl.range {
+            Some((msb, lsb)) => out.push_str(&format!("  {} [{}:{}] {};\n", decl.direction, msb, lsb, decl.base)),
+            None => out.push_str(&format!("  {} {};\n", decl.direction, escape(&decl.base))),
+        }
+    }
+    for wire in &wires {
+        out.push_str(&format!("  wire {};\n", wire));
+    }
+    for assign in assigns {
+        out.push_str(&assign);
+    }
+    out.push_str("endmodule\n");
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eqn::parse_eqn;
+    use crate::prop::{Circuit, Prop};
+
+    #[test]
+    fn writes_c17() {
+        let c17: Circuit = parse_eqn(include_str!("../../benchmark/converted_circuit/ISCAS85/c17.eqn")).unwrap();
+        let text = write_verilog("c17", &c17.inputs, &c17.outputs, &c17.exprs).unwrap();
+        assert_eq!(
+            text,
+            concat!(
+                "module c17(pi0, pi1, pi2, pi3, pi4, po0, po1);\n",
+                "  input pi0;\n  input pi1;\n  input pi2;\n  input pi3;\n  input pi4;\n",
+                "  output po0;\n  output po1;\n",
+                "  wire new_n5_;\n  wire new_n6_;\n",
+                "  assign new_n5_ = ~(pi2 & pi3);\n",
+                "  assign new_n6_ = (pi1 & new_n5_);\n",
+                "  assign po0 = ((pi0 & pi2) | new_n6_);\n",
+                "  assign po1 = (new_n6_ | (pi4 & new_n5_));\n",
+                "endmodule\n",
+            )
+        );
+    }
+
+    #[test]
+    fn groups_buses_and_escapes_names() {
+        let exprs: Vec<RecExpr<Prop>> = ["(* a[0] (! (+ a[1] b)))", "(maj (! (+ a[1] b)) 1x c[2])", "a[0]", "true"].iter().map(|e| e.parse().unwrap()).collect();
+        let outputs = ["y[1]", "y[0]", "z", "c[3]"].map(String::from);
+        let text = write_verilog("t", &["a[0]".to_string(), "a[1]".to_string()], &outputs, &exprs).unwrap();
+        // `c` has bits in both directions, so it is no bus
+        assert_eq!(
+            text,
+            concat!(
+                "module t(a, \\1x , b, \\c[2] , y, z, \\c[3] );\n",
+                "  input [1:0] a;\n  input \\1x ;\n  input b;\n  input \\c[2] ;\n",
+                "  output [1:0] y;\n  output z;\n  output \\c[3] ;\n",
+                "  wire new_n4_;\n",
+                "  assign new_n4_ = ~(a[1] | b);\n",
+                "  assign y[1] = (a[0] & new_n4_);\n",
+                "  assign y[0] = ((new_n4_ & \\1x ) | (new_n4_ & \\c[2] ) | (\\1x  & \\c[2] ));\n",
+                "  assign z = a[0];\n",
+                "  assign \\c[3]  = 1'b1;\n",
+                "endmodule\n",
+            )
+        );
+    }
+}
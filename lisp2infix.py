@@ -10,6 +10,10 @@ class PropParser(object):
             | ( * prop prop )
             | ( + prop prop )
             | ( & prop prop )
+            | ( ^ prop prop )
+            | ( xnor prop prop )
+            | ( mux prop prop prop )
+            | ( maj prop prop prop )
             | ( ! prop )
     """
 
@@ -58,6 +62,23 @@ class PropParser(object):
         "prop : LPAREN NOT prop RPAREN"
         p[0] = f"(!{p[3]})"
 
+    # ABC eqn has no XOR/MUX/MAJ, so they are expanded to AND/OR/NOT
+    def p_prop_xor(self, p):
+        "prop : LPAREN XOR prop prop RPAREN"
+        p[0] = f"(({p[3]} * (!{p[4]})) + ((!{p[3]}) * {p[4]}))"
+
+    def p_prop_xnor(self, p):
+        "prop : LPAREN XNOR prop prop RPAREN"
+        p[0] = f"(({p[3]} * {p[4]}) + ((!{p[3]}) * (!{p[4]})))"
+
+    def p_prop_mux(self, p):
+        "prop : LPAREN MUX prop prop prop RPAREN"
+        p[0] = f"(({p[3]} * {p[4]}) + ((!{p[3]}) * {p[5]}))"
+
+    def p_prop_maj(self, p):
+        "prop : LPAREN MAJ prop prop prop RPAREN"
+        p[0] = f"(({p[3]} * {p[4]}) + ({p[5]} * ({p[3]} + {p[4]})))"
+
     def p_term_id(self, p):
         "term : id"
         p[0] = p[1]
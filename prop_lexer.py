@@ -1,13 +1,18 @@
 import ply.lex as lex
 
 class PropLexer(object):
-    tokens = ("AND", "OR", "NOT", "CONCAT", "SYMBOL", "LPAREN", "RPAREN")
+    tokens = ("AND", "OR", "NOT", "CONCAT", "XOR", "XNOR", "MUX", "MAJ", "COMMA", "SYMBOL", "LPAREN", "RPAREN")
+
+    # operator names that would otherwise lex as symbols
+    reserved = {"xnor": "XNOR", "mux": "MUX", "maj": "MAJ"}
 
     # Regular expression rules for simple tokens
     t_AND = r"\*"
     t_OR = r"\+"
     t_CONCAT = r"\&"
     t_NOT = r"!"
+    t_XOR = r"\^"
+    t_COMMA = r","
     t_LPAREN = r"\("
     t_RPAREN = r"\)"
 
@@ -17,6 +22,7 @@ class PropLexer(object):
         #r"[a-zA-Z_?][a-zA-Z_0-9]*" 
         r"[a-zA-Z_0-9?][a-zA-Z_0-9\[\]]*"
         t.value = str(t.value)  # Converting symbol to string
+        t.type = self.reserved.get(t.value, "SYMBOL")
         return t
 
     def t_error(self, t):
@@ -228,38 +228,6 @@ if __name__ == "__main__":
     # parser =  CircuitParser.CircuitParser(input_file_path, output_file_path)
     # parser.process()
     
-    # load file to convert to s-expression (test)
-    with open ("test_data_beta_runner/original_circuit.eqn", "r") as myfile:
-        # read line by line
-        data=myfile.readlines()
-        
-    '''
-    #############################################################################
-    #
-    #                    Pre-processing the circuit for egg ....
-    #
-    #############################################################################
-    '''
-        
-    # if data[2] is 'OUTORDER = po0;\n':
-    if len(data[2].split(" = ")[1].rstrip().strip(";").split()) == 1:
-        # one output circuit
-        
-        conver_to_sexpr(data[3]) # put the only one equation to the function
-        FORMULA_LIST = None
-   
-    else:
-        # multiple output circuit
-        print("multiple output circuit")
-        multiple_output_flag = True
-        
-        # load all the content to `convert_to_sexpr()`
-        # file to input string
-        #FORMULA_LIST = conver_to_sexpr(data, multiple_output = multiple_output_flag)  
-        os.system("alpha_utils/infix2lisp/target/release/s-converter test_data_beta_runner/input_for_s-converter.txt test_data_beta_runner/sexpr_for_egg.txt")
-          
-        
-
     '''
     #############################################################################
     #
@@ -267,24 +235,8 @@ if __name__ == "__main__":
     #
     #############################################################################
     '''
-    # run egg 
-    os.system("e-rewriter/target/release/e-rewriter test_data_beta_runner/sexpr_for_egg.txt test_data_beta_runner/output_from_egg.txt test_data_beta_runner")
-    
-    '''
-    #############################################################################
-    #
-    #                  Post-processing the circuit for abc .... 
-    #
-    #############################################################################
-    '''
-    
-    # use concurrent futures to convert the s-expression to abc eqn
-    tasks_args = [(data, None, multiple_output_flag, i) for i in range(10)]
-    #convert_to_abc_eqn(data, None, multiple_output= multiple_output_flag, index = 0)
-    with concurrent.futures.ProcessPoolExecutor(64) as executor:
-        for task in tasks_args:
-            executor.submit(convert_to_abc_eqn, *task)
-        
+    # run egg, it reads and writes ABC's eqn directly (outputs keep their names, shared logic becomes new_n*_ signals)
+    os.system("e-rewriter/target/release/e-rewriter test_data_beta_runner/original_circuit.eqn test_data_beta_runner/optimized_circuit.eqn test_data_beta_runner")
     
     '''
     #############################################################################
@@ -320,7 +272,7 @@ if __name__ == "__main__":
     
     ################## st + dch ######################
     for i in range(10):
-        os.system(f"./abc/abc -c \"read_eqn test_data_beta_runner/optimized_circuit_{i}.eqn; st; dch -f; print_stats -p; read_lib asap7_clean.lib ; map ; topo; upsize; dnsize; stime\"")
+        os.system(f"./abc/abc -c \"read_eqn test_data_beta_runner/output_from_egg_{i}.eqn; st; dch -f; print_stats -p; read_lib asap7_clean.lib ; map ; topo; upsize; dnsize; stime\"")
     print("----------------------------------------------------------------------------------------")
     
     '''
@@ -359,7 +311,7 @@ if __name__ == "__main__":
     #
     #############################################################################
     '''
-    os.system("./abc/abc -c \"cec test_data_beta_runner/raw_circuit.eqn test_data_beta_runner/optimized_circuit_0.eqn\"")
+    os.system("./abc/abc -c \"cec test_data_beta_runner/raw_circuit.eqn test_data_beta_runner/optimized_circuit.eqn\"")
     # os.system("./abc/abc -c \"read_eqn test_data_beta_runner/raw_circuit.eqn; strash; write_aiger test_data_beta_runner/raw_circuit.aig\"")
     # os.system("./abc/abc -c \"read_eqn test_data_beta_runner/optimized_circuit.eqn; strash; write_aiger test_data_beta_runner/optimized_circuit.aig\"")
     # os.system("./abc/abc -c \"read_aiger test_data_beta_runner/raw_circuit.aig; collapse; write_blif test_data_beta_runner/raw_circuit.blif\"")
@@ -1,4 +1,5 @@
 // src/lib.rs
+#[allow(clippy::all)]
 mod parser;
 
 
@@ -8,6 +9,7 @@ pub enum Expr {
     Lisp(String, Box<Expr>, Box<Expr>), 
     Infix(String, Box<Expr>, Box<Expr>), 
     Not(Box<Expr>), // new
+    Ternary(String, Box<Expr>, Box<Expr>, Box<Expr>), // mux, maj
 }
 
 pub fn parse_expr<'input>(input: &'input str) -> Result<Expr, lalrpop_util::ParseError<usize, lalrpop_util::lexer::Token<'input>, &'static str>> {
@@ -1,4 +1,5 @@
 // src/main.rs
+#[allow(clippy::all)]
 mod parser;
 use std::collections::HashMap;
 use std::fs::File;
@@ -12,6 +13,7 @@ pub enum Expr {
     Lisp(String, Box<Expr>, Box<Expr>), 
     Infix(String, Box<Expr>, Box<Expr>), 
     Not(Box<Expr>), // new
+    Ternary(String, Box<Expr>, Box<Expr>, Box<Expr>), // mux, maj
 }
 
 impl Expr {
@@ -21,15 +23,31 @@ impl Expr {
             Expr::Lisp(op, e1, e2) => format!("({} {} {})", op, e1.to_lisp(), e2.to_lisp()),
             Expr::Infix(op, e1, e2) => format!("({} {} {})", e1.to_lisp(), op, e2.to_lisp()),
             Expr::Not(e) => format!("(! {})", e.to_lisp()), 
+            Expr::Ternary(op, e1, e2, e3) => format!("({} {} {} {})", op, e1.to_lisp(), e2.to_lisp(), e3.to_lisp()),
         }
     }
 
     fn to_infix(&self) -> String {
         match self {
             Expr::VariableStr(n) => n.to_string(),
-            Expr::Lisp(op, e1, e2) => format!("({} {} {})", e1.to_infix(), op, e2.to_infix()), 
-            Expr::Infix(op, e1, e2) => format!("({} {} {})", e1.to_infix(), op, e2.to_infix()),
+            Expr::Lisp(op, e1, e2) | Expr::Infix(op, e1, e2) => {
+                let (a, b) = (e1.to_infix(), e2.to_infix());
+                // ABC eqn has no XOR/MUX/MAJ, so they are expanded to AND/OR/NOT
+                match op.as_str() {
+                    "^" => format!("(({} * (!{})) + ((!{}) * {}))", a, b, a, b),
+                    "xnor" => format!("(({} * {}) + ((!{}) * (!{})))", a, b, a, b),
+                    _ => format!("({} {} {})", a, op, b),
+                }
+            },
             Expr::Not(e) => format!("(! {})", e.to_infix()),
+            Expr::Ternary(op, e1, e2, e3) => {
+                let (a, b, c) = (e1.to_infix(), e2.to_infix(), e3.to_infix());
+                if op == "mux" {
+                    format!("(({} * {}) + ((!{}) * {}))", a, b, a, c)
+                } else {
+                    format!("(({} * {}) + ({} * ({} + {})))", a, b, c, a, b)
+                }
+            },
         }
     }
 
@@ -85,6 +103,10 @@ impl Expr {
                 }
             },
             Expr::Not(e) => e.gather_and_expressions(expressions, id, true),
+            Expr::Ternary(..) => {
+                expressions.insert(*id, self.to_infix());
+                *id += 1;
+            },
         }
     }
 }
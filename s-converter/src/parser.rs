@@ -1,5 +1,5 @@
 // auto-generated: "lalrpop 0.19.12"
-// sha3: e6e64c318537a903891afe63b57ec3eafe72daa9f080566ca4a0fb8b3cc508c7
+// sha3: 5ba986d2cca52c3f1902bdeda3674aac124067e548233886cc44430b65f03117
 use crate::Expr;
 #[allow(unused_extern_crates)]
 extern crate lalrpop_util as __lalrpop_util;
@@ -29,78 +29,116 @@ mod __parse__Expr {
     }
     const __ACTION: &[i8] = &[
         // State 0
-        0, 2, 0, 3, 0, 0, 0, 16, 17,
+        0, 2, 0, 3, 0, 0, 0, 0, 0, 25, 26, 0, 27, 28,
         // State 1
-        0, 2, 0, 3, 0, 0, 0, 16, 17,
+        0, 2, 0, 3, 0, 0, 0, 0, 0, 25, 26, 0, 27, 28,
         // State 2
-        20, 6, 21, 3, 0, 22, 23, 16, 17,
+        31, 8, 32, 3, 0, 33, 34, 0, 35, 25, 26, 36, 27, 28,
         // State 3
-        0, 0, 21, 0, 0, 22, 23, 0, 0,
+        0, 2, 0, 3, 0, 0, 0, 0, 0, 25, 26, 0, 27, 28,
         // State 4
-        0, 2, 0, 3, 0, 0, 0, 16, 17,
+        0, 0, 32, 0, 0, 33, 34, 0, 35, 0, 0, 36, 0, 0,
         // State 5
-        0, 2, 0, 3, 0, 0, 0, 16, 17,
+        0, 2, 0, 3, 0, 0, 0, 0, 0, 25, 26, 0, 27, 28,
         // State 6
-        0, 2, 0, 3, 0, 0, 0, 16, 17,
+        0, 2, 0, 13, 0, 0, 0, 0, 0, 25, 26, 0, 27, 28,
         // State 7
-        0, 2, 21, 3, 0, 22, 23, 16, 17,
+        0, 2, 0, 3, 0, 0, 0, 0, 0, 25, 26, 0, 27, 28,
         // State 8
-        0, 2, 0, 3, 0, 0, 0, 16, 17,
+        0, 2, 0, 3, 0, 0, 0, 0, 0, 25, 26, 0, 27, 28,
         // State 9
-        0, 0, 21, 0, 0, 22, 23, 0, 0,
+        0, 2, 32, 3, 0, 33, 34, 0, 35, 25, 26, 36, 27, 28,
         // State 10
-        0, 2, 0, 3, 0, 0, 0, 16, 17,
+        0, 2, 0, 3, 0, 0, 0, 0, 0, 25, 26, 0, 27, 28,
         // State 11
-        0, 2, 0, 3, 0, 0, 0, 16, 17,
+        0, 2, 0, 3, 0, 0, 0, 0, 0, 25, 26, 0, 27, 28,
         // State 12
-        0, 2, 0, 3, 0, 0, 0, 16, 17,
+        31, 8, 32, 3, 0, 33, 34, 0, 35, 25, 26, 36, 27, 28,
         // State 13
-        0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 2, 0, 3, 0, 0, 0, 0, 0, 25, 26, 0, 27, 28,
         // State 14
-        0, -1, -1, -1, -1, -1, -1, -1, -1,
+        0, 0, 32, 0, 0, 33, 34, 0, 35, 0, 0, 36, 0, 0,
         // State 15
-        0, -13, -13, -13, -13, -13, -13, -13, -13,
+        0, 2, 0, 3, 0, 0, 0, 0, 0, 25, 26, 0, 27, 28,
         // State 16
-        0, -12, -12, -12, -12, -12, -12, -12, -12,
+        0, 2, 0, 3, 0, 0, 0, 0, 0, 25, 26, 0, 27, 28,
         // State 17
-        0, -3, -3, -3, -3, -3, -3, -3, -3,
+        0, 0, 32, 0, 0, 33, 34, 14, 35, 0, 0, 36, 0, 0,
         // State 18
-        0, 0, 0, 8, 0, 0, 0, 0, 0,
+        0, 2, 0, 3, 0, 0, 0, 0, 0, 25, 26, 0, 27, 28,
         // State 19
-        0, 0, 0, -8, 0, 0, 0, 0, 0,
+        0, 2, 0, 3, 0, 0, 0, 0, 0, 25, 26, 0, 27, 28,
         // State 20
-        0, -11, 0, -11, 0, 0, 0, -11, -11,
+        0, 2, 0, 3, 0, 0, 0, 0, 0, 25, 26, 0, 27, 28,
         // State 21
-        0, -9, 0, -9, 0, 0, 0, -9, -9,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
         // State 22
-        0, -10, 0, -10, 0, 0, 0, -10, -10,
+        0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
         // State 23
-        0, 0, -3, 0, 27, -3, -3, 0, 0,
+        0, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
         // State 24
-        0, 0, 0, 0, 28, 0, 0, 0, 0,
+        0, -17, 0, -17, 0, 0, 0, 0, 0, -17, -17, 0, -17, -17,
         // State 25
-        0, 0, 0, 0, 29, 0, 0, 0, 0,
+        0, -16, 0, -16, 0, 0, 0, 0, 0, -16, -16, 0, -16, -16,
         // State 26
-        0, -2, -2, -2, -2, -2, -2, -2, -2,
+        0, -19, -19, -19, -19, -19, -19, -19, -19, -19, -19, -19, -19, -19,
         // State 27
-        0, -6, -6, -6, -6, -6, -6, -6, -6,
+        0, -18, -18, -18, -18, -18, -18, -18, -18, -18, -18, -18, -18, -18,
         // State 28
-        0, -7, -7, -7, -7, -7, -7, -7, -7,
+        0, -3, -3, -3, -3, -3, -3, -3, -3, -3, -3, -3, -3, -3,
         // State 29
-        0, 0, 0, 0, 32, 0, 0, 0, 0,
+        0, 0, 0, 10, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
         // State 30
-        0, 0, 0, 0, 33, 0, 0, 0, 0,
+        0, 0, 0, -10, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
         // State 31
-        0, 0, 0, 0, 34, 0, 0, 0, 0,
+        0, -13, 0, -13, 0, 0, 0, 0, 0, -13, -13, 0, -13, -13,
         // State 32
-        0, 0, 0, 0, 35, 0, 0, 0, 0,
+        0, -11, 0, -11, 0, 0, 0, 0, 0, -11, -11, 0, -11, -11,
         // State 33
-        0, -4, -4, -4, -4, -4, -4, -4, -4,
+        0, -12, 0, -12, 0, 0, 0, 0, 0, -12, -12, 0, -12, -12,
         // State 34
-        0, -5, -5, -5, -5, -5, -5, -5, -5,
+        0, -14, 0, -14, 0, 0, 0, 0, 0, -14, -14, 0, -14, -14,
+        // State 35
+        0, -15, 0, -15, 0, 0, 0, 0, 0, -15, -15, 0, -15, -15,
+        // State 36
+        0, 0, 0, 0, 0, 0, 0, 14, 0, 0, 0, 0, 0, 0,
+        // State 37
+        0, 0, -3, 0, 41, -3, -3, -3, -3, 0, 0, -3, 0, 0,
+        // State 38
+        0, 0, 0, 0, 43, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        // State 39
+        0, 0, 0, 0, 44, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        // State 40
+        0, -2, -2, -2, -2, -2, -2, -2, -2, -2, -2, -2, -2, -2,
+        // State 41
+        0, 0, 0, 0, 0, 0, 0, 21, 0, 0, 0, 0, 0, 0,
+        // State 42
+        0, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6,
+        // State 43
+        0, -7, -7, -7, -7, -7, -7, -7, -7, -7, -7, -7, -7, -7,
+        // State 44
+        0, 0, 0, 0, 48, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        // State 45
+        0, 0, 0, 0, 50, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        // State 46
+        0, 0, 0, 0, 51, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        // State 47
+        0, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8,
+        // State 48
+        0, 0, 0, 0, 52, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        // State 49
+        0, 0, 0, 0, 53, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        // State 50
+        0, 0, 0, 0, 54, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        // State 51
+        0, -9, -9, -9, -9, -9, -9, -9, -9, -9, -9, -9, -9, -9,
+        // State 52
+        0, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4,
+        // State 53
+        0, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5,
     ];
     fn __action(state: i8, integer: usize) -> i8 {
-        __ACTION[(state as usize) * 9 + integer]
+        __ACTION[(state as usize) * 14 + integer]
     }
     const __EOF_ACTION: &[i8] = &[
         // State 0
@@ -130,15 +168,15 @@ mod __parse__Expr {
         // State 12
         0,
         // State 13
-        -14,
+        0,
         // State 14
-        -1,
+        0,
         // State 15
-        -13,
+        0,
         // State 16
-        -12,
+        0,
         // State 17
-        -3,
+        0,
         // State 18
         0,
         // State 19
@@ -146,21 +184,21 @@ mod __parse__Expr {
         // State 20
         0,
         // State 21
-        0,
+        -20,
         // State 22
         0,
         // State 23
-        0,
+        -1,
         // State 24
         0,
         // State 25
         0,
         // State 26
-        -2,
+        -19,
         // State 27
-        -6,
+        -18,
         // State 28
-        -7,
+        -3,
         // State 29
         0,
         // State 30
@@ -170,33 +208,82 @@ mod __parse__Expr {
         // State 32
         0,
         // State 33
-        -4,
+        0,
         // State 34
+        0,
+        // State 35
+        0,
+        // State 36
+        0,
+        // State 37
+        0,
+        // State 38
+        0,
+        // State 39
+        0,
+        // State 40
+        -2,
+        // State 41
+        0,
+        // State 42
+        -6,
+        // State 43
+        -7,
+        // State 44
+        0,
+        // State 45
+        0,
+        // State 46
+        0,
+        // State 47
+        -8,
+        // State 48
+        0,
+        // State 49
+        0,
+        // State 50
+        0,
+        // State 51
+        -9,
+        // State 52
+        -4,
+        // State 53
         -5,
     ];
     fn __goto(state: i8, nt: usize) -> i8 {
         match nt {
             0 => match state {
-                4 => 8,
-                7 => 9,
-                10 => 12,
-                0 => 13,
-                1 => 17,
-                5 => 23,
-                6 => 24,
-                8 => 25,
-                11 => 29,
-                12 => 30,
-                _ => 3,
+                5 => 10,
+                6 => 11,
+                9 => 14,
+                11 => 16,
+                12 => 17,
+                15 => 19,
+                0 => 21,
+                1 => 28,
+                3 => 36,
+                7 => 37,
+                8 => 38,
+                10 => 39,
+                13 => 41,
+                16 => 44,
+                18 => 45,
+                19 => 46,
+                20 => 48,
+                _ => 4,
             },
-            1 => 18,
+            1 => 29,
             2 => match state {
-                3 => 6,
-                7 => 10,
-                9 => 11,
-                _ => 4,
+                4 | 17 => 8,
+                9 => 15,
+                14 => 18,
+                _ => 5,
+            },
+            3 => match state {
+                2 | 12 => 6,
+                _ => 22,
             },
-            3 => 14,
+            4 => 23,
             _ => 0,
         }
     }
@@ -209,6 +296,11 @@ mod __parse__Expr {
             r###"")""###,
             r###""*""###,
             r###""+""###,
+            r###"",""###,
+            r###""^""###,
+            r###""maj""###,
+            r###""mux""###,
+            r###""xnor""###,
             r###"r#"[a-zA-Z_][a-zA-Z0-9_]*"#"###,
             r###"r#"[a-z]+\\[[0-9]+\\]"#"###,
         ];
@@ -263,7 +355,7 @@ mod __parse__Expr {
 
         #[inline]
         fn error_action(&self, state: i8) -> i8 {
-            __action(state, 9 - 1)
+            __action(state, 14 - 1)
         }
 
         #[inline]
@@ -333,8 +425,13 @@ mod __parse__Expr {
             Token(6, _) if true => Some(4),
             Token(7, _) if true => Some(5),
             Token(8, _) if true => Some(6),
-            Token(0, _) if true => Some(7),
-            Token(1, _) if true => Some(8),
+            Token(9, _) if true => Some(7),
+            Token(10, _) if true => Some(8),
+            Token(11, _) if true => Some(9),
+            Token(12, _) if true => Some(10),
+            Token(13, _) if true => Some(11),
+            Token(0, _) if true => Some(12),
+            Token(1, _) if true => Some(13),
             _ => None,
         }
     }
@@ -347,8 +444,8 @@ mod __parse__Expr {
     ) -> __Symbol<'input>
     {
         match __token_index {
-            0 | 1 | 2 | 3 | 4 | 5 | 6 | 7 | 8 => match __token {
-                Token(2, __tok0) | Token(3, __tok0) | Token(4, __tok0) | Token(5, __tok0) | Token(6, __tok0) | Token(7, __tok0) | Token(8, __tok0) | Token(0, __tok0) | Token(1, __tok0) if true => __Symbol::Variant0(__tok0),
+            0 | 1 | 2 | 3 | 4 | 5 | 6 | 7 | 8 | 9 | 10 | 11 | 12 | 13 => match __token {
+                Token(2, __tok0) | Token(3, __tok0) | Token(4, __tok0) | Token(5, __tok0) | Token(6, __tok0) | Token(7, __tok0) | Token(8, __tok0) | Token(9, __tok0) | Token(10, __tok0) | Token(11, __tok0) | Token(12, __tok0) | Token(13, __tok0) | Token(0, __tok0) | Token(1, __tok0) if true => __Symbol::Variant0(__tok0),
                 _ => unreachable!(),
             },
             _ => unreachable!(),
@@ -438,6 +535,24 @@ mod __parse__Expr {
                 __reduce12(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
             }
             13 => {
+                __reduce13(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
+            }
+            14 => {
+                __reduce14(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
+            }
+            15 => {
+                __reduce15(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
+            }
+            16 => {
+                __reduce16(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
+            }
+            17 => {
+                __reduce17(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
+            }
+            18 => {
+                __reduce18(input, __lookahead_start, __symbols, core::marker::PhantomData::<(&())>)
+            }
+            19 => {
                 // __Expr = Expr => ActionFn(0);
                 let __sym0 = __pop_Variant1(__symbols);
                 let __start = __sym0.0.clone();
@@ -500,11 +615,11 @@ mod __parse__Expr {
         _: core::marker::PhantomData<(&'input ())>,
     ) -> (usize, usize)
     {
-        // Expr = VariableStr => ActionFn(7);
+        // Expr = VariableStr => ActionFn(11);
         let __sym0 = __pop_Variant2(__symbols);
         let __start = __sym0.0.clone();
         let __end = __sym0.2.clone();
-        let __nt = super::__action7::<>(input, __sym0);
+        let __nt = super::__action11::<>(input, __sym0);
         __symbols.push((__start, __Symbol::Variant1(__nt), __end));
         (1, 0)
     }
@@ -517,7 +632,7 @@ mod __parse__Expr {
         _: core::marker::PhantomData<(&'input ())>,
     ) -> (usize, usize)
     {
-        // Expr = "(", "! ", Expr, ")" => ActionFn(8);
+        // Expr = "(", "! ", Expr, ")" => ActionFn(12);
         assert!(__symbols.len() >= 4);
         let __sym3 = __pop_Variant0(__symbols);
         let __sym2 = __pop_Variant1(__symbols);
@@ -525,7 +640,7 @@ mod __parse__Expr {
         let __sym0 = __pop_Variant0(__symbols);
         let __start = __sym0.0.clone();
         let __end = __sym3.2.clone();
-        let __nt = super::__action8::<>(input, __sym0, __sym1, __sym2, __sym3);
+        let __nt = super::__action12::<>(input, __sym0, __sym1, __sym2, __sym3);
         __symbols.push((__start, __Symbol::Variant1(__nt), __end));
         (4, 0)
     }
@@ -538,13 +653,13 @@ mod __parse__Expr {
         _: core::marker::PhantomData<(&'input ())>,
     ) -> (usize, usize)
     {
-        // Expr = "! ", Expr => ActionFn(9);
+        // Expr = "! ", Expr => ActionFn(13);
         assert!(__symbols.len() >= 2);
         let __sym1 = __pop_Variant1(__symbols);
         let __sym0 = __pop_Variant0(__symbols);
         let __start = __sym0.0.clone();
         let __end = __sym1.2.clone();
-        let __nt = super::__action9::<>(input, __sym0, __sym1);
+        let __nt = super::__action13::<>(input, __sym0, __sym1);
         __symbols.push((__start, __Symbol::Variant1(__nt), __end));
         (2, 0)
     }
@@ -557,7 +672,7 @@ mod __parse__Expr {
         _: core::marker::PhantomData<(&'input ())>,
     ) -> (usize, usize)
     {
-        // Expr = "(", NotOp, "(", Expr, Op, Expr, ")", ")" => ActionFn(10);
+        // Expr = "(", NotOp, "(", Expr, Op, Expr, ")", ")" => ActionFn(14);
         assert!(__symbols.len() >= 8);
         let __sym7 = __pop_Variant0(__symbols);
         let __sym6 = __pop_Variant0(__symbols);
@@ -569,7 +684,7 @@ mod __parse__Expr {
         let __sym0 = __pop_Variant0(__symbols);
         let __start = __sym0.0.clone();
         let __end = __sym7.2.clone();
-        let __nt = super::__action10::<>(input, __sym0, __sym1, __sym2, __sym3, __sym4, __sym5, __sym6, __sym7);
+        let __nt = super::__action14::<>(input, __sym0, __sym1, __sym2, __sym3, __sym4, __sym5, __sym6, __sym7);
         __symbols.push((__start, __Symbol::Variant1(__nt), __end));
         (8, 0)
     }
@@ -582,7 +697,7 @@ mod __parse__Expr {
         _: core::marker::PhantomData<(&'input ())>,
     ) -> (usize, usize)
     {
-        // Expr = "(", NotOp, "(", Op, Expr, Expr, ")", ")" => ActionFn(11);
+        // Expr = "(", NotOp, "(", Op, Expr, Expr, ")", ")" => ActionFn(15);
         assert!(__symbols.len() >= 8);
         let __sym7 = __pop_Variant0(__symbols);
         let __sym6 = __pop_Variant0(__symbols);
@@ -594,7 +709,7 @@ mod __parse__Expr {
         let __sym0 = __pop_Variant0(__symbols);
         let __start = __sym0.0.clone();
         let __end = __sym7.2.clone();
-        let __nt = super::__action11::<>(input, __sym0, __sym1, __sym2, __sym3, __sym4, __sym5, __sym6, __sym7);
+        let __nt = super::__action15::<>(input, __sym0, __sym1, __sym2, __sym3, __sym4, __sym5, __sym6, __sym7);
         __symbols.push((__start, __Symbol::Variant1(__nt), __end));
         (8, 0)
     }
@@ -607,7 +722,7 @@ mod __parse__Expr {
         _: core::marker::PhantomData<(&'input ())>,
     ) -> (usize, usize)
     {
-        // Expr = "(", Expr, Op, Expr, ")" => ActionFn(12);
+        // Expr = "(", Expr, Op, Expr, ")" => ActionFn(16);
         assert!(__symbols.len() >= 5);
         let __sym4 = __pop_Variant0(__symbols);
         let __sym3 = __pop_Variant1(__symbols);
@@ -616,7 +731,7 @@ mod __parse__Expr {
         let __sym0 = __pop_Variant0(__symbols);
         let __start = __sym0.0.clone();
         let __end = __sym4.2.clone();
-        let __nt = super::__action12::<>(input, __sym0, __sym1, __sym2, __sym3, __sym4);
+        let __nt = super::__action16::<>(input, __sym0, __sym1, __sym2, __sym3, __sym4);
         __symbols.push((__start, __Symbol::Variant1(__nt), __end));
         (5, 0)
     }
@@ -629,7 +744,7 @@ mod __parse__Expr {
         _: core::marker::PhantomData<(&'input ())>,
     ) -> (usize, usize)
     {
-        // Expr = "(", Op, Expr, Expr, ")" => ActionFn(13);
+        // Expr = "(", Op, Expr, Expr, ")" => ActionFn(17);
         assert!(__symbols.len() >= 5);
         let __sym4 = __pop_Variant0(__symbols);
         let __sym3 = __pop_Variant1(__symbols);
@@ -638,7 +753,7 @@ mod __parse__Expr {
         let __sym0 = __pop_Variant0(__symbols);
         let __start = __sym0.0.clone();
         let __end = __sym4.2.clone();
-        let __nt = super::__action13::<>(input, __sym0, __sym1, __sym2, __sym3, __sym4);
+        let __nt = super::__action17::<>(input, __sym0, __sym1, __sym2, __sym3, __sym4);
         __symbols.push((__start, __Symbol::Variant1(__nt), __end));
         (5, 0)
     }
@@ -651,15 +766,63 @@ mod __parse__Expr {
         _: core::marker::PhantomData<(&'input ())>,
     ) -> (usize, usize)
     {
-        // NotOp = "!" => ActionFn(6);
+        // Expr = "(", TernOp, Expr, Expr, Expr, ")" => ActionFn(18);
+        assert!(__symbols.len() >= 6);
+        let __sym5 = __pop_Variant0(__symbols);
+        let __sym4 = __pop_Variant1(__symbols);
+        let __sym3 = __pop_Variant1(__symbols);
+        let __sym2 = __pop_Variant1(__symbols);
+        let __sym1 = __pop_Variant2(__symbols);
+        let __sym0 = __pop_Variant0(__symbols);
+        let __start = __sym0.0.clone();
+        let __end = __sym5.2.clone();
+        let __nt = super::__action18::<>(input, __sym0, __sym1, __sym2, __sym3, __sym4, __sym5);
+        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
+        (6, 0)
+    }
+    pub(crate) fn __reduce8<
+        'input,
+    >(
+        input: &'input str,
+        __lookahead_start: Option<&usize>,
+        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
+        _: core::marker::PhantomData<(&'input ())>,
+    ) -> (usize, usize)
+    {
+        // Expr = TernOp, "(", Expr, ",", Expr, ",", Expr, ")" => ActionFn(19);
+        assert!(__symbols.len() >= 8);
+        let __sym7 = __pop_Variant0(__symbols);
+        let __sym6 = __pop_Variant1(__symbols);
+        let __sym5 = __pop_Variant0(__symbols);
+        let __sym4 = __pop_Variant1(__symbols);
+        let __sym3 = __pop_Variant0(__symbols);
+        let __sym2 = __pop_Variant1(__symbols);
+        let __sym1 = __pop_Variant0(__symbols);
+        let __sym0 = __pop_Variant2(__symbols);
+        let __start = __sym0.0.clone();
+        let __end = __sym7.2.clone();
+        let __nt = super::__action19::<>(input, __sym0, __sym1, __sym2, __sym3, __sym4, __sym5, __sym6, __sym7);
+        __symbols.push((__start, __Symbol::Variant1(__nt), __end));
+        (8, 0)
+    }
+    pub(crate) fn __reduce9<
+        'input,
+    >(
+        input: &'input str,
+        __lookahead_start: Option<&usize>,
+        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
+        _: core::marker::PhantomData<(&'input ())>,
+    ) -> (usize, usize)
+    {
+        // NotOp = "!" => ActionFn(10);
         let __sym0 = __pop_Variant0(__symbols);
         let __start = __sym0.0.clone();
         let __end = __sym0.2.clone();
-        let __nt = super::__action6::<>(input, __sym0);
+        let __nt = super::__action10::<>(input, __sym0);
         __symbols.push((__start, __Symbol::Variant2(__nt), __end));
         (1, 1)
     }
-    pub(crate) fn __reduce8<
+    pub(crate) fn __reduce10<
         'input,
     >(
         input: &'input str,
@@ -676,7 +839,7 @@ mod __parse__Expr {
         __symbols.push((__start, __Symbol::Variant2(__nt), __end));
         (1, 2)
     }
-    pub(crate) fn __reduce9<
+    pub(crate) fn __reduce11<
         'input,
     >(
         input: &'input str,
@@ -693,7 +856,7 @@ mod __parse__Expr {
         __symbols.push((__start, __Symbol::Variant2(__nt), __end));
         (1, 2)
     }
-    pub(crate) fn __reduce10<
+    pub(crate) fn __reduce12<
         'input,
     >(
         input: &'input str,
@@ -710,7 +873,75 @@ mod __parse__Expr {
         __symbols.push((__start, __Symbol::Variant2(__nt), __end));
         (1, 2)
     }
-    pub(crate) fn __reduce11<
+    pub(crate) fn __reduce13<
+        'input,
+    >(
+        input: &'input str,
+        __lookahead_start: Option<&usize>,
+        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
+        _: core::marker::PhantomData<(&'input ())>,
+    ) -> (usize, usize)
+    {
+        // Op = "^" => ActionFn(6);
+        let __sym0 = __pop_Variant0(__symbols);
+        let __start = __sym0.0.clone();
+        let __end = __sym0.2.clone();
+        let __nt = super::__action6::<>(input, __sym0);
+        __symbols.push((__start, __Symbol::Variant2(__nt), __end));
+        (1, 2)
+    }
+    pub(crate) fn __reduce14<
+        'input,
+    >(
+        input: &'input str,
+        __lookahead_start: Option<&usize>,
+        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
+        _: core::marker::PhantomData<(&'input ())>,
+    ) -> (usize, usize)
+    {
+        // Op = "xnor" => ActionFn(7);
+        let __sym0 = __pop_Variant0(__symbols);
+        let __start = __sym0.0.clone();
+        let __end = __sym0.2.clone();
+        let __nt = super::__action7::<>(input, __sym0);
+        __symbols.push((__start, __Symbol::Variant2(__nt), __end));
+        (1, 2)
+    }
+    pub(crate) fn __reduce15<
+        'input,
+    >(
+        input: &'input str,
+        __lookahead_start: Option<&usize>,
+        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
+        _: core::marker::PhantomData<(&'input ())>,
+    ) -> (usize, usize)
+    {
+        // TernOp = "mux" => ActionFn(8);
+        let __sym0 = __pop_Variant0(__symbols);
+        let __start = __sym0.0.clone();
+        let __end = __sym0.2.clone();
+        let __nt = super::__action8::<>(input, __sym0);
+        __symbols.push((__start, __Symbol::Variant2(__nt), __end));
+        (1, 3)
+    }
+    pub(crate) fn __reduce16<
+        'input,
+    >(
+        input: &'input str,
+        __lookahead_start: Option<&usize>,
+        __symbols: &mut alloc::vec::Vec<(usize,__Symbol<'input>,usize)>,
+        _: core::marker::PhantomData<(&'input ())>,
+    ) -> (usize, usize)
+    {
+        // TernOp = "maj" => ActionFn(9);
+        let __sym0 = __pop_Variant0(__symbols);
+        let __start = __sym0.0.clone();
+        let __end = __sym0.2.clone();
+        let __nt = super::__action9::<>(input, __sym0);
+        __symbols.push((__start, __Symbol::Variant2(__nt), __end));
+        (1, 3)
+    }
+    pub(crate) fn __reduce17<
         'input,
     >(
         input: &'input str,
@@ -725,9 +956,9 @@ mod __parse__Expr {
         let __end = __sym0.2.clone();
         let __nt = super::__action1::<>(input, __sym0);
         __symbols.push((__start, __Symbol::Variant2(__nt), __end));
-        (1, 3)
+        (1, 4)
     }
-    pub(crate) fn __reduce12<
+    pub(crate) fn __reduce18<
         'input,
     >(
         input: &'input str,
@@ -742,7 +973,7 @@ mod __parse__Expr {
         let __end = __sym0.2.clone();
         let __nt = super::__action2::<>(input, __sym0);
         __symbols.push((__start, __Symbol::Variant2(__nt), __end));
-        (1, 3)
+        (1, 4)
     }
 }
 pub use self::__parse__Expr::ExprParser;
@@ -767,6 +998,11 @@ mod __intern_token {
             ("^(\\))", false),
             ("^(\\*)", false),
             ("^(\\+)", false),
+            ("^(,)", false),
+            ("^(\\^)", false),
+            ("^(maj)", false),
+            ("^(mux)", false),
+            ("^(xnor)", false),
             (r"^(\s*)", true),
         ];
         __lalrpop_util::lexer::MatcherBuilder::new(__strs.iter().copied()).unwrap()
@@ -848,12 +1084,56 @@ fn __action6<
     (_, __0, _): (usize, &'input str, usize),
 ) -> String
 {
-    "!".to_string()
+    "^".to_string()
 }
 
 #[allow(unused_variables)]
 fn __action7<
     'input,
+>(
+    input: &'input str,
+    (_, __0, _): (usize, &'input str, usize),
+) -> String
+{
+    "xnor".to_string()
+}
+
+#[allow(unused_variables)]
+fn __action8<
+    'input,
+>(
+    input: &'input str,
+    (_, __0, _): (usize, &'input str, usize),
+) -> String
+{
+    "mux".to_string()
+}
+
+#[allow(unused_variables)]
+fn __action9<
+    'input,
+>(
+    input: &'input str,
+    (_, __0, _): (usize, &'input str, usize),
+) -> String
+{
+    "maj".to_string()
+}
+
+#[allow(unused_variables)]
+fn __action10<
+    'input,
+>(
+    input: &'input str,
+    (_, __0, _): (usize, &'input str, usize),
+) -> String
+{
+    "!".to_string()
+}
+
+#[allow(unused_variables)]
+fn __action11<
+    'input,
 >(
     input: &'input str,
     (_, n, _): (usize, String, usize),
@@ -863,7 +1143,7 @@ fn __action7<
 }
 
 #[allow(unused_variables)]
-fn __action8<
+fn __action12<
     'input,
 >(
     input: &'input str,
@@ -877,7 +1157,7 @@ fn __action8<
 }
 
 #[allow(unused_variables)]
-fn __action9<
+fn __action13<
     'input,
 >(
     input: &'input str,
@@ -889,7 +1169,7 @@ fn __action9<
 }
 
 #[allow(unused_variables)]
-fn __action10<
+fn __action14<
     'input,
 >(
     input: &'input str,
@@ -907,7 +1187,7 @@ fn __action10<
 }
 
 #[allow(unused_variables)]
-fn __action11<
+fn __action15<
     'input,
 >(
     input: &'input str,
@@ -925,7 +1205,7 @@ fn __action11<
 }
 
 #[allow(unused_variables)]
-fn __action12<
+fn __action16<
     'input,
 >(
     input: &'input str,
@@ -940,7 +1220,7 @@ fn __action12<
 }
 
 #[allow(unused_variables)]
-fn __action13<
+fn __action17<
     'input,
 >(
     input: &'input str,
@@ -954,6 +1234,40 @@ fn __action13<
     Expr::Infix(s, Box::new(e1), Box::new(e2))
 }
 
+#[allow(unused_variables)]
+fn __action18<
+    'input,
+>(
+    input: &'input str,
+    (_, _, _): (usize, &'input str, usize),
+    (_, t, _): (usize, String, usize),
+    (_, e1, _): (usize, Expr, usize),
+    (_, e2, _): (usize, Expr, usize),
+    (_, e3, _): (usize, Expr, usize),
+    (_, _, _): (usize, &'input str, usize),
+) -> Expr
+{
+    Expr::Ternary(t, Box::new(e1), Box::new(e2), Box::new(e3))
+}
+
+#[allow(unused_variables)]
+fn __action19<
+    'input,
+>(
+    input: &'input str,
+    (_, t, _): (usize, String, usize),
+    (_, _, _): (usize, &'input str, usize),
+    (_, e1, _): (usize, Expr, usize),
+    (_, _, _): (usize, &'input str, usize),
+    (_, e2, _): (usize, Expr, usize),
+    (_, _, _): (usize, &'input str, usize),
+    (_, e3, _): (usize, Expr, usize),
+    (_, _, _): (usize, &'input str, usize),
+) -> Expr
+{
+    Expr::Ternary(t, Box::new(e1), Box::new(e2), Box::new(e3))
+}
+
 pub trait __ToTriple<'input, >
 {
     fn to_triple(value: Self) -> Result<(usize,Token<'input>,usize), __lalrpop_util::ParseError<usize, Token<'input>, &'static str>>;
@@ -2,89 +2,15 @@ use std::collections::HashMap;
 use egg::*;
 use std::fs::File;
 use std::io::prelude::*;
-use std::io::Write;
 use std::env;
 // mod utils;
 // use utils::{language::*};
 use std::path::Path;
+use e_rewriter::aiger;
+use e_rewriter::liberty::{circuit_area, circuit_delay, Library};
+use e_rewriter::optimize::{count_operators, AstDepth, AstSize};
+use e_rewriter::prop::{ConstantFold, Prop};
 
-#[derive(Default)]
-pub struct ConstantFold;
-impl Analysis<Prop> for ConstantFold {
-    type Data = Option<(bool, PatternAst<Prop>)>;
-    fn merge(&mut self, to: &mut Self::Data, from: Self::Data) -> DidMerge {
-        merge_option(to, from, |a, b| {
-            assert_eq!(a.0, b.0, "Merged non-equal constants");
-            DidMerge(false, false)
-        })
-    }
-    fn make(egraph: &egg::EGraph<Prop, ConstantFold>, enode: &Prop) -> Self::Data {
-        let x = |i: &Id| egraph[*i].data.as_ref().map(|c| c.0);
-        let result = match enode {
-            Prop::Let([a, b]) => Some((
-                x(a) == x(b),
-                format!("(let {} {})", x(a)?, x(b)?).parse().unwrap(),
-            )),
-            Prop::Bool(c) => Some((*c, c.to_string().parse().unwrap())),
-            Prop::And([a, b]) => Some((
-                x(a)? && x(b)?,
-                format!("(* {} {})", x(a)?, x(b)?).parse().unwrap(),
-            )),
-            Prop::Not(a) => Some((!x(a)?, format!("(not {})", x(a)?).parse().unwrap())),
-            Prop::Or([a, b]) => Some((
-                x(a)? || x(b)?,
-                format!("(+ {} {})", x(a)?, x(b)?).parse().unwrap(),
-            )),
-            Prop::Implies([a, b]) => Some((
-                !x(a)? || x(b)?,
-                format!("(-> {} {})", x(a)?, x(b)?).parse().unwrap(),
-            )),
-            Prop::Concat([a, b]) => Some((
-                x(a)? > x(b)?,
-                format!("(& {} {})", x(a)?, x(b)?).parse().unwrap(),
-            )),
-            Prop::Symbol(_) => None,
-        };
-        //println!("Make: {:?} -> {:?}", enode, result);
-        result
-    }
-    fn modify(egraph: &mut egg::EGraph<Prop, ConstantFold>, id: Id) {
-        if let Some(c) = egraph[id].data.clone() {
-            egraph.union_instantiations(
-                &c.1,
-                &c.0.to_string().parse().unwrap(),
-                &Default::default(),
-                "analysis".to_string(),
-            );
-        }
-    }
-}
-
-define_language! {
-    enum Prop {
-        Bool(bool),
-        "*" = And([Id; 2]),
-        "!" = Not(Id),
-        "+" = Or([Id; 2]),
-        "->" = Implies([Id; 2]),
-        "let" = Let([Id; 2]),
-        "&" = Concat([Id; 2]),
-        Symbol(Symbol),
-    }
-}
-fn count_operators(s: &str) -> HashMap<String, i32> {
-    let mut operator_counts = HashMap::new();
-    for c in s.chars() {
-        match c {
-            '*' | '!' | '+' | '-' | '>' | '&' => {
-                let entry = operator_counts.entry(c.to_string()).or_insert(0);
-                *entry += 1;
-            },
-            _ => {},
-        }
-    }
-    operator_counts
-}
 
 /*
 
@@ -96,54 +22,47 @@ fn count_operators(s: &str) -> HashMap<String, i32> {
 - Total number of nodes
 */
 
+// cell weight per operator: areas from the Liberty library if one is given,
+// otherwise the weights this analyzer always used for `!`, `*` and `+`;
+// `^`, `xnor`, `mux` and `maj` are counted as the gates they expand into
+fn operator_weights(library: Option<&Library>) -> HashMap<String, f64> {
+    match library {
+        Some(library) => ["!", "*", "+"].iter().map(|op| (op.to_string(), library.area(op))).collect(),
+        None => [("!", 9.0), ("*", 22.0), ("+", 26.0)].iter().map(|(op, w)| (op.to_string(), *w)).collect(),
+    }
+}
+
 // get hashmap from count_operators and use it to calculate sum_of_liberty_mutiplied_node_number
 // input: hashmap from count_operators
-fn sum_of_liberty_mutiplied_node_number(operator_counts: &HashMap<String, i32>) -> i32 {
-    let mut sum = 0;
+fn sum_of_liberty_mutiplied_node_number(operator_counts: &HashMap<String, f64>, weights: &HashMap<String, f64>) -> f64 {
+    let mut sum = 0.0;
     for (operator, count) in operator_counts {
-        match operator.as_str() {
-            // "!" => 9 ,
-            // "+" => 26 ,
-            // "*"=> 22 ,
-            "!" => sum += 9 * count,
-            "+" => sum += 26 * count,
-            "*" => sum += 22 * count,
-            _ => {},
+        if let Some(weight) = weights.get(operator) {
+            sum += weight * count;
         }
     }
     sum
 }
 
-fn sum_of_nodes(operator_counts: &HashMap<String, i32>) -> i32 {
+fn sum_of_nodes(operator_counts: &HashMap<String, f64>) -> f64 {
     // sum up all the counts
-    let mut sum = 0;
-    for (operator, count) in operator_counts {
+    let mut sum = 0.0;
+    for count in operator_counts.values() {
         sum += count;
     }
     sum
 }
 
-fn average_liberty_mutiplied_node_number(operator_counts: &HashMap<String, i32>) -> f64 {
-    let mut sum = 0;
-    let mut count = 0;
+fn average_liberty_mutiplied_node_number(operator_counts: &HashMap<String, f64>, weights: &HashMap<String, f64>) -> f64 {
+    let mut sum = 0.0;
+    let mut count = 0.0;
     for (operator, c) in operator_counts {
-        match operator.as_str() {
-            "!" => {
-                sum += 9 * c;
-                count += c;
-            },
-            "+" => {
-                sum += 26 * c;
-                count += c;
-            },
-            "*" => {
-                sum += 22 * c;
-                count += c;
-            },
-            _ => {},
+        if let Some(weight) = weights.get(operator) {
+            sum += weight * c;
+            count += c;
         }
     }
-    sum as f64 / count as f64
+    sum / count
 }
 
 
@@ -162,37 +81,14 @@ fn average_liberty_mutiplied_node_number(operator_counts: &HashMap<String, i32>)
 //     }
 // }
 
-pub struct AstSize;
-impl<L: Language> CostFunction<L> for AstSize {
-    type Cost = usize;
-    fn cost<C>(&mut self, enode: &L, mut costs: C) -> Self::Cost
-    where
-        C: FnMut(Id) -> Self::Cost,
-    {
-        enode.fold(1, |sum, id| sum.saturating_add(costs(id)))
-    }
-}
-
-pub struct AstDepth;
-impl<L: Language> CostFunction<L> for AstDepth {
-    type Cost = usize;
-    fn cost<C>(&mut self, enode: &L, mut costs: C) -> Self::Cost
-    where
-        C: FnMut(Id) -> Self::Cost,
-    {
-        1 + enode.fold(0, |max, id| max.max(costs(id)))
-    }
-}
-
-fn count_ast_size_and_depth(s: &str, dot_name: &str) -> (usize, usize) {
-    let expr: RecExpr<Prop> = s.parse().unwrap();
+fn count_ast_size_and_depth(expr: &RecExpr<Prop>, dot_name: &str) -> (usize, usize) {
     let mut ast_size = AstSize;
     let mut ast_depth = AstDepth;
-    let size = ast_size.cost_rec(&expr);
-    let depth = ast_depth.cost_rec(&expr);
+    let size = ast_size.cost_rec(expr);
+    let depth = ast_depth.cost_rec(expr);
     //let expr: RecExpr<Prop> = result_string.parse().unwrap();
-    let mut egraphout = EGraph::new(ConstantFold {});
-    egraphout.add_expr(&expr);
+    let mut egraphout = EGraph::new(ConstantFold::default());
+    egraphout.add_expr(expr);
     let output_directory1 = "out_dot/";
     let output_file_name1 = format!("{}_graph_dot.dot",dot_name);
     let output_file_path1 = Path::new(output_directory1).join(output_file_name1);
@@ -200,32 +96,71 @@ fn count_ast_size_and_depth(s: &str, dot_name: &str) -> (usize, usize) {
     (size, depth)
 }
 
+// One expression for a multi-output circuit, `((o0 & o1) & o2) ...` as
+// run.py's `concatenate_equations` builds it from an eqn file.
+fn concat_outputs(exprs: &[RecExpr<Prop>]) -> RecExpr<Prop> {
+    let mut joined = RecExpr::default();
+    let mut root: Option<Id> = None;
+    for expr in exprs {
+        let offset = joined.as_ref().len();
+        for node in expr.as_ref() {
+            joined.add(node.clone().map_children(|c| Id::from(usize::from(c) + offset)));
+        }
+        let output = Id::from(joined.as_ref().len() - 1);
+        root = Some(match root {
+            Some(acc) => joined.add(Prop::Concat([acc, output])),
+            None => output,
+        });
+    }
+    joined
+}
+
 fn main() -> std::io::Result<()> {
     let args: Vec<String> = env::args().collect();
     let input_path = &args[1];
     let dot_name = &args[2];
+    // optional Liberty library for the SUM_LIB / AVE_LIB weights and the LIB_* metrics
+    let library = match args.get(3) {
+        Some(path) => {
+            let text = std::fs::read_to_string(path)?;
+            Some(Library::parse(&text).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?)
+        }
+        None => None,
+    };
+    let weights = operator_weights(library.as_ref());
     let mut input_file = File::open(input_path)?;
-    let mut contents = String::new();
-    input_file.read_to_string(&mut contents)?;
-    let operator_counts = count_operators(&contents);
+    let mut bytes = Vec::new();
+    input_file.read_to_end(&mut bytes)?;
+    let contents = if aiger::is_aiger(&bytes) {
+        let aig = aiger::parse_aiger::<Prop>(&bytes).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        concat_outputs(&aig.exprs).to_string()
+    } else {
+        String::from_utf8(bytes).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?
+    };
+    let expr: RecExpr<Prop> = contents.parse().map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, format!("{}", e)))?;
+    let operator_counts = count_operators(&expr);
     for (operator, count) in &operator_counts {
         //println!("{}: {}", operator, count);
-        if count > &0 {
+        if count > &0.0 {
             println!("{}: {}", operator, count);
         }
         else {
             println!("{}: 0", operator);
         }
     }
-    let (size, depth) = count_ast_size_and_depth(&contents, &dot_name);
+    let (size, depth) = count_ast_size_and_depth(&expr, dot_name);
     //println!("AST size: {}, AST depth: {}", size, depth);
     println!("ASTSize: {}", size);
     println!("ASTDepth: {}", depth);
-    let sum_of_liberty_mutiplied_node_number = sum_of_liberty_mutiplied_node_number(&operator_counts);
+    let sum_of_liberty_mutiplied_node_number = sum_of_liberty_mutiplied_node_number(&operator_counts, &weights);
     println!("SUM_LIB: {}", sum_of_liberty_mutiplied_node_number);
     let sum_of_nodes = sum_of_nodes(&operator_counts);
     println!("SUM_NODE: {}", sum_of_nodes);
-    let average_liberty_mutiplied_node_number = average_liberty_mutiplied_node_number(&operator_counts);
+    let average_liberty_mutiplied_node_number = average_liberty_mutiplied_node_number(&operator_counts, &weights);
     println!("AVE_LIB: {}", average_liberty_mutiplied_node_number);
+    if let Some(library) = &library {
+        println!("LIB_AREA: {}", circuit_area(library, std::slice::from_ref(&expr)));
+        println!("LIB_DELAY: {}", circuit_delay(library, std::slice::from_ref(&expr)));
+    }
     Ok(())
 }
\ No newline at end of file
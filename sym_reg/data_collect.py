@@ -30,8 +30,6 @@ def load_circuits(file_count):
     for i in tqdm(range(file_count), desc='Loding circuits and convert to eqn'):
         os.system(
             f"abc -c \"read_aiger aigfuzz/simple_circuit_{i}.aig; write_eqn aigfuzz/simple_circuit_{i}.eqn\"")
-        os.system(
-            f"aigtoaig aigfuzz/simple_circuit_{i}.aig aigfuzz/simple_circuit_{i}.aag")
 
 
 def process_circuits(file_count):
@@ -40,12 +38,9 @@ def process_circuits(file_count):
         parser = CircuitParser(
             f"aigfuzz/simple_circuit_{i}.eqn", f"aigfuzz/simple_circuit_{i}_processed.eqn")
         parser.process()
-        with open(f"aigfuzz/simple_circuit_{i}_processed.eqn", "r") as myfile:
-            data = myfile.readlines()
-        _ = run.conver_to_sexpr(
-            data, multiple_output=True, output_file_path=f"aigfuzz/simple_circuit_{i}.sexpr")
+        # the analyzer reads the AIGER file itself, outputs joined with `&` as conver_to_sexpr does
         os.system(
-            f"analyzer/target/release/analyzer aigfuzz/simple_circuit_{i}.sexpr > aigfuzz/simple_circuit_{i}.data")
+            f"analyzer/target/release/analyzer aigfuzz/simple_circuit_{i}.aig simple_circuit_{i} > aigfuzz/simple_circuit_{i}.data")
 
 
 def run_abc(file_count):
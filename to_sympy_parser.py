@@ -11,12 +11,17 @@ class PropParser(object):
             | prop * prop
             | prop + prop
             | prop & prop
+            | prop ^ prop
+            | prop xnor prop
+            | mux ( prop , prop , prop )
+            | maj ( prop , prop , prop )
             | ! prop
     """
 
     # Parsing rules
     precedence = (
         ("left", "OR"),
+        ("left", "XOR", "XNOR"),
         ("left", "AND"),
         ("left", "CONCAT"),
         ("right", "NOT"),
@@ -59,6 +64,23 @@ class PropParser(object):
         "prop : NOT prop"
         p[0] = Not(p[2])
 
+    # ABC eqn has no XOR/MUX/MAJ, so they are expanded to AND/OR/NOT
+    def p_prop_xor(self, p):
+        "prop : prop XOR prop"
+        p[0] = Or(And(p[1], Not(p[3])), And(Not(p[1]), p[3]))
+
+    def p_prop_xnor(self, p):
+        "prop : prop XNOR prop"
+        p[0] = Or(And(p[1], p[3]), And(Not(p[1]), Not(p[3])))
+
+    def p_prop_mux(self, p):
+        "prop : MUX LPAREN prop COMMA prop COMMA prop RPAREN"
+        p[0] = Or(And(p[3], p[5]), And(Not(p[3]), p[7]))
+
+    def p_prop_maj(self, p):
+        "prop : MAJ LPAREN prop COMMA prop COMMA prop RPAREN"
+        p[0] = Or(And(p[3], p[5]), And(p[7], Or(p[3], p[5])))
+
     def p_term_id(self, p):
         "term : id"
         p[0] = p[1]
@@ -11,6 +11,10 @@ class PropParser(object):
             | ( * prop prop )
             | ( + prop prop )
             | ( & prop prop )
+            | ( ^ prop prop )
+            | ( xnor prop prop )
+            | ( mux prop prop prop )
+            | ( maj prop prop prop )
             | ( ! prop )
     """
 
@@ -70,6 +74,23 @@ class PropParser(object):
         "prop : LPAREN NOT prop RPAREN"
         p[0] = Not(p[3])
 
+    # ABC eqn has no XOR/MUX/MAJ, so they are expanded to AND/OR/NOT
+    def p_prop_xor(self, p):
+        "prop : LPAREN XOR prop prop RPAREN"
+        p[0] = Or(And(p[3], Not(p[4])), And(Not(p[3]), p[4]))
+
+    def p_prop_xnor(self, p):
+        "prop : LPAREN XNOR prop prop RPAREN"
+        p[0] = Or(And(p[3], p[4]), And(Not(p[3]), Not(p[4])))
+
+    def p_prop_mux(self, p):
+        "prop : LPAREN MUX prop prop prop RPAREN"
+        p[0] = Or(And(p[3], p[4]), And(Not(p[3]), p[5]))
+
+    def p_prop_maj(self, p):
+        "prop : LPAREN MAJ prop prop prop RPAREN"
+        p[0] = Or(And(p[3], p[4]), And(p[5], Or(p[3], p[4])))
+
     def p_term_id(self, p):
         "term : id"
         p[0] = p[1]